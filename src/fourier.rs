@@ -71,9 +71,13 @@ pub fn fft(arr: &mut [Complex<f64>]) {
     let n = arr.len();
     let order = (n as f32).log2().round() as u8;
 
-    // bit-reversal
+    // bit-reversal, on usize so that transforms longer than 256 points are permuted correctly
     for j in 0..n {
-        let nj = bit_reversed(j as u8, order) as usize;
+        let nj = if order == 0 {
+            j
+        } else {
+            j.reverse_bits() >> (usize::BITS - u32::from(order))
+        };
         if j < nj {
             arr.swap(j, nj);
         }
@@ -174,6 +178,37 @@ mod tests {
         assert_eq!(input, output);
     }
 
+    #[test]
+    fn test_fft_long() {
+        // longer than 256 points, so the bit-reversal needs more than 8 bits
+        let n = 512;
+        let signal: Vec<Complex<f64>> = (0..n)
+            .map(|j| {
+                let t = j as f64;
+                Complex::new((0.3 * t).sin() + 0.01 * t, (0.7 * t).cos())
+            })
+            .collect();
+        let dft: Vec<Complex<f64>> = (0..n)
+            .map(|k| {
+                signal
+                    .iter()
+                    .enumerate()
+                    .map(|(j, x)| {
+                        let phase = -2.0 * std::f64::consts::PI * (j * k % n) as f64 / n as f64;
+                        x * Complex::new(0.0, phase).exp()
+                    })
+                    .sum()
+            })
+            .collect();
+
+        let mut res = signal.clone();
+        fft(&mut res);
+
+        for (a, b) in res.iter().zip(dft.iter()) {
+            assert!((a - b).abs() < 1e-9 * n as f64);
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_fft_arr_length() {
@@ -1,7 +1,10 @@
 //! Evaluation algorithms for functions
 
+use ndarray::{Array1, Array2};
 use num::Complex;
 
+use crate::linalg::{eigen::eigenvalues, solve};
+
 /// Polynomial of degree N-1
 #[derive(Debug, Copy, Clone)]
 pub struct Polynomial<const N: usize> {
@@ -25,6 +28,11 @@ impl<const N: usize> Polynomial<N> {
         Polynomial { coeff: arr }
     }
 
+    /// Return the coefficients in ascending order
+    pub fn coeff(&self) -> &[f64; N] {
+        &self.coeff
+    }
+
     /// Evaluate polynomial at *x* using Horner's method
     ///
     /// # References
@@ -38,6 +46,116 @@ impl<const N: usize> Polynomial<N> {
 
         val
     }
+
+    /// Return the complex roots as the eigenvalues of the companion matrix, sorted by ascending
+    /// real part
+    ///
+    /// Leading zero coefficients are ignored, so the polynomial may be of lower degree than N-1.
+    ///
+    /// # Panics
+    /// Panics if the QR algorithm does not converge
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::function::Polynomial;
+    ///
+    /// // x^2 + 1
+    /// let poly = Polynomial::new([1.0, 0.0, 1.0]);
+    /// let roots = poly.roots();
+    ///
+    /// assert!(roots[0].re.abs() < 1e-12 && (roots[0].im.abs() - 1.0).abs() < 1e-12);
+    /// ```
+    ///
+    /// # References
+    ///  - [Wikipedia: Companion matrix](https://en.wikipedia.org/wiki/Companion_matrix)
+    pub fn roots(&self) -> Vec<Complex<f64>> {
+        let degree = match self.coeff.iter().rposition(|c| *c != 0.0) {
+            Some(degree) if degree > 0 => degree,
+            _ => return Vec::new(),
+        };
+
+        let mut companion = Array2::zeros((degree, degree));
+        for i in 0..degree {
+            if i > 0 {
+                companion[(i, i - 1)] = 1.0;
+            }
+            companion[(i, degree - 1)] = -self.coeff[i] / self.coeff[degree];
+        }
+
+        eigenvalues(&companion)
+            .expect("QR algorithm did not converge")
+            .to_vec()
+    }
+}
+
+/// Padé approximant *P(x) / Q(x)* with numerator degree *m* and denominator degree *n*
+#[derive(Debug, Clone)]
+pub struct Pade {
+    num: Vec<f64>,
+    den: Vec<f64>,
+}
+
+impl Pade {
+    /// Compute the [m/n] Padé approximant from the Taylor coefficients *taylor* of a function
+    /// around zero. The denominator is normalized to `Q(0) = 1`.
+    ///
+    /// Returns None if the approximant does not exist, i.e. the linear system for the
+    /// denominator is singular.
+    ///
+    /// # Panics
+    /// Panics if fewer than `m + n + 1` Taylor coefficients are given
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::function::Pade;
+    ///
+    /// // exp(x) = 1 + x + x^2 / 2 + x^3 / 6 + x^4 / 24 + ...
+    /// let taylor = [1.0, 1.0, 1.0 / 2.0, 1.0 / 6.0, 1.0 / 24.0];
+    /// let pade = Pade::new(&taylor, 2, 2).unwrap();
+    ///
+    /// assert!((pade.eval(1.0) - 1.0_f64.exp()).abs() < 5e-3);
+    /// ```
+    ///
+    /// # References
+    ///  - [Wikipedia: Padé approximant](https://en.wikipedia.org/wiki/Pad%C3%A9_approximant)
+    ///  - [Wolfram MathWorld: Padé Approximant](https://mathworld.wolfram.com/PadeApproximant.html)
+    pub fn new(taylor: &[f64], m: usize, n: usize) -> Option<Self> {
+        assert!(
+            taylor.len() > m + n,
+            "m + n + 1 Taylor coefficients are needed"
+        );
+        let c = |i: isize| if i < 0 { 0.0 } else { taylor[i as usize] };
+
+        // sum_j q_j c_{m+k-j} = -c_{m+k} for k = 1..n
+        let mat = Array2::from_shape_fn((n, n), |(k, j)| c((m + k) as isize - j as isize));
+        let rhs = Array1::from_shape_fn(n, |k| -c((m + k + 1) as isize));
+
+        let mut den = vec![1.0];
+        den.extend(solve(&mat, &rhs).ok()?);
+
+        let num = (0..=m)
+            .map(|i| (0..=i.min(n)).map(|j| den[j] * taylor[i - j]).sum())
+            .collect();
+
+        Some(Pade { num, den })
+    }
+
+    /// Return the coefficients of the numerator in ascending order
+    pub fn numerator(&self) -> &[f64] {
+        &self.num
+    }
+
+    /// Return the coefficients of the denominator in ascending order
+    pub fn denominator(&self) -> &[f64] {
+        &self.den
+    }
+
+    /// Evaluate the approximant at *x*
+    pub fn eval(&self, x: f64) -> f64 {
+        let horner = |coeff: &[f64]| coeff.iter().rev().fold(0.0, |acc, c| c + x * acc);
+
+        horner(&self.num) / horner(&self.den)
+    }
 }
 
 /// Calcuate the gamma function of z0 using the Lanczos approximation
@@ -241,3 +359,79 @@ pub fn cos(x: f64, iter: i32) -> f64 {
 
     y
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polynomial_roots() {
+        // (x - 1)(x + 2)(x - 3)(x^2 + 2x + 5) with roots -1 +- 2i
+        let poly = Polynomial::new([30.0, -13.0, -14.0, -4.0, 0.0, 1.0]);
+        let roots = poly.roots();
+
+        let expected = [
+            Complex::new(-2.0, 0.0),
+            Complex::new(-1.0, -2.0),
+            Complex::new(-1.0, 2.0),
+            Complex::new(1.0, 0.0),
+            Complex::new(3.0, 0.0),
+        ];
+        assert_eq!(roots.len(), 5);
+        for (r, e) in roots.iter().zip(expected) {
+            assert!((r - e).norm() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_polynomial_roots_lower_degree() {
+        // 2x - 4 with a vanishing leading coefficient
+        let roots = Polynomial::new([-4.0, 2.0, 0.0]).roots();
+        assert_eq!(roots.len(), 1);
+        assert!((roots[0] - Complex::new(2.0, 0.0)).norm() < 1e-12);
+
+        assert!(Polynomial::new([3.0, 0.0]).roots().is_empty());
+    }
+
+    #[test]
+    fn test_pade_exp() {
+        let mut taylor = vec![1.0];
+        for i in 1..7 {
+            taylor.push(taylor[i - 1] / i as f64);
+        }
+
+        // [2/2]: (1 + x/2 + x^2/12) / (1 - x/2 + x^2/12)
+        let pade = Pade::new(&taylor, 2, 2).unwrap();
+        let expected_num = [1.0, 0.5, 1.0 / 12.0];
+        let expected_den = [1.0, -0.5, 1.0 / 12.0];
+        for i in 0..3 {
+            assert!((pade.numerator()[i] - expected_num[i]).abs() < 1e-12);
+            assert!((pade.denominator()[i] - expected_den[i]).abs() < 1e-12);
+        }
+
+        let pade = Pade::new(&taylor, 3, 3).unwrap();
+        assert!((pade.eval(-1.0) - (-1.0_f64).exp()).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_pade_zero_diagonal() {
+        // sin(x) has vanishing even coefficients, which needs pivoting for [2/2]
+        let taylor = [0.0, 1.0, 0.0, -1.0 / 6.0, 0.0, 1.0 / 120.0];
+        assert!(Pade::new(&taylor, 2, 2).is_some());
+
+        let pade = Pade::new(&taylor, 3, 2).unwrap();
+
+        // x (1 - 7x^2/60) / (1 + x^2/20)
+        assert!((pade.eval(0.5) - 0.5 * (1.0 - 7.0 / 240.0) / (1.0 + 1.0 / 80.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pade_polynomial() {
+        // [m/0] is the truncated Taylor series
+        let taylor = [1.0, 2.0, 3.0];
+        let pade = Pade::new(&taylor, 2, 0).unwrap();
+
+        assert_eq!(pade.denominator(), &[1.0]);
+        assert_eq!(pade.eval(2.0), 17.0);
+    }
+}
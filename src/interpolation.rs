@@ -1,7 +1,16 @@
-//! Interpolation (and extrapolation) of errorless datasets
+//! Interpolation (and extrapolation) of errorless datasets and smoothing of noisy ones
+//!
+//! One-dimensional methods live in this module, multidimensional grids and scattered data
+//! are handled in [`grid`] and [`scattered`].
+
+pub mod bspline;
+pub mod grid;
+pub mod rational;
+pub mod scattered;
 
 use num::Float;
 
+use crate::linalg::banded::{solve_cyclic_tridiagonal, solve_tridiagonal};
 use crate::{function::Polynomial, sort::co_sort};
 
 /// Interpolate a dataset *f(xs)->ys* at point *x* using Neville interpolation
@@ -32,6 +41,127 @@ pub fn neville<F: Float>(xs: &[F], ys: &[F], x: F) -> F {
     q[0]
 }
 
+/// Interpolate a dataset *f(xs)->ys* at point *x* using Neville interpolation and return the
+/// interpolated value together with an error estimate
+///
+/// The error estimate is the last correction applied while walking through the Neville tableau.
+/// This is the quantity used to decide on convergence in Richardson-style extrapolation.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::neville_with_error;
+///
+/// let xs = vec![0.0, 0.5, 1.0, 1.5, 2.0];
+/// let ys: Vec<f64> = xs.iter().map(|x: &f64| x.exp()).collect();
+///
+/// let (y, dy) = neville_with_error(&xs, &ys, 0.75);
+///
+/// assert!((y - 0.75_f64.exp()).abs() < 1e-3);
+/// assert!(dy.abs() < 1e-2);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Neville's Algorithm](https://en.wikipedia.org/wiki/Neville%27s_algorithm)
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 3.2
+pub fn neville_with_error<F: Float>(xs: &[F], ys: &[F], x: F) -> (F, F) {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+
+    let mut ns = 0;
+    let mut dif = (x - xs[0]).abs();
+    for (i, xi) in xs.iter().enumerate() {
+        let dift = (x - *xi).abs();
+        if dift < dif {
+            ns = i;
+            dif = dift;
+        }
+    }
+
+    // c and d are the differences between neighbouring entries of the tableau
+    let mut c = ys.to_vec();
+    let mut d = ys.to_vec();
+    let mut y = ys[ns];
+    let mut dy = F::zero();
+    let mut ns = ns as isize - 1;
+
+    for m in 1..n {
+        for i in 0..n - m {
+            let ho = xs[i] - x;
+            let hp = xs[i + m] - x;
+            let den = (c[i + 1] - d[i]) / (ho - hp);
+            d[i] = hp * den;
+            c[i] = ho * den;
+        }
+
+        dy = if 2 * (ns + 1) < (n - m) as isize {
+            c[(ns + 1) as usize]
+        } else {
+            let v = d[ns as usize];
+            ns -= 1;
+            v
+        };
+        y = y + dy;
+    }
+
+    (y, dy)
+}
+
+/// Return the coefficients of the polynomial of degree N-1 through the N points *(xs, ys)*
+///
+/// # Panics
+/// Panics if N is zero or *xs* or *ys* do not contain exactly N points
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::polynomial_coefficients;
+///
+/// let xs = [-1.0, 0.0, 1.0];
+/// let ys = [2.0, 1.0, 2.0];
+///
+/// let poly = polynomial_coefficients::<3>(&xs, &ys);
+///
+/// assert_eq!(poly.coeff(), &[1.0, 0.0, 1.0]);
+/// assert_eq!(poly.eval(2.0), 5.0);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Polynomial interpolation](https://en.wikipedia.org/wiki/Polynomial_interpolation)
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 3.5
+pub fn polynomial_coefficients<const N: usize>(xs: &[f64], ys: &[f64]) -> Polynomial<N> {
+    assert!(N > 0, "at least one point is needed");
+    assert_eq!(xs.len(), N);
+    assert_eq!(ys.len(), N);
+
+    // coefficients of the master polynomial prod_j (x - xs[j])
+    let mut s = [0.0; N];
+    let mut coeff = [0.0; N];
+    s[N - 1] = -xs[0];
+    for (i, xi) in xs.iter().enumerate().skip(1) {
+        for j in N - 1 - i..N - 1 {
+            s[j] -= xi * s[j + 1];
+        }
+        s[N - 1] -= xi;
+    }
+
+    for (xj, yj) in xs.iter().zip(ys.iter()) {
+        // derivative of the master polynomial at xj
+        let mut phi = N as f64;
+        for k in (1..N).rev() {
+            phi = k as f64 * s[k] + xj * phi;
+        }
+
+        // synthetic division by (x - xj)
+        let ff = yj / phi;
+        let mut b = 1.0;
+        for k in (0..N).rev() {
+            coeff[k] += b * ff;
+            b = s[k] + xj * b;
+        }
+    }
+
+    Polynomial::new(coeff)
+}
+
 /// Linear spline interpolation
 pub struct LinearSplines {
     xs: Vec<f64>,
@@ -63,87 +193,368 @@ impl LinearSplines {
     }
 
     pub fn eval(&self, x: f64) -> f64 {
-        let idx = self.xs.binary_search_by(|v| v.partial_cmp(&x).unwrap());
-
-        match idx {
-            Ok(i) => self.ys[i],
-            Err(i) => self.splines[i - 1].eval(x - self.xs[i - 1]),
-        }
+        eval_piecewise(&self.xs, &self.ys, &self.splines, x)
     }
 }
 
-/// Natural cubic spline interpolation
+/// Boundary condition of a cubic spline
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum BoundaryCondition {
+    /// Vanishing second derivative at both ends
+    Natural,
+    /// Given first derivatives *(dy0, dyn)* at both ends
+    Clamped(f64, f64),
+    /// Continuous third derivative at the second and second to last knot
+    NotAKnot,
+    /// Periodic function with matching first and second derivatives at both ends.
+    /// Requires `ys[0] == ys[n - 1]`.
+    Periodic,
+}
+
+/// Cubic spline interpolation
 pub struct CubicSplines {
     xs: Vec<f64>,
     ys: Vec<f64>,
     splines: Vec<Polynomial<4>>,
+    periodic: bool,
 }
 
 impl CubicSplines {
+    /// Create a natural cubic spline through *(xs, ys)*
     pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        Self::with_boundary(xs, ys, BoundaryCondition::Natural)
+    }
+
+    /// Create a cubic spline through *(xs, ys)* with boundary condition *bc*
+    ///
+    /// # Panics
+    /// Panics if *xs* and *ys* differ in length, if there are fewer than two points,
+    /// or fewer than four points for [`BoundaryCondition::NotAKnot`] and [`BoundaryCondition::Periodic`].
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::interpolation::{BoundaryCondition, CubicSplines};
+    ///
+    /// let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+    /// let ys: Vec<f64> = xs.iter().map(|x| x * x * x).collect();
+    ///
+    /// let spline = CubicSplines::with_boundary(&xs, &ys, BoundaryCondition::Clamped(0.0, 48.0));
+    ///
+    /// assert!((spline.eval(2.5) - 15.625).abs() < 1e-10);
+    /// ```
+    ///
+    /// # References
+    ///  - [Wikipedia: Spline interpolation](https://en.wikipedia.org/wiki/Spline_interpolation)
+    ///  - [Wolfram MathWorld: Cubic Spline](https://mathworld.wolfram.com/CubicSpline.html)
+    pub fn with_boundary(xs: &[f64], ys: &[f64], bc: BoundaryCondition) -> Self {
+        assert_eq!(xs.len(), ys.len());
         let n = xs.len();
-        let a = ys.to_vec();
-        let mut b = vec![0.0; n - 1];
-        let mut d = vec![0.0; n - 1];
+        assert!(n >= 2, "at least two points are needed");
 
+        let a = ys.to_vec();
         let h: Vec<f64> = xs.windows(2).map(|x| x[1] - x[0]).collect();
-        let mut alpha: Vec<f64> = vec![0.0; n - 1];
-        for i in 1..n - 1 {
-            alpha[i] = 3.0 / h[i] * (a[i + 1] - a[i]) - 3.0 / h[i - 1] * (a[i] - a[i - 1]);
+        let slope: Vec<f64> = (0..n - 1).map(|i| (a[i + 1] - a[i]) / h[i]).collect();
+
+        // c[i] is half the second derivative at xs[i]
+        let c = match bc {
+            BoundaryCondition::Natural => {
+                let mut sub = vec![0.0; n];
+                let mut diag = vec![1.0; n];
+                let mut sup = vec![0.0; n];
+                let mut rhs = vec![0.0; n];
+                for i in 1..n - 1 {
+                    sub[i] = h[i - 1];
+                    diag[i] = 2.0 * (h[i - 1] + h[i]);
+                    sup[i] = h[i];
+                    rhs[i] = 3.0 * (slope[i] - slope[i - 1]);
+                }
+                solve_tridiagonal(&sub[1..], &diag, &sup[..n - 1], &rhs)
+                    .expect("abscissas must be strictly increasing")
+            }
+            BoundaryCondition::Clamped(dy0, dyn_) => {
+                let mut sub = vec![0.0; n];
+                let mut diag = vec![0.0; n];
+                let mut sup = vec![0.0; n];
+                let mut rhs = vec![0.0; n];
+                diag[0] = 2.0 * h[0];
+                sup[0] = h[0];
+                rhs[0] = 3.0 * (slope[0] - dy0);
+                for i in 1..n - 1 {
+                    sub[i] = h[i - 1];
+                    diag[i] = 2.0 * (h[i - 1] + h[i]);
+                    sup[i] = h[i];
+                    rhs[i] = 3.0 * (slope[i] - slope[i - 1]);
+                }
+                sub[n - 1] = h[n - 2];
+                diag[n - 1] = 2.0 * h[n - 2];
+                rhs[n - 1] = 3.0 * (dyn_ - slope[n - 2]);
+                solve_tridiagonal(&sub[1..], &diag, &sup[..n - 1], &rhs)
+                    .expect("abscissas must be strictly increasing")
+            }
+            BoundaryCondition::NotAKnot => {
+                assert!(n >= 4, "not-a-knot splines need at least four points");
+
+                // eliminate c[0] and c[n - 1] using the continuity of the third derivative
+                let m = n - 2;
+                let mut sub = vec![0.0; m];
+                let mut diag = vec![0.0; m];
+                let mut sup = vec![0.0; m];
+                let mut rhs = vec![0.0; m];
+                for k in 0..m {
+                    let i = k + 1;
+                    sub[k] = h[i - 1];
+                    diag[k] = 2.0 * (h[i - 1] + h[i]);
+                    sup[k] = h[i];
+                    rhs[k] = 3.0 * (slope[i] - slope[i - 1]);
+                }
+                diag[0] += h[0] * (h[0] + h[1]) / h[1];
+                sup[0] -= h[0] * h[0] / h[1];
+                diag[m - 1] += h[n - 2] * (h[n - 2] + h[n - 3]) / h[n - 3];
+                sub[m - 1] -= h[n - 2] * h[n - 2] / h[n - 3];
+
+                let inner = solve_tridiagonal(&sub[1..], &diag, &sup[..m - 1], &rhs)
+                    .expect("abscissas must be strictly increasing");
+                let mut c = vec![0.0; n];
+                c[1..n - 1].copy_from_slice(&inner);
+                c[0] = ((h[0] + h[1]) * c[1] - h[0] * c[2]) / h[1];
+                c[n - 1] = ((h[n - 2] + h[n - 3]) * c[n - 2] - h[n - 2] * c[n - 3]) / h[n - 3];
+                c
+            }
+            BoundaryCondition::Periodic => {
+                assert!(n >= 4, "periodic splines need at least four points");
+                assert!(
+                    (a[0] - a[n - 1]).abs() <= 1e-12 * a[0].abs().max(1.0),
+                    "periodic splines need ys[0] == ys[n - 1]"
+                );
+
+                // c[n - 1] == c[0], which closes the system cyclically
+                let m = n - 1;
+                let mut sub = vec![0.0; m];
+                let mut diag = vec![0.0; m];
+                let mut sup = vec![0.0; m];
+                let mut rhs = vec![0.0; m];
+                diag[0] = 2.0 * (h[n - 2] + h[0]);
+                sup[0] = h[0];
+                rhs[0] = 3.0 * (slope[0] - slope[n - 2]);
+                for i in 1..m {
+                    sub[i] = h[i - 1];
+                    diag[i] = 2.0 * (h[i - 1] + h[i]);
+                    sup[i] = h[i];
+                    rhs[i] = 3.0 * (slope[i] - slope[i - 1]);
+                }
+
+                let mut c = solve_cyclic_tridiagonal(
+                    &sub[1..],
+                    &diag,
+                    &sup[..m - 1],
+                    h[n - 2],
+                    h[n - 2],
+                    &rhs,
+                )
+                .expect("abscissas must be strictly increasing");
+                c.push(c[0]);
+                c
+            }
+        };
+
+        let mut splines = Vec::new();
+        for i in 0..n - 1 {
+            let b = slope[i] - h[i] * (c[i + 1] + 2.0 * c[i]) / 3.0;
+            let d = (c[i + 1] - c[i]) / 3.0 / h[i];
+            splines.push(Polynomial::new([a[i], b, c[i], d]));
         }
 
-        let mut c = vec![0.0; n];
-        let mut l = vec![0.0; n];
-        let mut mu = vec![0.0; n];
-        let mut z = vec![0.0; n];
-        l[0] = 1.0;
-        mu[0] = 0.0;
-        z[0] = 0.0;
+        CubicSplines {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            splines,
+            periodic: bc == BoundaryCondition::Periodic,
+        }
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        let x = if self.periodic {
+            let x0 = self.xs[0];
+            let period = self.xs[self.xs.len() - 1] - x0;
+            x0 + (x - x0).rem_euclid(period)
+        } else {
+            x
+        };
+
+        eval_piecewise(&self.xs, &self.ys, &self.splines, x)
+    }
+}
+
+/// Shape-preserving piecewise cubic Hermite interpolation (PCHIP)
+///
+/// The derivatives at the knots are chosen with the Fritsch-Carlson conditions such that
+/// the interpolant is monotone wherever the data is monotone and does not overshoot local extrema.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::MonotoneCubicSplines;
+///
+/// let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+/// let ys = vec![0.0, 0.0, 1.0, 1.0, 1.0];
+///
+/// let spline = MonotoneCubicSplines::new(&xs, &ys);
+///
+/// for i in 0..=400 {
+///     let y = spline.eval(i as f64 * 0.01);
+///     assert!((0.0..=1.0).contains(&y));
+/// }
+/// ```
+///
+/// # References
+///  - [Wikipedia: Monotone cubic interpolation](https://en.wikipedia.org/wiki/Monotone_cubic_interpolation)
+///  - F. N. Fritsch and R. E. Carlson, *Monotone Piecewise Cubic Interpolation*, SIAM J. Numer. Anal. 17 (1980)
+pub struct MonotoneCubicSplines {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    splines: Vec<Polynomial<4>>,
+}
+
+impl MonotoneCubicSplines {
+    /// # Panics
+    /// Panics if *xs* and *ys* differ in length or if there are fewer than two points
+    pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        let n = xs.len();
+        assert!(n >= 2, "at least two points are needed");
 
-        for i in 1..n - 1 {
-            l[i] = 2.0 * (xs[i + 1] - xs[i - 1]) - h[i - 1] * mu[i - 1];
-            mu[i] = h[i] / l[i];
-            z[i] = (alpha[i] - h[i - 1] * z[i - 1]) / l[i];
+        let h: Vec<f64> = xs.windows(2).map(|x| x[1] - x[0]).collect();
+        let slope: Vec<f64> = (0..n - 1).map(|i| (ys[i + 1] - ys[i]) / h[i]).collect();
+
+        let mut dys = vec![0.0; n];
+        if n == 2 {
+            dys[0] = slope[0];
+            dys[1] = slope[0];
+        } else {
+            // weighted harmonic mean of the neighbouring slopes
+            for i in 1..n - 1 {
+                if slope[i - 1] * slope[i] > 0.0 {
+                    let w1 = 2.0 * h[i] + h[i - 1];
+                    let w2 = h[i] + 2.0 * h[i - 1];
+                    dys[i] = (w1 + w2) / (w1 / slope[i - 1] + w2 / slope[i]);
+                }
+            }
+            dys[0] = pchip_end_derivative(h[0], h[1], slope[0], slope[1]);
+            dys[n - 1] = pchip_end_derivative(h[n - 2], h[n - 3], slope[n - 2], slope[n - 3]);
         }
-        l[n - 1] = 1.0;
-        z[n - 1] = 0.0;
-        c[n - 1] = 0.0;
 
-        for i in (0..n - 1).rev() {
-            c[i] = z[i] - mu[i] * c[i + 1];
-            b[i] = (a[i + 1] - a[i]) / h[i] - h[i] * (c[i + 1] + 2.0 * c[i]) / 3.0;
-            d[i] = (c[i + 1] - c[i]) / 3.0 / h[i];
+        MonotoneCubicSplines {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            splines: hermite_splines(xs, ys, &dys),
         }
+    }
 
-        let mut vec_splices = Vec::new();
-        for i in 0..n - 1 {
-            let mut coeffs: Vec<f64> = vec![0.0; 4];
-            coeffs[0] = a[i];
-            coeffs[1] = b[i];
-            coeffs[2] = c[i];
-            coeffs[3] = d[i];
+    pub fn eval(&self, x: f64) -> f64 {
+        eval_piecewise(&self.xs, &self.ys, &self.splines, x)
+    }
+}
 
-            let splice: Polynomial<4> = Polynomial::from_slice(&coeffs[..]);
-            vec_splices.push(splice);
-        }
+/// Akima spline interpolation
+///
+/// The derivatives at the knots are weighted averages of the neighbouring slopes, which
+/// suppresses the wiggles natural cubic splines show next to outliers and steps.
+///
+/// # References
+///  - [Wikipedia: Akima spline](https://en.wikipedia.org/wiki/Akima_spline)
+///  - H. Akima, *A New Method of Interpolation and Smooth Curve Fitting Based on Local Procedures*, J. ACM 17 (1970)
+pub struct AkimaSplines {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    splines: Vec<Polynomial<4>>,
+}
 
-        CubicSplines {
+impl AkimaSplines {
+    /// # Panics
+    /// Panics if *xs* and *ys* differ in length or if there are fewer than three points
+    pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        let n = xs.len();
+        assert!(n >= 3, "at least three points are needed");
+
+        // slopes padded with two extrapolated values on each side
+        let mut m = vec![0.0; n + 3];
+        for i in 0..n - 1 {
+            m[i + 2] = (ys[i + 1] - ys[i]) / (xs[i + 1] - xs[i]);
+        }
+        m[1] = 2.0 * m[2] - m[3];
+        m[0] = 2.0 * m[1] - m[2];
+        m[n + 1] = 2.0 * m[n] - m[n - 1];
+        m[n + 2] = 2.0 * m[n + 1] - m[n];
+
+        let dys: Vec<f64> = (0..n)
+            .map(|i| {
+                let w1 = (m[i + 3] - m[i + 2]).abs();
+                let w2 = (m[i + 1] - m[i]).abs();
+                if w1 + w2 == 0.0 {
+                    0.5 * (m[i + 1] + m[i + 2])
+                } else {
+                    (w1 * m[i + 1] + w2 * m[i + 2]) / (w1 + w2)
+                }
+            })
+            .collect();
+
+        AkimaSplines {
             xs: xs.to_vec(),
             ys: ys.to_vec(),
-            splines: vec_splices,
+            splines: hermite_splines(xs, ys, &dys),
         }
     }
 
     pub fn eval(&self, x: f64) -> f64 {
-        let idx = self.xs.binary_search_by(|v| v.partial_cmp(&x).unwrap());
+        eval_piecewise(&self.xs, &self.ys, &self.splines, x)
+    }
+}
 
-        match idx {
-            Ok(i) => self.ys[i],
-            Err(i) => self.splines[i - 1].eval(x - self.xs[i - 1]),
+/// Evaluate piecewise polynomials *splines* defined on the intervals of *xs* at *x*.
+/// Points outside of *xs* are extrapolated with the first or last polynomial.
+fn eval_piecewise<const N: usize>(
+    xs: &[f64],
+    ys: &[f64],
+    splines: &[Polynomial<N>],
+    x: f64,
+) -> f64 {
+    let idx = xs.binary_search_by(|v| v.partial_cmp(&x).unwrap());
+
+    match idx {
+        Ok(i) => ys[i],
+        Err(i) => {
+            let i = i.clamp(1, splines.len());
+            splines[i - 1].eval(x - xs[i - 1])
         }
     }
 }
 
+/// Convert values *ys* and derivatives *dys* at *xs* into cubic Hermite polynomials
+fn hermite_splines(xs: &[f64], ys: &[f64], dys: &[f64]) -> Vec<Polynomial<4>> {
+    (0..xs.len() - 1)
+        .map(|i| {
+            let h = xs[i + 1] - xs[i];
+            let slope = (ys[i + 1] - ys[i]) / h;
+            let c = (3.0 * slope - 2.0 * dys[i] - dys[i + 1]) / h;
+            let d = (dys[i] + dys[i + 1] - 2.0 * slope) / (h * h);
+            Polynomial::new([ys[i], dys[i], c, d])
+        })
+        .collect()
+}
+
+/// Shape-preserving three-point estimate of the derivative at an end point
+fn pchip_end_derivative(h0: f64, h1: f64, m0: f64, m1: f64) -> f64 {
+    let d = ((2.0 * h0 + h1) * m0 - h0 * m1) / (h0 + h1);
+
+    if d.signum() != m0.signum() || m0 == 0.0 {
+        0.0
+    } else if m0.signum() != m1.signum() && d.abs() > 3.0 * m0.abs() {
+        3.0 * m0
+    } else {
+        d
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use ndarray::Array1;
@@ -155,7 +566,7 @@ mod tests {
         let xs = Array1::linspace(0.0, 4.0 * std::f64::consts::PI, 10);
         let ys = xs.map(|x| f64::sin(*x));
 
-        let linear_spline = LinearSplines::new(&xs.as_slice().unwrap(), &ys.as_slice().unwrap());
+        let linear_spline = LinearSplines::new(xs.as_slice().unwrap(), ys.as_slice().unwrap());
 
         for i in 0..6000 {
             let x: f64 = i as f64 * 0.001;
@@ -168,11 +579,156 @@ mod tests {
         let xs = Array1::linspace(0.0, 4.0 * std::f64::consts::PI, 8);
         let ys = xs.map(|x| f64::sin(*x));
 
-        let cubic_spline = CubicSplines::new(&xs.as_slice().unwrap(), &ys.as_slice().unwrap());
+        let cubic_spline = CubicSplines::new(xs.as_slice().unwrap(), ys.as_slice().unwrap());
 
         for i in 0..6000 {
             let x: f64 = i as f64 * 0.001;
             println!("{} {}", x, cubic_spline.eval(x));
         }
     }
+
+    #[test]
+    fn test_cubic_splines_natural() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0];
+        let ys = vec![0.0, 1.0, 0.0, 1.0];
+
+        let spline = CubicSplines::new(&xs, &ys);
+
+        // second derivative vanishes at both ends
+        let h = 1e-4;
+        let d2 = |x: f64| (spline.eval(x + h) - 2.0 * spline.eval(x) + spline.eval(x - h)) / h / h;
+        assert!(d2(h).abs() < 1e-2);
+        assert!(d2(3.0 - h).abs() < 1e-2);
+        assert!((spline.eval(1.5) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cubic_splines_reproduce_cubic() {
+        let f = |x: f64| 2.0 * x * x * x - x * x + 3.0 * x - 1.0;
+        let xs = vec![-1.0, -0.2, 0.5, 1.0, 2.5, 3.0];
+        let ys: Vec<f64> = xs.iter().map(|x| f(*x)).collect();
+
+        let clamped = CubicSplines::with_boundary(&xs, &ys, BoundaryCondition::Clamped(11.0, 51.0));
+        let not_a_knot = CubicSplines::with_boundary(&xs, &ys, BoundaryCondition::NotAKnot);
+
+        for i in 0..=40 {
+            let x = -1.0 + i as f64 * 0.1;
+            assert!((clamped.eval(x) - f(x)).abs() < 1e-10);
+            assert!((not_a_knot.eval(x) - f(x)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_cubic_splines_periodic() {
+        let two_pi = 2.0 * std::f64::consts::PI;
+        let xs = Array1::linspace(0.0, two_pi, 17);
+        let mut ys = xs.map(|x| f64::sin(*x));
+        ys[16] = ys[0];
+
+        let spline = CubicSplines::with_boundary(
+            xs.as_slice().unwrap(),
+            ys.as_slice().unwrap(),
+            BoundaryCondition::Periodic,
+        );
+
+        for i in 0..100 {
+            let x = i as f64 * 0.1;
+            assert!((spline.eval(x) - x.sin()).abs() < 1e-3);
+        }
+
+        // slope matches across the periodic boundary
+        let h = 1e-6;
+        let left = (spline.eval(two_pi) - spline.eval(two_pi - h)) / h;
+        let right = (spline.eval(h) - spline.eval(0.0)) / h;
+        assert!((left - right).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_monotone_cubic_splines() {
+        let xs = vec![0.0, 1.0, 2.0, 2.5, 4.0, 6.0];
+        let ys = vec![0.0, 0.1, 0.2, 5.0, 5.1, 5.2];
+
+        let spline = MonotoneCubicSplines::new(&xs, &ys);
+
+        let mut last = spline.eval(0.0);
+        for i in 1..=600 {
+            let y = spline.eval(i as f64 * 0.01);
+            assert!(y >= last - 1e-12);
+            assert!((0.0..=5.2 + 1e-12).contains(&y));
+            last = y;
+        }
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            assert!((spline.eval(*x) - y).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_akima_splines() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let ys = vec![1.0, 1.0, 1.0, 2.0, 3.0, 3.0, 3.0];
+
+        let spline = AkimaSplines::new(&xs, &ys);
+
+        // flat parts stay flat
+        for i in 0..=100 {
+            let x = i as f64 * 0.01;
+            assert!((spline.eval(x) - 1.0).abs() < 1e-12);
+            assert!((spline.eval(x + 5.0) - 3.0).abs() < 1e-12);
+        }
+
+        // straight lines are reproduced
+        let ys: Vec<f64> = xs.iter().map(|x| 0.5 * x - 1.0).collect();
+        let spline = AkimaSplines::new(&xs, &ys);
+        assert!((spline.eval(2.7) - 0.35).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_neville_with_error() {
+        let xs = vec![1.0, 2.0, 3.0, 4.0];
+        let ys = vec![1.0, 8.0, 27.0, 64.0];
+
+        // exact for polynomials up to degree N-1, the value agrees with neville
+        let (y, dy) = neville_with_error(&xs, &ys, 2.5);
+        assert!((y - 15.625).abs() < 1e-12);
+        assert!((y - neville(&xs, &ys, 2.5)).abs() < 1e-12);
+        assert!(dy.abs() < 1.0);
+
+        let (y, dy) = neville_with_error(&xs, &ys, 3.0);
+        assert_eq!(y, 27.0);
+        assert_eq!(dy, 0.0);
+    }
+
+    #[test]
+    fn test_neville_error_estimate() {
+        let xs: Vec<f64> = (0..6).map(|i| i as f64 * 0.3).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x.sin()).collect();
+
+        let (y, dy) = neville_with_error(&xs, &ys, 0.8);
+        let err = (y - 0.8_f64.sin()).abs();
+
+        assert!(err < 1e-4);
+        assert!(err < 10.0 * dy.abs());
+    }
+
+    #[test]
+    fn test_polynomial_coefficients() {
+        let xs = [-2.0, -0.5, 1.0, 3.0, 4.0];
+        let ys: Vec<f64> = xs
+            .iter()
+            .map(|x| 2.0 - x + 0.5 * x * x - 3.0 * x * x * x + x * x * x * x)
+            .collect();
+
+        let poly = polynomial_coefficients::<5>(&xs, &ys);
+        let expected = [2.0, -1.0, 0.5, -3.0, 1.0];
+
+        for (c, e) in poly.coeff().iter().zip(expected.iter()) {
+            assert!((c - e).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one point is needed")]
+    fn test_polynomial_coefficients_empty() {
+        polynomial_coefficients::<0>(&[], &[]);
+    }
 }
@@ -0,0 +1,614 @@
+//! B-splines and smoothing splines for noisy datasets
+
+use ndarray::prelude::*;
+
+use crate::linalg::cholesky::Cholesky;
+
+/// Spline of degree *k* in B-spline representation
+///
+/// # References
+///  - [Wikipedia: B-spline](https://en.wikipedia.org/wiki/B-spline)
+///  - [Wikipedia: De Boor's algorithm](https://en.wikipedia.org/wiki/De_Boor%27s_algorithm)
+///  - C. de Boor, *A Practical Guide to Splines*, Springer (1978)
+#[derive(Debug, Clone)]
+pub struct BSpline {
+    knots: Vec<f64>,
+    coeffs: Vec<f64>,
+    degree: usize,
+}
+
+impl BSpline {
+    /// Create a B-spline from its full (non-decreasing) knot vector and coefficients
+    ///
+    /// # Panics
+    /// Panics if `knots.len() != coeffs.len() + degree + 1`
+    pub fn new(knots: &[f64], coeffs: &[f64], degree: usize) -> Self {
+        assert_eq!(
+            knots.len(),
+            coeffs.len() + degree + 1,
+            "number of knots does not match number of coefficients and degree"
+        );
+
+        BSpline {
+            knots: knots.to_vec(),
+            coeffs: coeffs.to_vec(),
+            degree,
+        }
+    }
+
+    /// Least squares fit of a B-spline of given *degree* to the dataset *(xs, ys)*
+    ///
+    /// The spline is defined on `[xs[0], xs[n - 1]]` with the user-chosen *interior_knots*.
+    /// The boundary knots are repeated `degree + 1` times.
+    /// Optional *weights* scale the squared residuals, e.g. `1 / sigma^2` for data with known errors.
+    ///
+    /// # Panics
+    /// Panics if *xs* or *interior_knots* are not sorted, if an interior knot lies outside of
+    /// `(xs[0], xs[n - 1])`, if there are fewer data points than coefficients or if a knot
+    /// interval contains no data
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::interpolation::bspline::BSpline;
+    ///
+    /// let xs: Vec<f64> = (0..50).map(|i| i as f64 * 0.1).collect();
+    /// let ys: Vec<f64> = xs.iter().map(|x| x * x - 2.0 * x).collect();
+    ///
+    /// let spline = BSpline::fit(&xs, &ys, None, &[1.0, 2.0, 3.0], 3);
+    ///
+    /// assert!((spline.eval(2.45) - (2.45 * 2.45 - 4.9)).abs() < 1e-10);
+    /// ```
+    pub fn fit(
+        xs: &[f64],
+        ys: &[f64],
+        weights: Option<&[f64]>,
+        interior_knots: &[f64],
+        degree: usize,
+    ) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        assert!(crate::sort::is_sorted(xs), "xs needs to be sorted");
+        assert!(
+            crate::sort::is_sorted(interior_knots),
+            "interior knots need to be sorted"
+        );
+        assert!(
+            interior_knots
+                .iter()
+                .all(|t| xs[0] < *t && *t < xs[xs.len() - 1]),
+            "interior knots need to lie inside the data range"
+        );
+
+        let knots = clamped_knots(xs[0], xs[xs.len() - 1], interior_knots, degree);
+        let m = knots.len() - degree - 1;
+        assert!(
+            xs.len() >= m,
+            "not enough data points for the number of knots"
+        );
+
+        let design = design_matrix(&knots, degree, xs);
+        let w = weight_vector(weights, xs.len());
+        let (normal, rhs) = normal_equations(&design, &w, ys);
+        let coeffs = Cholesky::new(&normal)
+            .expect("knots are not covered by enough data points")
+            .solve(&rhs);
+
+        BSpline::new(&knots, coeffs.as_slice().unwrap(), degree)
+    }
+
+    pub fn knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    pub fn coeffs(&self) -> &[f64] {
+        &self.coeffs
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Evaluate the spline at *x* using de Boor's algorithm.
+    /// Points outside of the knot span are extrapolated with the first or last polynomial piece.
+    pub fn eval(&self, x: f64) -> f64 {
+        let k = self.degree;
+        let t = &self.knots;
+        let mu = find_span(t, k, x);
+
+        let mut d: Vec<f64> = (0..=k).map(|j| self.coeffs[j + mu - k]).collect();
+        for r in 1..=k {
+            for j in (r..=k).rev() {
+                let left = t[j + mu - k];
+                let right = t[j + 1 + mu - r];
+                let alpha = (x - left) / (right - left);
+                d[j] = (1.0 - alpha) * d[j - 1] + alpha * d[j];
+            }
+        }
+
+        d[k]
+    }
+
+    /// Return the derivative of the spline, a spline of one degree less
+    ///
+    /// # Panics
+    /// Panics if the spline has degree zero
+    pub fn derivative(&self) -> Self {
+        let k = self.degree;
+        assert!(k > 0, "cannot differentiate a spline of degree zero");
+        let t = &self.knots;
+
+        let coeffs: Vec<f64> = (0..self.coeffs.len() - 1)
+            .map(|i| {
+                let dt = t[i + k + 1] - t[i + 1];
+                if dt == 0.0 {
+                    0.0
+                } else {
+                    k as f64 * (self.coeffs[i + 1] - self.coeffs[i]) / dt
+                }
+            })
+            .collect();
+
+        BSpline::new(&t[1..t.len() - 1], &coeffs, k - 1)
+    }
+}
+
+/// Evaluate all B-spline basis functions of *degree* over *knots* at *x*
+///
+/// Returns a vector of length `knots.len() - degree - 1`, of which at most `degree + 1`
+/// consecutive entries are non-zero.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::bspline::bspline_basis;
+///
+/// let knots = [0.0, 0.0, 0.0, 1.0, 2.0, 2.0, 2.0];
+/// let basis = bspline_basis(&knots, 2, 0.5);
+///
+/// assert_eq!(basis.len(), 4);
+/// assert!((basis.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+/// ```
+pub fn bspline_basis(knots: &[f64], degree: usize, x: f64) -> Vec<f64> {
+    let mut basis = vec![0.0; knots.len() - degree - 1];
+    let mu = find_span(knots, degree, x);
+
+    for (r, v) in nonzero_basis(knots, degree, mu, x).into_iter().enumerate() {
+        basis[mu - degree + r] = v;
+    }
+
+    basis
+}
+
+/// Cubic smoothing spline
+///
+/// Minimizes `sum_i w_i (y_i - s(x_i))^2 + lambda * integral s''(x)^2 dx` over all cubic splines
+/// with knots at the data points.
+/// If no smoothing parameter *lambda* is given, it is chosen by minimizing the generalized
+/// cross-validation score.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::bspline::SmoothingSpline;
+///
+/// let xs: Vec<f64> = (0..40).map(|i| i as f64 * 0.1).collect();
+/// let ys: Vec<f64> = xs
+///     .iter()
+///     .enumerate()
+///     .map(|(i, x)| x.sin() + if i % 2 == 0 { 0.05 } else { -0.05 })
+///     .collect();
+///
+/// let spline = SmoothingSpline::new(&xs, &ys, None, None);
+///
+/// assert!((spline.eval(2.0) - 2.0_f64.sin()).abs() < 0.03);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Smoothing spline](https://en.wikipedia.org/wiki/Smoothing_spline)
+///  - P. Craven and G. Wahba, *Smoothing noisy data with spline functions*, Numer. Math. 31 (1979)
+#[derive(Debug, Clone)]
+pub struct SmoothingSpline {
+    spline: BSpline,
+    lambda: f64,
+    gcv: f64,
+}
+
+impl SmoothingSpline {
+    /// # Panics
+    /// Panics if *xs* is not strictly increasing, if there are fewer than four points
+    /// or if *lambda* is not positive
+    pub fn new(xs: &[f64], ys: &[f64], weights: Option<&[f64]>, lambda: Option<f64>) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        let n = xs.len();
+        assert!(n >= 4, "at least four points are needed");
+        assert!(
+            xs.windows(2).all(|w| w[0] < w[1]),
+            "xs needs to be strictly increasing"
+        );
+
+        let degree = 3;
+        let knots = clamped_knots(xs[0], xs[n - 1], &xs[1..n - 1], degree);
+        let design = design_matrix(&knots, degree, xs);
+        let w = weight_vector(weights, n);
+        let (normal, rhs) = normal_equations(&design, &w, ys);
+        let penalty = penalty_matrix(&knots, degree);
+
+        let problem = SmoothingProblem {
+            design: &design,
+            w: &w,
+            ys,
+            normal: &normal,
+            rhs: &rhs,
+            penalty: &penalty,
+        };
+
+        let lambda = match lambda {
+            Some(lambda) => {
+                assert!(lambda > 0.0, "smoothing parameter needs to be positive");
+                lambda
+            }
+            None => problem.minimize_gcv(),
+        };
+        let (coeffs, gcv) = problem.solve(lambda);
+
+        SmoothingSpline {
+            spline: BSpline::new(&knots, coeffs.as_slice().unwrap(), degree),
+            lambda,
+            gcv,
+        }
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        self.spline.eval(x)
+    }
+
+    /// Return the underlying B-spline representation
+    pub fn spline(&self) -> &BSpline {
+        &self.spline
+    }
+
+    /// Return the smoothing parameter
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Return the generalized cross-validation score of the fit.
+    /// The score is infinite if the spline practically interpolates the data.
+    pub fn gcv(&self) -> f64 {
+        self.gcv
+    }
+}
+
+struct SmoothingProblem<'a> {
+    design: &'a Array2<f64>,
+    w: &'a Array1<f64>,
+    ys: &'a [f64],
+    normal: &'a Array2<f64>,
+    rhs: &'a Array1<f64>,
+    penalty: &'a Array2<f64>,
+}
+
+impl SmoothingProblem<'_> {
+    /// Return the coefficients and the GCV score for smoothing parameter *lambda*
+    fn solve(&self, lambda: f64) -> (Array1<f64>, f64) {
+        let n = self.ys.len() as f64;
+        let chol = Cholesky::new(&(self.normal + &(self.penalty * lambda)))
+            .expect("penalized normal equations are positive definite");
+        let coeffs = chol.solve(self.rhs);
+
+        let fitted = self.design.dot(&coeffs);
+        let rss: f64 = (0..self.ys.len())
+            .map(|i| self.w[i] * (self.ys[i] - fitted[i]).powi(2))
+            .sum();
+        let trace = chol.solve_many(self.normal).diag().sum();
+
+        // close to interpolation the residual and the denominator are both dominated by rounding
+        let dof = 1.0 - trace / n;
+        if dof < 1e-6 {
+            return (coeffs, f64::INFINITY);
+        }
+
+        (coeffs, rss / n / dof.powi(2))
+    }
+
+    /// Find the smoothing parameter with minimal GCV score by a coarse logarithmic scan
+    /// followed by a golden-section refinement
+    fn minimize_gcv(&self) -> f64 {
+        let scale = self.normal.diag().sum() / self.penalty.diag().sum();
+        let gcv = |p: f64| self.solve(scale * 10.0_f64.powf(p)).1;
+
+        let step = 0.5;
+        let mut best = -12.0;
+        let mut best_gcv = gcv(best);
+        let mut p = best + step;
+        while p <= 6.0 {
+            let g = gcv(p);
+            if g < best_gcv {
+                best = p;
+                best_gcv = g;
+            }
+            p += step;
+        }
+
+        let phi = 0.5 * (5.0_f64.sqrt() - 1.0);
+        let mut a = best - step;
+        let mut b = best + step;
+        let mut c = b - phi * (b - a);
+        let mut d = a + phi * (b - a);
+        let mut gc = gcv(c);
+        let mut gd = gcv(d);
+        while b - a > 1e-3 {
+            if gc < gd {
+                b = d;
+                d = c;
+                gd = gc;
+                c = b - phi * (b - a);
+                gc = gcv(c);
+            } else {
+                a = c;
+                c = d;
+                gc = gd;
+                d = a + phi * (b - a);
+                gd = gcv(d);
+            }
+        }
+
+        scale * 10.0_f64.powf(0.5 * (a + b))
+    }
+}
+
+/// Build a knot vector with boundary knots repeated `degree + 1` times
+fn clamped_knots(a: f64, b: f64, interior_knots: &[f64], degree: usize) -> Vec<f64> {
+    let mut knots = vec![a; degree + 1];
+    knots.extend_from_slice(interior_knots);
+    knots.extend(std::iter::repeat_n(b, degree + 1));
+
+    knots
+}
+
+/// Find the knot span index *mu* with `t[mu] <= x < t[mu + 1]`, restricted to the valid range
+fn find_span(t: &[f64], k: usize, x: f64) -> usize {
+    let n = t.len() - k - 1;
+
+    if x >= t[n] {
+        // last non-empty span
+        let mut mu = n - 1;
+        while mu > k && t[mu] == t[mu + 1] {
+            mu -= 1;
+        }
+        return mu;
+    }
+    if x <= t[k] {
+        let mut mu = k;
+        while mu < n - 1 && t[mu] == t[mu + 1] {
+            mu += 1;
+        }
+        return mu;
+    }
+
+    // t[lo] <= x < t[hi]
+    let mut lo = k;
+    let mut hi = n;
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if x < t[mid] {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    lo
+}
+
+/// Evaluate the `k + 1` basis functions which are non-zero on span *mu* at *x*
+fn nonzero_basis(t: &[f64], k: usize, mu: usize, x: f64) -> Vec<f64> {
+    let mut basis = vec![0.0; k + 1];
+    let mut left = vec![0.0; k + 1];
+    let mut right = vec![0.0; k + 1];
+    basis[0] = 1.0;
+
+    for j in 1..=k {
+        left[j] = x - t[mu + 1 - j];
+        right[j] = t[mu + j] - x;
+        let mut saved = 0.0;
+        for r in 0..j {
+            let temp = basis[r] / (right[r + 1] + left[j - r]);
+            basis[r] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        basis[j] = saved;
+    }
+
+    basis
+}
+
+fn design_matrix(knots: &[f64], degree: usize, xs: &[f64]) -> Array2<f64> {
+    let m = knots.len() - degree - 1;
+    let mut design = Array2::zeros((xs.len(), m));
+
+    for (i, x) in xs.iter().enumerate() {
+        let mu = find_span(knots, degree, *x);
+        for (r, v) in nonzero_basis(knots, degree, mu, *x).into_iter().enumerate() {
+            design[(i, mu - degree + r)] = v;
+        }
+    }
+
+    design
+}
+
+fn weight_vector(weights: Option<&[f64]>, n: usize) -> Array1<f64> {
+    match weights {
+        Some(w) => {
+            assert_eq!(w.len(), n);
+            Array1::from(w.to_vec())
+        }
+        None => Array1::ones(n),
+    }
+}
+
+/// Return `B^T W B` and `B^T W y`
+fn normal_equations(
+    design: &Array2<f64>,
+    w: &Array1<f64>,
+    ys: &[f64],
+) -> (Array2<f64>, Array1<f64>) {
+    let weighted = design * &w.view().insert_axis(Axis(1));
+    let ys = Array1::from(ys.to_vec());
+
+    (weighted.t().dot(design), weighted.t().dot(&ys))
+}
+
+/// Gram matrix of the second derivatives of the basis functions, `integral B_i'' B_j'' dx`
+fn penalty_matrix(knots: &[f64], degree: usize) -> Array2<f64> {
+    let m = knots.len() - degree - 1;
+    let second: Vec<BSpline> = (0..m)
+        .map(|i| {
+            let mut coeffs = vec![0.0; m];
+            coeffs[i] = 1.0;
+            BSpline::new(knots, &coeffs, degree)
+                .derivative()
+                .derivative()
+        })
+        .collect();
+
+    let mut penalty = Array2::zeros((m, m));
+    for span in knots.windows(2) {
+        let (a, b) = (span[0], span[1]);
+        if a == b {
+            continue;
+        }
+
+        // Simpson's rule is exact for the product of two linear functions
+        let nodes = [a, 0.5 * (a + b), b];
+        let values: Vec<[f64; 3]> = second
+            .iter()
+            .map(|s| [s.eval(nodes[0]), s.eval(nodes[1]), s.eval(nodes[2])])
+            .collect();
+        for i in 0..m {
+            for j in i..m {
+                let (u, v) = (values[i], values[j]);
+                let integral = (b - a) / 6.0 * (u[0] * v[0] + 4.0 * u[1] * v[1] + u[2] * v[2]);
+                penalty[(i, j)] += integral;
+                if i != j {
+                    penalty[(j, i)] += integral;
+                }
+            }
+        }
+    }
+
+    penalty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bspline_basis_partition_of_unity() {
+        let knots = [0.0, 0.0, 0.0, 0.0, 0.5, 1.5, 2.0, 3.0, 3.0, 3.0, 3.0];
+
+        for i in 0..=30 {
+            let x = i as f64 * 0.1;
+            let basis = bspline_basis(&knots, 3, x);
+            assert!((basis.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            assert!(basis.iter().all(|b| *b >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_bspline_eval_matches_basis() {
+        let knots = [0.0, 0.0, 0.0, 1.0, 2.0, 4.0, 4.0, 4.0];
+        let coeffs = [1.0, -2.0, 0.5, 3.0, 1.5];
+        let spline = BSpline::new(&knots, &coeffs, 2);
+
+        for i in 0..=40 {
+            let x = i as f64 * 0.1;
+            let basis = bspline_basis(&knots, 2, x);
+            let expected: f64 = basis.iter().zip(coeffs.iter()).map(|(b, c)| b * c).sum();
+            assert!((spline.eval(x) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_bspline_derivative() {
+        let xs: Vec<f64> = (0..30).map(|i| i as f64 * 0.1).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * x * x).collect();
+        let spline = BSpline::fit(&xs, &ys, None, &[1.0, 2.0], 3);
+
+        let first = spline.derivative();
+        let second = first.derivative();
+        for x in [0.3, 1.1, 2.7] {
+            assert!((first.eval(x) - 3.0 * x * x).abs() < 1e-9);
+            assert!((second.eval(x) - 6.0 * x).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_bspline_fit_weights() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = vec![0.0, 1.0, 2.0, 10.0, 4.0];
+        let weights = vec![1.0, 1.0, 1.0, 1e-12, 1.0];
+
+        let spline = BSpline::fit(&xs, &ys, Some(&weights), &[], 1);
+
+        assert!((spline.eval(2.5) - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_smoothing_spline_limits() {
+        let xs: Vec<f64> = (0..12).map(|i| i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| (0.7 * x).sin() + 0.1 * x).collect();
+
+        // weak smoothing interpolates the data
+        let spline = SmoothingSpline::new(&xs, &ys, None, Some(1e-10));
+        for (x, y) in xs.iter().zip(ys.iter()) {
+            assert!((spline.eval(*x) - y).abs() < 1e-8);
+        }
+
+        // strong smoothing approaches the linear regression
+        let spline = SmoothingSpline::new(&xs, &ys, None, Some(1e12));
+        let h = 1e-3;
+        let d2 = (spline.eval(5.0 + h) - 2.0 * spline.eval(5.0) + spline.eval(5.0 - h)) / h / h;
+        assert!(d2.abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_smoothing_spline_gcv() {
+        let xs: Vec<f64> = (0..40).map(|i| i as f64 * 0.15).collect();
+        let noise = [0.08, -0.05, 0.02, -0.09, 0.06, -0.01, -0.07, 0.04];
+        let ys: Vec<f64> = xs
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x.cos() + noise[i % noise.len()])
+            .collect();
+
+        let spline = SmoothingSpline::new(&xs, &ys, None, None);
+        let interpolating = SmoothingSpline::new(&xs, &ys, None, Some(1e-10));
+
+        // the chosen smoothing parameter beats finite scores on both sides of it
+        assert!(spline.lambda() > 0.0);
+        for factor in [0.1, 10.0] {
+            let other = SmoothingSpline::new(&xs, &ys, None, Some(spline.lambda() * factor));
+            assert!(other.gcv().is_finite());
+            assert!(spline.gcv() < other.gcv());
+        }
+        let err: f64 = xs.iter().map(|x| (spline.eval(*x) - x.cos()).powi(2)).sum();
+        let err_interp: f64 = xs
+            .iter()
+            .map(|x| (interpolating.eval(*x) - x.cos()).powi(2))
+            .sum();
+        assert!(err < err_interp);
+    }
+
+    #[test]
+    #[should_panic(expected = "interior knots need to be sorted")]
+    fn test_bspline_fit_unsorted_knots() {
+        let xs: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+        BSpline::fit(&xs, &xs, None, &[3.0, 1.0], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "interior knots need to lie inside the data range")]
+    fn test_bspline_fit_knot_outside_data() {
+        let xs: Vec<f64> = (0..20).map(|i| i as f64 * 0.25).collect();
+        BSpline::fit(&xs, &xs, None, &[1.0, 6.0], 3);
+    }
+}
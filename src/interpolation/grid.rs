@@ -0,0 +1,264 @@
+//! Interpolation on multidimensional rectilinear grids
+
+use ndarray::prelude::*;
+
+/// N-linear interpolation on a rectilinear grid
+///
+/// For two dimensions this is bilinear, for three dimensions trilinear interpolation.
+/// Points outside of the grid are extrapolated linearly from the border cells.
+///
+/// # Example
+/// ```
+/// use ndarray::{ArrayD, IxDyn};
+///
+/// use scialg::interpolation::grid::GridInterpolator;
+///
+/// let xs = vec![0.0, 1.0, 2.0];
+/// let ys = vec![0.0, 2.0];
+/// let values = ArrayD::from_shape_fn(IxDyn(&[3, 2]), |idx| {
+///     xs[idx[0]] + 3.0 * ys[idx[1]]
+/// });
+///
+/// let grid = GridInterpolator::new(vec![xs, ys], values);
+///
+/// assert!((grid.eval(&[1.5, 0.5]) - 3.0).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Bilinear interpolation](https://en.wikipedia.org/wiki/Bilinear_interpolation)
+///  - [Wikipedia: Multivariate interpolation](https://en.wikipedia.org/wiki/Multivariate_interpolation)
+pub struct GridInterpolator {
+    axes: Vec<Vec<f64>>,
+    values: ArrayD<f64>,
+}
+
+impl GridInterpolator {
+    /// Create an interpolator from the grid coordinates along every axis and the values on the grid
+    ///
+    /// # Panics
+    /// Panics if the shape of *values* does not match the lengths of *axes*, if an axis has
+    /// fewer than two points or if an axis is not strictly increasing
+    pub fn new(axes: Vec<Vec<f64>>, values: ArrayD<f64>) -> Self {
+        assert_eq!(axes.len(), values.ndim(), "number of axes does not match");
+        for (axis, len) in axes.iter().zip(values.shape()) {
+            assert_eq!(axis.len(), *len, "axis length does not match values");
+            check_axis(axis);
+        }
+
+        GridInterpolator { axes, values }
+    }
+
+    /// Evaluate the interpolant at *point*
+    ///
+    /// # Panics
+    /// Panics if the dimension of *point* does not match the grid
+    pub fn eval(&self, point: &[f64]) -> f64 {
+        let ndim = self.axes.len();
+        assert_eq!(point.len(), ndim, "dimension of point does not match");
+
+        let cells: Vec<(usize, f64)> = self
+            .axes
+            .iter()
+            .zip(point)
+            .map(|(axis, x)| {
+                let i = find_cell(axis, *x);
+                (i, (x - axis[i]) / (axis[i + 1] - axis[i]))
+            })
+            .collect();
+
+        // sum over the 2^N corners of the cell
+        let mut idx = vec![0; ndim];
+        let mut sum = 0.0;
+        for corner in 0..1 << ndim {
+            let mut weight = 1.0;
+            for (d, (i, t)) in cells.iter().enumerate() {
+                if corner >> d & 1 == 1 {
+                    idx[d] = i + 1;
+                    weight *= t;
+                } else {
+                    idx[d] = *i;
+                    weight *= 1.0 - t;
+                }
+            }
+            sum += weight * self.values[IxDyn(&idx)];
+        }
+
+        sum
+    }
+}
+
+/// Bicubic interpolation on a two-dimensional rectilinear grid
+///
+/// Every grid cell is described by a bicubic Hermite patch whose first and cross derivatives
+/// are estimated with finite differences, so the interpolant has a continuous gradient.
+///
+/// # Example
+/// ```
+/// use ndarray::Array2;
+///
+/// use scialg::interpolation::grid::BicubicInterpolator;
+///
+/// let xs: Vec<f64> = (0..10).map(|i| i as f64 * 0.2).collect();
+/// let ys: Vec<f64> = (0..8).map(|i| i as f64 * 0.25).collect();
+/// let values = Array2::from_shape_fn((10, 8), |(i, j)| xs[i].sin() * ys[j].cos());
+///
+/// let bicubic = BicubicInterpolator::new(&xs, &ys, values);
+///
+/// assert!((bicubic.eval(0.9, 1.1) - 0.9_f64.sin() * 1.1_f64.cos()).abs() < 1e-2);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Bicubic interpolation](https://en.wikipedia.org/wiki/Bicubic_interpolation)
+pub struct BicubicInterpolator {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    values: Array2<f64>,
+    dx: Array2<f64>,
+    dy: Array2<f64>,
+    dxy: Array2<f64>,
+}
+
+impl BicubicInterpolator {
+    /// # Panics
+    /// Panics if the shape of *values* is not `(xs.len(), ys.len())`, or if *xs* or *ys* have
+    /// fewer than two points or are not strictly increasing
+    pub fn new(xs: &[f64], ys: &[f64], values: Array2<f64>) -> Self {
+        assert_eq!(
+            values.dim(),
+            (xs.len(), ys.len()),
+            "shape does not match axes"
+        );
+        check_axis(xs);
+        check_axis(ys);
+
+        let dx = differentiate(&values, xs, Axis(0));
+        let dy = differentiate(&values, ys, Axis(1));
+        let dxy = differentiate(&dy, xs, Axis(0));
+
+        BicubicInterpolator {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            values,
+            dx,
+            dy,
+            dxy,
+        }
+    }
+
+    pub fn eval(&self, x: f64, y: f64) -> f64 {
+        let i = find_cell(&self.xs, x);
+        let j = find_cell(&self.ys, y);
+        let hx = self.xs[i + 1] - self.xs[i];
+        let hy = self.ys[j + 1] - self.ys[j];
+        let t = (x - self.xs[i]) / hx;
+        let u = (y - self.ys[j]) / hy;
+
+        // cubic Hermite basis for values and scaled derivatives at both ends
+        let basis = |t: f64| {
+            let t2 = t * t;
+            let t3 = t2 * t;
+            (
+                [2.0 * t3 - 3.0 * t2 + 1.0, -2.0 * t3 + 3.0 * t2],
+                [t3 - 2.0 * t2 + t, t3 - t2],
+            )
+        };
+        let (ht, gt) = basis(t);
+        let (hu, gu) = basis(u);
+
+        let mut sum = 0.0;
+        for a in 0..2 {
+            for b in 0..2 {
+                let idx = (i + a, j + b);
+                sum += ht[a] * hu[b] * self.values[idx]
+                    + gt[a] * hu[b] * hx * self.dx[idx]
+                    + ht[a] * gu[b] * hy * self.dy[idx]
+                    + gt[a] * gu[b] * hx * hy * self.dxy[idx];
+            }
+        }
+
+        sum
+    }
+}
+
+fn check_axis(axis: &[f64]) {
+    assert!(axis.len() >= 2, "axis needs at least two points");
+    assert!(
+        axis.windows(2).all(|w| w[0] < w[1]),
+        "axis needs to be strictly increasing"
+    );
+}
+
+/// Return the index *i* of the cell `[axis[i], axis[i + 1]]` containing *x*,
+/// restricted to the border cells for points outside of the axis
+fn find_cell(axis: &[f64], x: f64) -> usize {
+    let idx = axis.partition_point(|v| *v <= x);
+
+    idx.clamp(1, axis.len() - 1) - 1
+}
+
+/// Estimate the derivative of *values* along *axis* with central differences in the interior
+/// and one-sided differences at the borders
+fn differentiate(values: &Array2<f64>, coords: &[f64], axis: Axis) -> Array2<f64> {
+    let n = coords.len();
+    let mut res = Array2::zeros(values.dim());
+
+    for (lane, mut out) in values.lanes(axis).into_iter().zip(res.lanes_mut(axis)) {
+        for k in 0..n {
+            let (lo, hi) = (k.saturating_sub(1), (k + 1).min(n - 1));
+            out[k] = (lane[hi] - lane[lo]) / (coords[hi] - coords[lo]);
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_trilinear() {
+        let axes = vec![
+            vec![0.0, 1.0, 3.0],
+            vec![-1.0, 0.0, 0.5, 1.0],
+            vec![0.0, 2.0],
+        ];
+        let f = |p: &[f64]| 1.0 + 2.0 * p[0] - p[1] + 0.5 * p[2];
+        let values = ArrayD::from_shape_fn(IxDyn(&[3, 4, 2]), |idx| {
+            f(&[axes[0][idx[0]], axes[1][idx[1]], axes[2][idx[2]]])
+        });
+
+        let grid = GridInterpolator::new(axes, values);
+
+        // linear functions are reproduced, also outside of the grid
+        for p in [[0.5, 0.2, 1.0], [2.9, -0.7, 0.1], [3.5, 1.2, -1.0]] {
+            assert!((grid.eval(&p) - f(&p)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_grid_bilinear_nodes() {
+        let values = ArrayD::from_shape_vec(IxDyn(&[2, 2]), vec![1.0, 2.0, 3.0, 5.0]).unwrap();
+        let grid = GridInterpolator::new(vec![vec![0.0, 1.0], vec![0.0, 1.0]], values);
+
+        assert_eq!(grid.eval(&[0.0, 0.0]), 1.0);
+        assert_eq!(grid.eval(&[1.0, 1.0]), 5.0);
+        assert_eq!(grid.eval(&[0.5, 0.5]), 2.75);
+    }
+
+    #[test]
+    fn test_bicubic() {
+        let xs: Vec<f64> = (0..6).map(|i| i as f64).collect();
+        let ys = vec![0.0, 0.5, 1.5, 2.0, 3.0];
+        let f = |x: f64, y: f64| 1.0 + x - 2.0 * y + 0.5 * x * y;
+        let values = Array2::from_shape_fn((6, 5), |(i, j)| f(xs[i], ys[j]));
+
+        let bicubic = BicubicInterpolator::new(&xs, &ys, values);
+
+        // bilinear functions have exact finite difference derivatives
+        for (x, y) in [(0.3, 0.2), (2.5, 1.7), (4.9, 2.9)] {
+            assert!((bicubic.eval(x, y) - f(x, y)).abs() < 1e-12);
+        }
+        assert_eq!(bicubic.eval(2.0, 1.5), f(2.0, 1.5));
+    }
+}
@@ -0,0 +1,305 @@
+//! Rational and barycentric interpolation
+
+use num::Float;
+
+/// Interpolate a dataset *f(xs)->ys* at point *x* with the diagonal rational function
+/// through all points using the Bulirsch-Stoer algorithm
+///
+/// Returns the interpolated value and an error estimate.
+/// Unlike polynomial interpolation, rational functions can follow poles close to the data.
+/// If *x* is located on a pole of the interpolant, infinity is returned.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::rational::bulirsch_stoer;
+///
+/// let f = |x: f64| 1.0 / (x - 1.05);
+/// let xs = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+/// let ys: Vec<f64> = xs.iter().map(|x| f(*x)).collect();
+///
+/// let (y, _dy) = bulirsch_stoer(&xs, &ys, 0.9);
+///
+/// assert!((y - f(0.9)).abs() < 1e-8);
+/// ```
+///
+/// # References
+///  - R. Bulirsch and J. Stoer, *Fehlerabschätzungen und Extrapolation mit rationalen Funktionen
+///    bei Verfahren vom Richardson-Typus*, Numer. Math. 6 (1964)
+///  - [Wolfram MathWorld: Bulirsch-Stoer Algorithm](https://mathworld.wolfram.com/Bulirsch-StoerAlgorithm.html)
+pub fn bulirsch_stoer<F: Float>(xs: &[F], ys: &[F], x: F) -> (F, F) {
+    assert_eq!(xs.len(), ys.len());
+    let n = xs.len();
+    let tiny = F::min_positive_value().sqrt();
+
+    let mut c = ys.to_vec();
+    let mut d = ys.to_vec();
+    let mut ns = 0;
+    let mut hh = (x - xs[0]).abs();
+    for i in 0..n {
+        let h = (x - xs[i]).abs();
+        if h == F::zero() {
+            return (ys[i], F::zero());
+        } else if h < hh {
+            ns = i;
+            hh = h;
+        }
+        d[i] = ys[i] + tiny;
+    }
+
+    let mut y = ys[ns];
+    let mut dy = F::zero();
+    // index of the tableau entry the path currently runs through, shifted by one
+    let mut ns = ns as isize - 1;
+
+    for m in 1..n {
+        for i in 0..n - m {
+            let w = c[i + 1] - d[i];
+            let h = xs[i + m] - x;
+            let t = (xs[i] - x) * d[i] / h;
+            let dd = t - c[i + 1];
+            if dd == F::zero() {
+                return (F::infinity(), F::infinity());
+            }
+            let dd = w / dd;
+            d[i] = c[i + 1] * dd;
+            c[i] = t * dd;
+        }
+
+        dy = if 2 * (ns + 1) < (n - m) as isize {
+            c[(ns + 1) as usize]
+        } else {
+            let v = d[ns as usize];
+            ns -= 1;
+            v
+        };
+        y = y + dy;
+    }
+
+    (y, dy)
+}
+
+/// Barycentric rational interpolation after Floater and Hormann
+///
+/// The interpolant has no poles on the real line and converges with order *d + 1* on smooth data.
+/// For `d = n - 1` it is identical to polynomial interpolation, small *d* avoid Runge oscillations.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::rational::FloaterHormann;
+///
+/// let runge = |x: f64| 1.0 / (1.0 + 25.0 * x * x);
+/// let xs: Vec<f64> = (0..21).map(|i| -1.0 + i as f64 * 0.1).collect();
+/// let ys: Vec<f64> = xs.iter().map(|x| runge(*x)).collect();
+///
+/// let interpolant = FloaterHormann::new(&xs, &ys, 3);
+///
+/// assert!((interpolant.eval(0.95) - runge(0.95)).abs() < 1e-2);
+/// ```
+///
+/// # References
+///  - M. S. Floater and K. Hormann, *Barycentric rational interpolation with no poles and high
+///    rates of approximation*, Numer. Math. 107 (2007)
+pub struct FloaterHormann {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl FloaterHormann {
+    /// # Panics
+    /// Panics if *xs* and *ys* differ in length or if `d >= xs.len()`
+    pub fn new(xs: &[f64], ys: &[f64], d: usize) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        let n = xs.len();
+        assert!(
+            d < n,
+            "blending degree needs to be smaller than the number of points"
+        );
+
+        let mut weights = vec![0.0; n];
+        for (k, weight) in weights.iter_mut().enumerate() {
+            let i_min = k.saturating_sub(d);
+            let i_max = k.min(n - 1 - d);
+            let mut sum = 0.0;
+            for i in i_min..=i_max {
+                let mut prod = 1.0;
+                for j in i..=i + d {
+                    if j != k {
+                        prod /= (xs[k] - xs[j]).abs();
+                    }
+                }
+                sum += prod;
+            }
+            *weight = if (k + d).is_multiple_of(2) { sum } else { -sum };
+        }
+
+        FloaterHormann {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            weights,
+        }
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        barycentric_eval(&self.xs, &self.ys, &self.weights, x)
+    }
+}
+
+/// Polynomial interpolation in the second (true) barycentric form
+///
+/// After an O(n^2) setup every evaluation takes O(n) operations, and the evaluation is
+/// numerically stable for well distributed points such as Chebyshev nodes.
+///
+/// # Example
+/// ```
+/// use scialg::interpolation::rational::BarycentricLagrange;
+///
+/// let xs = vec![-1.0, 0.0, 1.0, 2.0];
+/// let ys = vec![-1.0, 0.0, 1.0, 8.0];
+///
+/// let interpolant = BarycentricLagrange::new(&xs, &ys);
+///
+/// assert!((interpolant.eval(0.5) - 0.125).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - J.-P. Berrut and L. N. Trefethen, *Barycentric Lagrange Interpolation*, SIAM Review 46 (2004)
+///  - [Wikipedia: Lagrange polynomial](https://en.wikipedia.org/wiki/Lagrange_polynomial#Barycentric_form)
+pub struct BarycentricLagrange {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl BarycentricLagrange {
+    /// # Panics
+    /// Panics if *xs* and *ys* differ in length or if there are no points
+    pub fn new(xs: &[f64], ys: &[f64]) -> Self {
+        assert_eq!(xs.len(), ys.len());
+        assert!(!xs.is_empty(), "at least one point is needed");
+
+        let weights = xs
+            .iter()
+            .enumerate()
+            .map(|(j, xj)| {
+                let prod: f64 = xs
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| *k != j)
+                    .map(|(_, xk)| xj - xk)
+                    .product();
+                1.0 / prod
+            })
+            .collect();
+
+        BarycentricLagrange {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            weights,
+        }
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        barycentric_eval(&self.xs, &self.ys, &self.weights, x)
+    }
+
+    /// Evaluate the interpolant at *x* and estimate the error as the difference to the
+    /// interpolant through all points but the last one
+    pub fn eval_with_error(&self, x: f64) -> (f64, f64) {
+        let y = self.eval(x);
+        let n = self.xs.len();
+        if n == 1 {
+            return (y, y.abs());
+        }
+
+        // dropping node n - 1 multiplies every other weight by (x_j - x_{n-1})
+        let x_last = self.xs[n - 1];
+        let weights: Vec<f64> = (0..n - 1)
+            .map(|j| self.weights[j] * (self.xs[j] - x_last))
+            .collect();
+        let y_reduced = barycentric_eval(&self.xs[..n - 1], &self.ys[..n - 1], &weights, x);
+
+        (y, (y - y_reduced).abs())
+    }
+}
+
+/// Evaluate the barycentric formula `sum(w_j y_j / (x - x_j)) / sum(w_j / (x - x_j))`
+fn barycentric_eval(xs: &[f64], ys: &[f64], weights: &[f64], x: f64) -> f64 {
+    let mut num = 0.0;
+    let mut den = 0.0;
+
+    for j in 0..xs.len() {
+        let dx = x - xs[j];
+        if dx == 0.0 {
+            return ys[j];
+        }
+        let t = weights[j] / dx;
+        num += t * ys[j];
+        den += t;
+    }
+
+    num / den
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulirsch_stoer_rational_function() {
+        // a diagonal rational function is reproduced exactly
+        let f = |x: f64| (1.0 + 2.0 * x) / (1.0 - 0.5 * x);
+        let xs = vec![-1.0, -0.3, 0.4, 1.0, 1.5];
+        let ys: Vec<f64> = xs.iter().map(|x| f(*x)).collect();
+
+        for x in [-0.8, 0.0, 0.7, 1.2] {
+            let (y, dy) = bulirsch_stoer(&xs, &ys, x);
+            assert!((y - f(x)).abs() < 1e-10);
+            assert!(dy.abs() < 1e-8);
+        }
+        assert_eq!(bulirsch_stoer(&xs, &ys, 0.4), (ys[2], 0.0));
+    }
+
+    #[test]
+    fn test_floater_hormann_polynomial_limit() {
+        // d = n - 1 gives the interpolating polynomial
+        let xs = vec![0.0, 0.5, 1.5, 2.0, 3.0];
+        let ys: Vec<f64> = xs.iter().map(|x| x * x * x * x - x).collect();
+
+        let fh = FloaterHormann::new(&xs, &ys, 4);
+        let lagrange = BarycentricLagrange::new(&xs, &ys);
+
+        for i in 0..30 {
+            let x = i as f64 * 0.1;
+            assert!((fh.eval(x) - (x.powi(4) - x)).abs() < 1e-9);
+            assert!((lagrange.eval(x) - fh.eval(x)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_floater_hormann_runge() {
+        let runge = |x: f64| 1.0 / (1.0 + 25.0 * x * x);
+        let xs: Vec<f64> = (0..41).map(|i| -1.0 + i as f64 * 0.05).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| runge(*x)).collect();
+
+        let fh = FloaterHormann::new(&xs, &ys, 3);
+        let lagrange = BarycentricLagrange::new(&xs, &ys);
+
+        let x = 0.975;
+        assert!((fh.eval(x) - runge(x)).abs() < 1e-4);
+        assert!((lagrange.eval(x) - runge(x)).abs() > 1.0);
+    }
+
+    #[test]
+    fn test_barycentric_lagrange_error_estimate() {
+        let xs: Vec<f64> = (0..8).map(|i| i as f64 * 0.2).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x.exp()).collect();
+
+        let interpolant = BarycentricLagrange::new(&xs, &ys);
+        let (y, err) = interpolant.eval_with_error(0.7);
+
+        assert!((y - 0.7_f64.exp()).abs() < 1e-8);
+        assert!((y - 0.7_f64.exp()).abs() <= err);
+        assert!(err < 1e-6);
+    }
+}
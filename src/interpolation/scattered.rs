@@ -0,0 +1,309 @@
+//! Interpolation of scattered multidimensional data
+
+use ndarray::prelude::*;
+
+use crate::linalg::solve;
+
+/// Radial basis function *phi(r)* with shape parameter *epsilon* where applicable
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RadialBasisFunction {
+    /// `exp(-(epsilon r)^2)`
+    Gaussian(f64),
+    /// `sqrt(1 + (epsilon r)^2)`
+    Multiquadric(f64),
+    /// `r^2 ln(r)`
+    ThinPlate,
+}
+
+impl RadialBasisFunction {
+    fn eval(&self, r: f64) -> f64 {
+        match self {
+            RadialBasisFunction::Gaussian(eps) => (-(eps * r).powi(2)).exp(),
+            RadialBasisFunction::Multiquadric(eps) => (1.0 + (eps * r).powi(2)).sqrt(),
+            RadialBasisFunction::ThinPlate => {
+                if r == 0.0 {
+                    0.0
+                } else {
+                    r * r * r.ln()
+                }
+            }
+        }
+    }
+}
+
+/// Radial basis function interpolation of scattered data
+///
+/// The interpolant is `s(x) = sum_i w_i phi(|x - x_i|)`. Thin-plate splines are augmented with
+/// a linear polynomial, which makes the system uniquely solvable and reproduces linear functions.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::interpolation::scattered::{RadialBasisFunction, RbfInterpolator};
+///
+/// let points = array![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0], [0.5, 0.5]];
+/// let values = vec![0.0, 1.0, 1.0, 2.0, 1.0];
+///
+/// let rbf = RbfInterpolator::new(&points, &values, RadialBasisFunction::ThinPlate).unwrap();
+///
+/// assert!((rbf.eval(&[0.25, 0.5]) - 0.75).abs() < 1e-10);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Radial basis function interpolation](https://en.wikipedia.org/wiki/Radial_basis_function_interpolation)
+///  - [Wikipedia: Thin plate spline](https://en.wikipedia.org/wiki/Thin_plate_spline)
+pub struct RbfInterpolator {
+    points: Array2<f64>,
+    weights: Array1<f64>,
+    poly: Option<Array1<f64>>,
+    kernel: RadialBasisFunction,
+}
+
+impl RbfInterpolator {
+    /// Create an interpolator through *values* at *points*, which holds one point per row
+    ///
+    /// Returns None if the interpolation system is singular, e.g. for duplicate points.
+    ///
+    /// # Panics
+    /// Panics if the number of points and values differ
+    pub fn new(points: &Array2<f64>, values: &[f64], kernel: RadialBasisFunction) -> Option<Self> {
+        let (n, dim) = points.dim();
+        assert_eq!(n, values.len(), "number of points and values differ");
+
+        let augmented = kernel == RadialBasisFunction::ThinPlate;
+        let m = if augmented { n + dim + 1 } else { n };
+
+        let mut mat = Array2::zeros((m, m));
+        let mut rhs = Array1::zeros(m);
+        for i in 0..n {
+            for j in 0..n {
+                mat[(i, j)] = kernel.eval(distance(points.row(i), points.row(j)));
+            }
+            rhs[i] = values[i];
+
+            if augmented {
+                mat[(i, n)] = 1.0;
+                mat[(n, i)] = 1.0;
+                for d in 0..dim {
+                    mat[(i, n + 1 + d)] = points[(i, d)];
+                    mat[(n + 1 + d, i)] = points[(i, d)];
+                }
+            }
+        }
+
+        let sol = solve(&mat, &rhs).ok()?;
+
+        Some(RbfInterpolator {
+            points: points.to_owned(),
+            weights: sol.slice(s![..n]).to_owned(),
+            poly: augmented.then(|| sol.slice(s![n..]).to_owned()),
+            kernel,
+        })
+    }
+
+    /// Evaluate the interpolant at *point*
+    ///
+    /// # Panics
+    /// Panics if the dimension of *point* differs from the dimension of the data points
+    pub fn eval(&self, point: &[f64]) -> f64 {
+        assert_eq!(
+            point.len(),
+            self.points.ncols(),
+            "dimension of the point differs from the data"
+        );
+        let point = ArrayView1::from(point);
+        let mut sum: f64 = self
+            .points
+            .rows()
+            .into_iter()
+            .zip(self.weights.iter())
+            .map(|(p, w)| w * self.kernel.eval(distance(p, point)))
+            .sum();
+
+        if let Some(poly) = &self.poly {
+            sum += poly[0] + poly.slice(s![1..]).dot(&point);
+        }
+
+        sum
+    }
+}
+
+/// Inverse distance weighting (Shepard's method) of scattered data
+///
+/// The interpolant is the weighted mean of all values with weights `1 / |x - x_i|^p`.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::interpolation::scattered::InverseDistanceWeighting;
+///
+/// let points = array![[0.0, 0.0], [2.0, 0.0]];
+/// let idw = InverseDistanceWeighting::new(&points, &[1.0, 3.0], 2.0);
+///
+/// assert_eq!(idw.eval(&[1.0, 0.0]), 2.0);
+/// assert_eq!(idw.eval(&[2.0, 0.0]), 3.0);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Inverse distance weighting](https://en.wikipedia.org/wiki/Inverse_distance_weighting)
+pub struct InverseDistanceWeighting {
+    points: Array2<f64>,
+    values: Vec<f64>,
+    power: f64,
+}
+
+impl InverseDistanceWeighting {
+    /// Create an interpolator through *values* at *points*, which holds one point per row
+    ///
+    /// # Panics
+    /// Panics if the number of points and values differ
+    pub fn new(points: &Array2<f64>, values: &[f64], power: f64) -> Self {
+        assert_eq!(
+            points.nrows(),
+            values.len(),
+            "number of points and values differ"
+        );
+
+        InverseDistanceWeighting {
+            points: points.to_owned(),
+            values: values.to_vec(),
+            power,
+        }
+    }
+
+    /// Evaluate the interpolant at *point*
+    ///
+    /// # Panics
+    /// Panics if the dimension of *point* differs from the dimension of the data points
+    pub fn eval(&self, point: &[f64]) -> f64 {
+        assert_eq!(
+            point.len(),
+            self.points.ncols(),
+            "dimension of the point differs from the data"
+        );
+        let point = ArrayView1::from(point);
+        let mut num = 0.0;
+        let mut den = 0.0;
+
+        for (p, v) in self.points.rows().into_iter().zip(self.values.iter()) {
+            let r = distance(p, point);
+            if r == 0.0 {
+                return *v;
+            }
+            let w = r.powf(-self.power);
+            num += w * v;
+            den += w;
+        }
+
+        num / den
+    }
+}
+
+fn distance(a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_points() -> (Array2<f64>, Vec<f64>) {
+        let f = |x: f64, y: f64| (x * y).sin() + 0.5 * x;
+        let mut points = Vec::new();
+        let mut values = Vec::new();
+        for i in 0..7 {
+            for j in 0..7 {
+                // slightly perturbed grid
+                let x = i as f64 * 0.5 + 0.05 * ((i * j) % 3) as f64;
+                let y = j as f64 * 0.5 - 0.04 * ((i + j) % 2) as f64;
+                points.extend([x, y]);
+                values.push(f(x, y));
+            }
+        }
+
+        (Array2::from_shape_vec((49, 2), points).unwrap(), values)
+    }
+
+    #[test]
+    fn test_rbf_interpolates_data() {
+        let (points, values) = sample_points();
+
+        for kernel in [
+            RadialBasisFunction::Gaussian(1.5),
+            RadialBasisFunction::Multiquadric(1.0),
+            RadialBasisFunction::ThinPlate,
+        ] {
+            let rbf = RbfInterpolator::new(&points, &values, kernel).unwrap();
+            for (p, v) in points.rows().into_iter().zip(values.iter()) {
+                assert!((rbf.eval(p.as_slice().unwrap()) - v).abs() < 1e-6);
+            }
+            let f = (1.2_f64 * 1.3).sin() + 0.6;
+            assert!((rbf.eval(&[1.2, 1.3]) - f).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn test_thin_plate_reproduces_linear() {
+        let points = array![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.5],
+            [0.0, 1.0, 0.2],
+            [1.0, 1.0, 1.0],
+            [0.3, 0.6, 0.9]
+        ];
+        let values: Vec<f64> = points
+            .rows()
+            .into_iter()
+            .map(|p| 1.0 - p[0] + 2.0 * p[1] + 0.5 * p[2])
+            .collect();
+
+        let rbf = RbfInterpolator::new(&points, &values, RadialBasisFunction::ThinPlate).unwrap();
+
+        assert!((rbf.eval(&[0.5, 0.5, 0.5]) - 1.75).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_rbf_duplicate_points() {
+        let points = array![[0.0, 0.0], [0.0, 0.0]];
+
+        assert!(
+            RbfInterpolator::new(&points, &[1.0, 2.0], RadialBasisFunction::Gaussian(1.0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_inverse_distance_weighting() {
+        let (points, values) = sample_points();
+        let idw = InverseDistanceWeighting::new(&points, &values, 2.0);
+
+        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        for i in 0..30 {
+            let v = idw.eval(&[i as f64 * 0.1, 3.0 - i as f64 * 0.1]);
+            assert!(min <= v && v <= max);
+        }
+        assert_eq!(idw.eval(&[0.0, 0.0]), values[0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension of the point differs from the data")]
+    fn test_rbf_dimension_mismatch() {
+        let (points, values) = sample_points();
+        let rbf = RbfInterpolator::new(&points, &values, RadialBasisFunction::ThinPlate).unwrap();
+        rbf.eval(&[0.5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension of the point differs from the data")]
+    fn test_idw_dimension_mismatch() {
+        let (points, values) = sample_points();
+        InverseDistanceWeighting::new(&points, &values, 2.0).eval(&[0.5, 0.5, 0.5]);
+    }
+}
@@ -7,6 +7,7 @@
 //!  - Integration
 //!  - Inter- (and extra)polation
 //!  - Linear algebra
+//!  - Matrix math
 //!  - Dataset modelling (aka fitting)
 //!  - Differential equations
 //!  - Optimization
@@ -22,6 +23,7 @@ pub mod function;
 pub mod integration;
 pub mod interpolation;
 pub mod linalg;
+pub mod matrix;
 pub mod model;
 pub mod ode;
 pub mod optimize;
@@ -1,18 +1,89 @@
 //! Linear algebra algorithms
+//!
+//! Failures that depend on the values of a matrix, such as a singular or indefinite matrix or an
+//! iteration that does not converge, are reported as a [`LinalgError`]. Factorizations also return
+//! [`LinalgError::NonSquare`] or [`LinalgError::DimensionMismatch`] for operands of the wrong
+//! shape, because the shape of the input is often only known at runtime. Once a factorization
+//! exists, its `solve` methods panic if the right-hand side does not match the dimension of the
+//! factorized matrix, like the arithmetic operators of `ndarray`: the dimension is fixed by the
+//! factorization, so a mismatch is a programming error, and a `Result` would burden every
+//! repeated solve with an error path that cannot occur in correct code.
+
+pub mod banded;
+pub mod cholesky;
+pub mod eigen;
+pub mod gemm;
+pub mod krylov;
+pub mod lu;
+pub mod matrix_function;
+pub mod qr;
+pub mod scalar;
+pub mod sparse;
+pub mod structured;
+pub mod svd;
+
+use std::fmt;
 
 use ndarray::prelude::*;
-use num::Float;
+use num::{Float, Zero};
+
+use crate::linalg::lu::LU;
+use crate::linalg::scalar::Scalar;
+
+/// Errors of linear algebra algorithms
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LinalgError {
+    /// The matrix is not square
+    NonSquare,
+    /// The dimensions of the operands do not match
+    DimensionMismatch,
+    /// The matrix is singular to working precision
+    Singular,
+    /// The matrix is not positive definite
+    NotPositiveDefinite,
+    /// An iterative algorithm did not converge
+    NoConvergence,
+}
+
+impl fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinalgError::NonSquare => write!(f, "matrix is not square"),
+            LinalgError::DimensionMismatch => write!(f, "dimensions do not match"),
+            LinalgError::Singular => write!(f, "matrix is singular"),
+            LinalgError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            LinalgError::NoConvergence => write!(f, "algorithm did not converge"),
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
 
 /// Gaussian Elimination
 ///
+/// Reduces the augmented matrix *arr* with at least as many columns as rows to reduced row
+/// echelon form. Returns [`LinalgError::DimensionMismatch`] if *arr* has fewer columns than
+/// rows and [`LinalgError::Singular`] if a pivot is zero or not finite.
+///
+/// # Note
+/// This algorithm does not pivot and fails on matrices with zeros on the diagonal.
+/// Use [`LU`] to solve linear systems instead.
+///
 /// # References
 ///  - [Wikipedia: Guassian elimination](https://en.wikipedia.org/wiki/Gaussian_elimination)
 ///  - [LibreTexts Mathematics: Solving Systems with Gauss-Jordan
 ///  Elimination](https://math.libretexts.org/Courses/Community_College_of_Denver/MAT_1320_Finite_Mathematics/03%3A_Solving_Systems_of_Equations/3.03%3A_Solving_Systems_with_Gaussian_Elimination)
 /// - [Brilliant: Gauss-Jordan Elimination](https://brilliant.org/wiki/gaussian-elimination/)
-pub fn gauss_jordan<F: Float>(arr: &mut Array2<F>) {
+pub fn gauss_jordan<F: Float>(arr: &mut Array2<F>) -> Result<(), LinalgError> {
+    if arr.ncols() < arr.nrows() {
+        return Err(LinalgError::DimensionMismatch);
+    }
+
     for k in 0..arr.nrows() {
         let pivot = arr[(k, k)];
+        if pivot == F::zero() || !pivot.is_finite() {
+            return Err(LinalgError::Singular);
+        }
         arr.row_mut(k).mapv_inplace(|e| e / pivot);
         for j in 0..arr.nrows() {
             if j == k {
@@ -24,37 +95,189 @@ pub fn gauss_jordan<F: Float>(arr: &mut Array2<F>) {
             }
         }
     }
+
+    Ok(())
+}
+
+/// Solve the linear system *arr x = rhs* using an [`LU`] decomposition with partial pivoting
+///
+/// The system may be real or complex. Returns [`LinalgError::NonSquare`] if *arr* is not
+/// square, [`LinalgError::DimensionMismatch`] if the dimensions of *arr* and *rhs* do not match
+/// and [`LinalgError::Singular`] if *arr* is singular.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use num::Complex;
+///
+/// use scialg::linalg::solve;
+///
+/// let arr = array![[0.0, 1.0], [2.0, 1.0]];
+/// let rhs = array![1.0, 5.0];
+///
+/// assert_eq!(solve(&arr, &rhs), Ok(array![2.0, 1.0]));
+///
+/// let arr = array![
+///     [Complex::new(1.0, 1.0), Complex::new(0.0, -1.0)],
+///     [Complex::new(0.0, -1.0), Complex::new(2.0, 0.0)]
+/// ];
+/// let x = solve(&arr, &array![Complex::new(1.0, 0.0), Complex::new(0.0, 0.0)]).unwrap();
+///
+/// assert!((x[0] - Complex::new(6.0, -4.0) / 13.0).norm() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Gaussian elimination](https://en.wikipedia.org/wiki/Gaussian_elimination#Pseudocode)
+pub fn solve<F: Scalar>(arr: &Array2<F>, rhs: &Array1<F>) -> Result<Array1<F>, LinalgError> {
+    if arr.nrows() != rhs.len() {
+        return Err(LinalgError::DimensionMismatch);
+    }
+
+    LU::new(arr).map(|lu| lu.solve(rhs))
 }
 
 /// Return the transpose of *arr*
-pub fn transpose<F: Float>(arr: &Array2<F>) -> Array2<F> {
+///
+/// The matrix is copied in square tiles, so that both reading and writing stay cache friendly.
+pub fn transpose<F: Scalar>(arr: &Array2<F>) -> Array2<F> {
+    const TILE: usize = 32;
     let ni = arr.shape()[0];
     let nj = arr.shape()[1];
     let mut res = Array2::zeros((nj, ni));
 
-    for j in 0..ni {
-        for i in 0..nj {
-            res[(i, j)] = arr[(j, i)];
+    for j0 in (0..ni).step_by(TILE) {
+        for i0 in (0..nj).step_by(TILE) {
+            for j in j0..ni.min(j0 + TILE) {
+                for i in i0..nj.min(i0 + TILE) {
+                    res[(i, j)] = arr[(j, i)];
+                }
+            }
         }
     }
 
     res
 }
 
+/// Return the conjugate transpose of *arr*, which is the transpose for real matrices
+pub fn conj_transpose<F: Scalar>(arr: &Array2<F>) -> Array2<F> {
+    let mut res = transpose(arr);
+    res.mapv_inplace(F::conj);
+
+    res
+}
+
 /// Return the inverse of *arr*
 ///
-/// # Panics
-/// Panics if arr is not a square matrix
-pub fn invert<F: Float>(arr: &Array2<F>) -> Array2<F> {
-    assert!(arr.is_square());
-    let n = arr.shape()[0];
-    let identity: Array2<F> = Array2::from_diag_elem(n, F::one());
+/// Returns [`LinalgError::NonSquare`] if *arr* is not square and [`LinalgError::Singular`] if it
+/// is singular.
+pub fn invert<F: Scalar>(arr: &Array2<F>) -> Result<Array2<F>, LinalgError> {
+    LU::new(arr).map(|lu| lu.inverse())
+}
+
+/// Return the normwise backward error `|rhs - arr x| / (|arr| |x| + |rhs|)` of an approximate
+/// solution *x* of `arr x = rhs` in the infinity norm
+///
+/// This is the smallest relative perturbation of *arr* and *rhs* for which *x* is an exact
+/// solution. A backward error of the order of the machine epsilon means that the solver is as
+/// accurate as the data allows; the forward error is then bounded by the backward error times
+/// the condition number. Returns [`LinalgError::DimensionMismatch`] if the dimensions of *arr*,
+/// *x* and *rhs* do not match.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::{normwise_backward_error, solve};
+///
+/// let arr: Array2<f64> = array![[4.0, 1.0], [2.0, 3.0]];
+/// let rhs = array![1.0, 2.0];
+/// let x = solve(&arr, &rhs).unwrap();
+///
+/// assert!(normwise_backward_error(&arr, &x, &rhs).unwrap() < 1e-15);
+/// assert!(normwise_backward_error(&arr, &array![0.2, 0.6], &rhs).unwrap() > 1e-3);
+/// ```
+///
+/// # References
+///  - N. J. Higham, *Accuracy and Stability of Numerical Algorithms*, 2nd ed., SIAM (2002),
+///    section 7.1 (Rigal-Gaches theorem)
+pub fn normwise_backward_error<F: Scalar>(
+    arr: &Array2<F>,
+    x: &Array1<F>,
+    rhs: &Array1<F>,
+) -> Result<F::Real, LinalgError> {
+    let residual = residual(arr, x, rhs)?;
+    let norm_inf = |v: &Array1<F>| {
+        v.iter()
+            .map(|e| e.modulus())
+            .fold(F::Real::zero(), F::Real::max)
+    };
+    let norm_arr = arr
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().fold(F::Real::zero(), |acc, e| acc + e.modulus()))
+        .fold(F::Real::zero(), F::Real::max);
+
+    Ok(ratio(
+        norm_inf(&residual),
+        norm_arr * norm_inf(x) + norm_inf(rhs),
+    ))
+}
+
+/// Return the componentwise backward error `max_i |rhs - arr x|_i / (|arr| |x| + |rhs|)_i` of an
+/// approximate solution *x* of `arr x = rhs`
+///
+/// This is the smallest relative perturbation of the individual entries of *arr* and *rhs* for
+/// which *x* is an exact solution, so that zeros and the scaling of the entries are respected.
+/// Returns [`LinalgError::DimensionMismatch`] if the dimensions of *arr*, *x* and *rhs* do not
+/// match.
+///
+/// # References
+///  - N. J. Higham, *Accuracy and Stability of Numerical Algorithms*, 2nd ed., SIAM (2002),
+///    section 7.2 (Oettli-Prager theorem)
+pub fn componentwise_backward_error<F: Scalar>(
+    arr: &Array2<F>,
+    x: &Array1<F>,
+    rhs: &Array1<F>,
+) -> Result<F::Real, LinalgError> {
+    let residual = residual(arr, x, rhs)?;
+
+    Ok(arr
+        .rows()
+        .into_iter()
+        .zip(residual.iter().zip(rhs.iter()))
+        .map(|(row, (r, b))| {
+            let scale = row
+                .iter()
+                .zip(x.iter())
+                .fold(b.modulus(), |acc, (a, xj)| acc + a.modulus() * xj.modulus());
+            ratio(r.modulus(), scale)
+        })
+        .fold(F::Real::zero(), F::Real::max))
+}
+
+/// Return `rhs - arr x`
+fn residual<F: Scalar>(
+    arr: &Array2<F>,
+    x: &Array1<F>,
+    rhs: &Array1<F>,
+) -> Result<Array1<F>, LinalgError> {
+    if arr.ncols() != x.len() || arr.nrows() != rhs.len() {
+        return Err(LinalgError::DimensionMismatch);
+    }
 
-    let mut stacked =
-        ndarray::concatenate(ndarray::Axis(1), &[arr.view(), identity.view()]).unwrap();
-    gauss_jordan(&mut stacked);
+    Ok(Array1::from_shape_fn(rhs.len(), |i| {
+        (0..x.len()).fold(rhs[i], |acc, j| acc - arr[(i, j)] * x[j])
+    }))
+}
 
-    stacked.slice(s![.., n..]).to_owned()
+/// Return the ratio of a residual and its scale, which is zero if the scale vanishes because the
+/// residual is bounded by the scale
+fn ratio<F: Float>(residual: F, scale: F) -> F {
+    if scale > F::zero() {
+        residual / scale
+    } else {
+        F::zero()
+    }
 }
 
 #[cfg(test)]
@@ -75,11 +298,43 @@ mod tests {
         )
         .unwrap();
 
-        gauss_jordan(&mut input);
+        gauss_jordan(&mut input).unwrap();
 
         assert_eq!(input, output);
     }
 
+    #[test]
+    fn test_solve_zero_diagonal() {
+        let arr: Array2<f64> =
+            Array2::from_shape_vec((3, 3), vec![0.0, 2.0, 1.0, 1.0, 0.0, 0.0, 3.0, 1.0, 0.0])
+                .unwrap();
+        let rhs = Array1::from(vec![5.0, 1.0, 5.0]);
+
+        let x = solve(&arr, &rhs).unwrap();
+        for (xi, ei) in x.iter().zip([1.0, 2.0, 1.0]) {
+            assert!((xi - ei).abs() < 1e-12);
+        }
+
+        let singular: Array2<f64> =
+            Array2::from_shape_vec((2, 2), vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert_eq!(
+            solve(&singular, &Array1::ones(2)),
+            Err(LinalgError::Singular)
+        );
+    }
+
+    #[test]
+    fn test_invert_zero_diagonal() {
+        let input: Array2<f64> =
+            Array2::from_shape_vec((3, 3), vec![0.0, 1.0, 0.0, 0.0, 0.0, 2.0, 4.0, 0.0, 0.0])
+                .unwrap();
+        let output: Array2<f64> =
+            Array2::from_shape_vec((3, 3), vec![0.0, 0.0, 0.25, 1.0, 0.0, 0.0, 0.0, 0.5, 0.0])
+                .unwrap();
+
+        assert_eq!(invert(&input), Ok(output));
+    }
+
     #[test]
     fn test_transpose() {
         let input: Array2<f32> =
@@ -96,6 +351,57 @@ mod tests {
         let output: Array2<f64> =
             Array2::from_shape_vec((2, 2), vec![-1.0, 1.5, 1.0, -1.0]).unwrap();
 
-        assert_eq!(invert(&input), output);
+        assert_eq!(invert(&input), Ok(output));
+    }
+
+    #[test]
+    fn test_linalg_errors() {
+        let rect: Array2<f64> = Array2::ones((2, 3));
+        assert_eq!(invert(&rect), Err(LinalgError::NonSquare));
+        assert_eq!(solve(&rect, &Array1::ones(2)), Err(LinalgError::NonSquare));
+
+        let square: Array2<f64> = Array2::eye(2);
+        assert_eq!(
+            solve(&square, &Array1::ones(3)),
+            Err(LinalgError::DimensionMismatch)
+        );
+
+        let mut zero_pivot: Array2<f64> = array![[0.0, 1.0, 1.0], [1.0, 0.0, 2.0]];
+        assert_eq!(gauss_jordan(&mut zero_pivot), Err(LinalgError::Singular));
+        let mut tall: Array2<f64> = Array2::ones((3, 2));
+        assert_eq!(gauss_jordan(&mut tall), Err(LinalgError::DimensionMismatch));
+    }
+
+    #[test]
+    fn test_backward_error() {
+        let arr: Array2<f64> = Array2::from_shape_fn((6, 6), |(i, j)| 1.0 / (i + j + 1) as f64);
+        let rhs = Array1::from_shape_fn(6, |i| i as f64 - 2.0);
+        let x = solve(&arr, &rhs).unwrap();
+
+        // LU with partial pivoting is backward stable even for the ill-conditioned Hilbert matrix
+        assert!(normwise_backward_error(&arr, &x, &rhs).unwrap() < 1e-14);
+        assert!(componentwise_backward_error(&arr, &x, &rhs).unwrap() < 1e-12);
+
+        // a perturbed solution of a well-conditioned system
+        let arr: Array2<f64> = array![[4.0, 1.0], [2.0, 3.0]];
+        let x = array![0.1 + 1e-6, 0.6];
+        let eta = normwise_backward_error(&arr, &x, &array![1.0, 2.0]).unwrap();
+        assert!((eta - 4e-6 / 5.0).abs() < 1e-12);
+
+        // rows with a zero scale are solved exactly, and inconsistent rows are maximally wrong
+        let arr: Array2<f64> = array![[1.0, 0.0], [0.0, 0.0]];
+        let rhs = array![1.0, 0.0];
+        assert_eq!(
+            componentwise_backward_error(&arr, &array![1.0, 5.0], &rhs),
+            Ok(0.0)
+        );
+        assert_eq!(
+            componentwise_backward_error(&arr, &array![1.0, 5.0], &array![1.0, 1.0]),
+            Ok(1.0)
+        );
+        assert_eq!(
+            normwise_backward_error(&arr, &array![1.0], &rhs),
+            Err(LinalgError::DimensionMismatch)
+        );
     }
 }
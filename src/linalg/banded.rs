@@ -0,0 +1,489 @@
+//! Tridiagonal and banded linear systems
+//!
+//! Such systems arise from cubic splines and from implicit finite difference schemes, where
+//! storing the full matrix would waste both memory and time.
+
+use ndarray::prelude::*;
+use num::Float;
+
+use crate::linalg::LinalgError;
+
+/// Solve the tridiagonal system with subdiagonal *sub*, diagonal *diag* and superdiagonal *sup*
+/// using the Thomas algorithm
+///
+/// For *n* unknowns, *sub* and *sup* have length `n - 1`, with `sub[i] = A[i + 1, i]` and
+/// `sup[i] = A[i, i + 1]`. The algorithm does not pivot and is stable for diagonally dominant
+/// or symmetric positive definite matrices. Returns [`LinalgError::Singular`] if a zero pivot
+/// is encountered.
+///
+/// # Panics
+/// Panics if the lengths of the bands and *rhs* do not match
+///
+/// # Example
+/// ```
+/// use scialg::linalg::banded::solve_tridiagonal;
+///
+/// // 1D Laplacian
+/// let x = solve_tridiagonal(&[-1.0, -1.0], &[2.0, 2.0, 2.0], &[-1.0, -1.0], &[1.0, 0.0, 1.0]);
+///
+/// assert!(x.unwrap().iter().all(|e: &f64| (e - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - [Wikipedia: Tridiagonal matrix algorithm](https://en.wikipedia.org/wiki/Tridiagonal_matrix_algorithm)
+pub fn solve_tridiagonal<F: Float>(
+    sub: &[F],
+    diag: &[F],
+    sup: &[F],
+    rhs: &[F],
+) -> Result<Vec<F>, LinalgError> {
+    let n = diag.len();
+    assert!(n > 0);
+    assert_eq!(sub.len(), n - 1);
+    assert_eq!(sup.len(), n - 1);
+    assert_eq!(rhs.len(), n);
+
+    let mut c = vec![F::zero(); n];
+    let mut x = vec![F::zero(); n];
+
+    let mut beta = diag[0];
+    if beta == F::zero() {
+        return Err(LinalgError::Singular);
+    }
+    x[0] = rhs[0] / beta;
+    for i in 1..n {
+        c[i] = sup[i - 1] / beta;
+        beta = diag[i] - sub[i - 1] * c[i];
+        if beta == F::zero() {
+            return Err(LinalgError::Singular);
+        }
+        x[i] = (rhs[i] - sub[i - 1] * x[i - 1]) / beta;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] = x[i] - c[i + 1] * x[i + 1];
+    }
+
+    Ok(x)
+}
+
+/// Solve the cyclic tridiagonal system with additional corner entries *alpha* (bottom left,
+/// `A[n - 1, 0]`) and *beta* (top right, `A[0, n - 1]`) using the Sherman-Morrison formula
+///
+/// The bands are laid out as in [`solve_tridiagonal`]. Such systems arise from periodic
+/// boundary conditions. Returns [`LinalgError::Singular`] if a zero pivot is encountered.
+///
+/// # Panics
+/// Panics if there are fewer than three unknowns or the lengths of the bands and *rhs* do not
+/// match
+///
+/// # References
+///  - [Wikipedia: Tridiagonal matrix algorithm](https://en.wikipedia.org/wiki/Tridiagonal_matrix_algorithm#Variants)
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 2.7.2
+pub fn solve_cyclic_tridiagonal<F: Float>(
+    sub: &[F],
+    diag: &[F],
+    sup: &[F],
+    alpha: F,
+    beta: F,
+    rhs: &[F],
+) -> Result<Vec<F>, LinalgError> {
+    let n = diag.len();
+    assert!(n >= 3, "cyclic systems need at least three unknowns");
+    let gamma = if diag[0] == F::zero() {
+        -F::one()
+    } else {
+        -diag[0]
+    };
+
+    let mut bb = diag.to_vec();
+    bb[0] = diag[0] - gamma;
+    bb[n - 1] = diag[n - 1] - alpha * beta / gamma;
+    let mut x = solve_tridiagonal(sub, &bb, sup, rhs)?;
+
+    let mut u = vec![F::zero(); n];
+    u[0] = gamma;
+    u[n - 1] = alpha;
+    let z = solve_tridiagonal(sub, &bb, sup, &u)?;
+
+    let denom = F::one() + z[0] + beta * z[n - 1] / gamma;
+    if denom == F::zero() {
+        return Err(LinalgError::Singular);
+    }
+    let fact = (x[0] + beta * x[n - 1] / gamma) / denom;
+    for (xi, zi) in x.iter_mut().zip(z) {
+        *xi = *xi - fact * zi;
+    }
+
+    Ok(x)
+}
+
+/// Square band matrix with *kl* subdiagonals and *ku* superdiagonals in compact storage
+///
+/// The entry `A[i, j]` is stored at `band[ku + i - j, j]`, as in LAPACK, so the diagonals are
+/// the rows of the band array.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::linalg::banded::BandedMatrix;
+///
+/// let mut arr = BandedMatrix::zeros(3, 1, 0);
+/// arr.set(0, 0, 1.0);
+/// arr.set(1, 0, 2.0);
+///
+/// assert_eq!(arr.get(1, 0), 2.0);
+/// assert_eq!(arr.to_dense(), array![[1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, 0.0]]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandedMatrix<F> {
+    band: Array2<F>,
+    kl: usize,
+    ku: usize,
+}
+
+impl<F: Float> BandedMatrix<F> {
+    /// Create the *n* x *n* zero matrix with *kl* subdiagonals and *ku* superdiagonals
+    pub fn zeros(n: usize, kl: usize, ku: usize) -> Self {
+        BandedMatrix {
+            band: Array2::zeros((kl + ku + 1, n)),
+            kl,
+            ku,
+        }
+    }
+
+    /// Create a band matrix from the band of *arr*, ignoring all entries outside of it
+    ///
+    /// # Panics
+    /// Panics if *arr* is not a square matrix
+    pub fn from_dense(arr: &Array2<F>, kl: usize, ku: usize) -> Self {
+        assert!(arr.is_square());
+        let n = arr.nrows();
+        let mut res = Self::zeros(n, kl, ku);
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..n.min(j + kl + 1) {
+                res.set(i, j, arr[(i, j)]);
+            }
+        }
+
+        res
+    }
+
+    /// Return the dimension
+    pub fn dim(&self) -> usize {
+        self.band.ncols()
+    }
+
+    /// Return the number of subdiagonals
+    pub fn kl(&self) -> usize {
+        self.kl
+    }
+
+    /// Return the number of superdiagonals
+    pub fn ku(&self) -> usize {
+        self.ku
+    }
+
+    /// Return the compact band storage
+    pub fn band(&self) -> &Array2<F> {
+        &self.band
+    }
+
+    /// Return the entry at (*i*, *j*), which is zero outside of the band
+    ///
+    /// # Panics
+    /// Panics if the position is out of bounds
+    pub fn get(&self, i: usize, j: usize) -> F {
+        assert!(i < self.dim() && j < self.dim());
+
+        if i + self.ku >= j && i <= j + self.kl {
+            self.band[(self.ku + i - j, j)]
+        } else {
+            F::zero()
+        }
+    }
+
+    /// Set the entry at (*i*, *j*) to *value*
+    ///
+    /// # Panics
+    /// Panics if the position is outside of the band
+    pub fn set(&mut self, i: usize, j: usize, value: F) {
+        assert!(i < self.dim() && j < self.dim());
+        assert!(
+            i + self.ku >= j && i <= j + self.kl,
+            "position is outside of the band"
+        );
+
+        self.band[(self.ku + i - j, j)] = value;
+    }
+
+    /// Return the product `A x`
+    ///
+    /// # Panics
+    /// Panics if the length of *x* does not match the dimension
+    pub fn matvec(&self, x: &Array1<F>) -> Array1<F> {
+        let n = self.dim();
+        assert_eq!(x.len(), n);
+        let mut res = Array1::zeros(n);
+
+        for j in 0..n {
+            for i in j.saturating_sub(self.ku)..n.min(j + self.kl + 1) {
+                res[i] = res[i] + self.band[(self.ku + i - j, j)] * x[j];
+            }
+        }
+
+        res
+    }
+
+    /// Convert to a dense matrix
+    pub fn to_dense(&self) -> Array2<F> {
+        let n = self.dim();
+        Array2::from_shape_fn((n, n), |(i, j)| self.get(i, j))
+    }
+}
+
+/// LU decomposition with partial pivoting `P A = L U` of a band matrix
+///
+/// Row interchanges widen the upper band of *U* to `kl + ku` superdiagonals, but the storage
+/// stays proportional to `n (2 kl + ku + 1)`.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array1};
+///
+/// use scialg::linalg::banded::{BandedLU, BandedMatrix};
+///
+/// let arr = BandedMatrix::from_dense(&array![[0.0, 1.0, 0.0], [2.0, 1.0, 1.0], [0.0, 1.0, 3.0]], 1, 1);
+/// let lu = BandedLU::new(&arr).unwrap();
+/// let x: Array1<f64> = lu.solve(&array![1.0, 4.0, 4.0]);
+///
+/// assert!((&x - &array![1.0, 1.0, 1.0]).iter().all(|e| e.abs() < 1e-12));
+/// assert!((lu.determinant() + 6.0).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Band matrix](https://en.wikipedia.org/wiki/Band_matrix)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 4.3
+#[derive(Debug, Clone)]
+pub struct BandedLU<F> {
+    lu: Array2<F>,
+    perm: Vec<usize>,
+    kl: usize,
+    ku: usize,
+}
+
+impl<F: Float> BandedLU<F> {
+    /// Decompose *arr*
+    ///
+    /// Returns [`LinalgError::Singular`] if *arr* is singular to working precision or a pivot
+    /// is not finite.
+    pub fn new(arr: &BandedMatrix<F>) -> Result<Self, LinalgError> {
+        let n = arr.dim();
+        let (kl, ku) = (arr.kl, arr.ku);
+        let width = kl + ku;
+
+        // A[i, j] is stored at lu[width + i - j, j]
+        let mut lu = Array2::zeros((2 * kl + ku + 1, n));
+        lu.slice_mut(s![kl.., ..]).assign(&arr.band);
+        let norm = (0..n)
+            .map(|j| arr.band.column(j).fold(F::zero(), |acc, e| acc + e.abs()))
+            .fold(F::zero(), F::max);
+        let tol = F::epsilon() * norm;
+
+        let mut perm = vec![0; n];
+        for k in 0..n {
+            let last = (n - 1).min(k + kl);
+            let right = (n - 1).min(k + width);
+
+            let p = (k..=last).fold(k, |p, i| {
+                if lu[(width + i - k, k)].abs() > lu[(width + p - k, k)].abs() {
+                    i
+                } else {
+                    p
+                }
+            });
+            perm[k] = p;
+            let pivot = lu[(width + p - k, k)].abs();
+            // a NaN in the band surfaces as a non-finite pivot
+            if !pivot.is_finite() || pivot <= tol {
+                return Err(LinalgError::Singular);
+            }
+            if p != k {
+                for j in k..=right {
+                    lu.swap((width + k - j, j), (width + p - j, j));
+                }
+            }
+
+            let pivot = lu[(width, k)];
+            for i in k + 1..=last {
+                let factor = lu[(width + i - k, k)] / pivot;
+                lu[(width + i - k, k)] = factor;
+                for j in k + 1..=right {
+                    lu[(width + i - j, j)] =
+                        lu[(width + i - j, j)] - factor * lu[(width + k - j, j)];
+                }
+            }
+        }
+
+        Ok(BandedLU { lu, perm, kl, ku })
+    }
+
+    /// Solve `A x = b`
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.perm.len();
+        assert_eq!(b.len(), n);
+        let width = self.kl + self.ku;
+        let mut x = b.clone();
+
+        for k in 0..n {
+            x.swap(k, self.perm[k]);
+            for i in k + 1..=(n - 1).min(k + self.kl) {
+                x[i] = x[i] - self.lu[(width + i - k, k)] * x[k];
+            }
+        }
+        for i in (0..n).rev() {
+            let mut sum = x[i];
+            for j in i + 1..=(n - 1).min(i + width) {
+                sum = sum - self.lu[(width + i - j, j)] * x[j];
+            }
+            x[i] = sum / self.lu[(width, i)];
+        }
+
+        x
+    }
+
+    /// Return the determinant of *A*
+    pub fn determinant(&self) -> F {
+        let swaps = self
+            .perm
+            .iter()
+            .enumerate()
+            .filter(|(k, p)| k != *p)
+            .count();
+        let det = self
+            .lu
+            .row(self.kl + self.ku)
+            .fold(F::one(), |acc, e| acc * *e);
+
+        if swaps % 2 == 0 {
+            det
+        } else {
+            -det
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dense_tridiagonal(sub: &[f64], diag: &[f64], sup: &[f64]) -> Array2<f64> {
+        let n = diag.len();
+        let mut arr = Array2::from_diag(&Array1::from_vec(diag.to_vec()));
+        for i in 0..n - 1 {
+            arr[(i + 1, i)] = sub[i];
+            arr[(i, i + 1)] = sup[i];
+        }
+
+        arr
+    }
+
+    #[test]
+    fn test_solve_tridiagonal() {
+        let sub = [1.0, -2.0, 0.5, 1.5];
+        let diag = [4.0, 5.0, 6.0, 3.0, 4.0];
+        let sup = [2.0, 1.0, -1.0, 0.5];
+        let rhs = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let x = Array1::from_vec(solve_tridiagonal(&sub, &diag, &sup, &rhs).unwrap());
+        let residual =
+            dense_tridiagonal(&sub, &diag, &sup).dot(&x) - Array1::from_vec(rhs.to_vec());
+        assert!(residual.iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_solve_tridiagonal_singular() {
+        assert_eq!(
+            solve_tridiagonal(&[1.0], &[1.0, 1.0], &[1.0], &[1.0, 2.0]).unwrap_err(),
+            LinalgError::Singular
+        );
+    }
+
+    #[test]
+    fn test_solve_cyclic_tridiagonal() {
+        let sub = [1.0, -2.0, 0.5, 1.5];
+        let diag = [4.0, 5.0, 6.0, 3.0, 4.0];
+        let sup = [2.0, 1.0, -1.0, 0.5];
+        let rhs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let (alpha, beta) = (0.7, -1.2);
+
+        let x = Array1::from_vec(
+            solve_cyclic_tridiagonal(&sub, &diag, &sup, alpha, beta, &rhs).unwrap(),
+        );
+        let mut arr = dense_tridiagonal(&sub, &diag, &sup);
+        arr[(4, 0)] = alpha;
+        arr[(0, 4)] = beta;
+        let residual = arr.dot(&x) - Array1::from_vec(rhs.to_vec());
+        assert!(residual.iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_banded_matrix() {
+        let dense = Array2::from_shape_fn((6, 6), |(i, j)| {
+            if i <= j + 2 && j <= i + 1 {
+                (i * 6 + j) as f64 + 1.0
+            } else {
+                0.0
+            }
+        });
+        let banded = BandedMatrix::from_dense(&dense, 2, 1);
+        let x = Array1::linspace(-1.0, 1.0, 6);
+
+        assert_eq!(banded.to_dense(), dense);
+        assert_eq!(banded.matvec(&x), dense.dot(&x));
+    }
+
+    #[test]
+    fn test_banded_lu() {
+        // needs pivoting: zero diagonal in the first row
+        let dense = Array2::from_shape_fn((8, 8), |(i, j)| {
+            if i == j && i == 0 {
+                0.0
+            } else if i <= j + 2 && j <= i + 1 {
+                ((i + 2 * j) as f64).sin() + if i == j { 0.5 } else { 0.0 }
+            } else {
+                0.0
+            }
+        });
+        let lu = BandedLU::new(&BandedMatrix::from_dense(&dense, 2, 1)).unwrap();
+        let b = Array1::linspace(1.0, 2.0, 8);
+
+        let x = lu.solve(&b);
+        let residual = dense.dot(&x) - &b;
+        assert!(residual.iter().all(|e| e.abs() < 1e-12));
+
+        let det = crate::linalg::lu::LU::new(&dense).unwrap().determinant();
+        assert!((lu.determinant() - det).abs() < 1e-12 * det.abs().max(1.0));
+    }
+
+    #[test]
+    fn test_banded_lu_singular() {
+        let arr = BandedMatrix::from_dense(&array![[1.0, 2.0], [2.0, 4.0]], 1, 1);
+
+        assert_eq!(BandedLU::new(&arr).unwrap_err(), LinalgError::Singular);
+    }
+
+    #[test]
+    fn test_banded_lu_nan() {
+        let arr = BandedMatrix::from_dense(
+            &array![[2.0, 1.0, 0.0], [f64::NAN, 2.0, 1.0], [0.0, 1.0, 2.0]],
+            1,
+            1,
+        );
+
+        assert_eq!(BandedLU::new(&arr).unwrap_err(), LinalgError::Singular);
+    }
+}
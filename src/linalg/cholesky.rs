@@ -0,0 +1,494 @@
+//! Cholesky and LDL^T decompositions of symmetric matrices
+
+use ndarray::prelude::*;
+
+use crate::linalg::gemm::gemm;
+use crate::linalg::lu::norm_1;
+use crate::linalg::scalar::RealScalar;
+use crate::linalg::LinalgError;
+
+/// Number of columns factorized before the trailing matrix is updated
+const BLOCK: usize = 64;
+
+/// Cholesky decomposition `A = L L^T` of a symmetric positive definite matrix
+///
+/// Only the lower triangle of *A* is referenced.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::cholesky::Cholesky;
+///
+/// let arr: Array2<f64> = array![[4.0, 2.0], [2.0, 3.0]];
+/// let chol = Cholesky::new(&arr).unwrap();
+///
+/// assert_eq!(chol.l(), array![[2.0, 0.0], [1.0, 2.0_f64.sqrt()]]);
+/// assert!((chol.log_determinant() - 8.0_f64.ln()).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Cholesky decomposition](https://en.wikipedia.org/wiki/Cholesky_decomposition)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 4.2
+#[derive(Debug, Clone)]
+pub struct Cholesky<F> {
+    l: Array2<F>,
+}
+
+impl<F: RealScalar> Cholesky<F> {
+    /// Decompose *arr*
+    ///
+    /// Returns [`LinalgError::NotPositiveDefinite`] if *arr* is not positive definite.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let mut l = arr.to_owned();
+
+        // left-looking within a panel of columns, right-looking between panels, so that the
+        // trailing matrix is updated with a single matrix product per panel
+        for k0 in (0..n).step_by(BLOCK) {
+            let k1 = n.min(k0 + BLOCK);
+
+            for j in k0..k1 {
+                let mut d = l[(j, j)];
+                for k in k0..j {
+                    d = d - l[(j, k)] * l[(j, k)];
+                }
+                if d <= F::zero() || d.is_nan() {
+                    return Err(LinalgError::NotPositiveDefinite);
+                }
+                let ljj = d.sqrt();
+                l[(j, j)] = ljj;
+
+                for i in j + 1..n {
+                    let mut sum = l[(i, j)];
+                    for k in k0..j {
+                        sum = sum - l[(i, k)] * l[(j, k)];
+                    }
+                    l[(i, j)] = sum / ljj;
+                }
+            }
+            if k1 == n {
+                break;
+            }
+
+            // A22 <- A22 - L21 L21^T
+            let (l21, a22) = l.multi_slice_mut((s![k1.., k0..k1], s![k1.., k1..]));
+            gemm(-F::one(), l21.view(), l21.t(), F::one(), a22);
+        }
+        for i in 0..n {
+            for j in i + 1..n {
+                l[(i, j)] = F::zero();
+            }
+        }
+
+        Ok(Cholesky { l })
+    }
+
+    /// Return the lower triangular factor *L*
+    pub fn l(&self) -> Array2<F> {
+        self.l.clone()
+    }
+
+    /// Solve `A x = b`
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.l.nrows();
+        assert_eq!(b.len(), n);
+        let mut x = b.to_owned();
+
+        for i in 0..n {
+            for k in 0..i {
+                x[i] = x[i] - self.l[(i, k)] * x[k];
+            }
+            x[i] = x[i] / self.l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                x[i] = x[i] - self.l[(k, i)] * x[k];
+            }
+            x[i] = x[i] / self.l[(i, i)];
+        }
+
+        x
+    }
+
+    /// Solve `A X = B` for every column of *B*
+    ///
+    /// # Panics
+    /// Panics if the number of rows of *b* does not match the dimension of *A*
+    pub fn solve_many(&self, b: &Array2<F>) -> Array2<F> {
+        let mut x = Array2::zeros(b.raw_dim());
+
+        for (col, mut out) in b.columns().into_iter().zip(x.columns_mut()) {
+            out.assign(&self.solve(&col.to_owned()));
+        }
+
+        x
+    }
+
+    /// Return the inverse of *A*
+    pub fn inverse(&self) -> Array2<F> {
+        let n = self.l.nrows();
+
+        self.solve_many(&Array2::from_diag_elem(n, F::one()))
+    }
+
+    /// Return the natural logarithm of the determinant of *A*
+    pub fn log_determinant(&self) -> F {
+        let two = F::one() + F::one();
+
+        self.l
+            .diag()
+            .iter()
+            .fold(F::zero(), |acc, d| acc + two * d.ln())
+    }
+
+    /// Update the decomposition to the one of `A + x x^T` in O(n^2) operations
+    pub fn update(&mut self, x: &Array1<F>) {
+        self.rank_one(x, F::one())
+            .expect("positive definite matrices stay positive definite on update");
+    }
+
+    /// Update the decomposition to the one of `A - x x^T` in O(n^2) operations
+    ///
+    /// Returns [`LinalgError::NotPositiveDefinite`] and leaves the decomposition unchanged
+    /// if the downdated matrix is not positive definite.
+    pub fn downdate(&mut self, x: &Array1<F>) -> Result<(), LinalgError> {
+        self.rank_one(x, -F::one())
+    }
+
+    fn rank_one(&mut self, x: &Array1<F>, sign: F) -> Result<(), LinalgError> {
+        let n = self.l.nrows();
+        assert_eq!(x.len(), n);
+        let mut l = self.l.clone();
+        let mut x = x.to_owned();
+
+        for k in 0..n {
+            let lkk = l[(k, k)];
+            let r2 = lkk * lkk + sign * x[k] * x[k];
+            if r2 <= F::zero() {
+                return Err(LinalgError::NotPositiveDefinite);
+            }
+            let r = r2.sqrt();
+            let c = r / lkk;
+            let s = x[k] / lkk;
+            l[(k, k)] = r;
+            for i in k + 1..n {
+                l[(i, k)] = (l[(i, k)] + sign * s * x[i]) / c;
+                x[i] = c * x[i] - s * l[(i, k)];
+            }
+        }
+
+        self.l = l;
+        Ok(())
+    }
+}
+
+/// LDL^T decomposition `A = L D L^T` of a symmetric matrix without pivoting
+///
+/// *L* is unit lower triangular and *D* diagonal. Unlike the Cholesky decomposition no square
+/// roots are taken and indefinite matrices are supported, as long as no leading principal
+/// minor vanishes. Only the lower triangle of *A* is referenced.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::cholesky::LDL;
+///
+/// let arr: Array2<f64> = array![[1.0, 2.0], [2.0, 1.0]];
+/// let ldl = LDL::new(&arr).unwrap();
+///
+/// assert_eq!(ldl.d(), array![1.0, -3.0]);
+/// assert_eq!(ldl.solve(&array![3.0, 3.0]), array![1.0, 1.0]);
+/// ```
+///
+/// # References
+///  - [Wikipedia: LDL decomposition](https://en.wikipedia.org/wiki/Cholesky_decomposition#LDL_decomposition)
+///  - P. E. Gill, G. H. Golub, W. Murray and M. A. Saunders, *Methods for modifying matrix
+///    factorizations*, Math. Comp. 28 (1974)
+#[derive(Debug, Clone)]
+pub struct LDL<F> {
+    l: Array2<F>,
+    d: Array1<F>,
+    tol: F,
+}
+
+impl<F: RealScalar> LDL<F> {
+    /// Decompose *arr*
+    ///
+    /// Returns [`LinalgError::Singular`] if a pivot vanishes relative to the norm of *arr*.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let tol = F::epsilon() * norm_1(arr);
+        let mut l = Array2::from_diag_elem(n, F::one());
+        let mut d = Array1::zeros(n);
+
+        for j in 0..n {
+            let mut dj = arr[(j, j)];
+            for k in 0..j {
+                dj = dj - l[(j, k)] * l[(j, k)] * d[k];
+            }
+            if dj.abs() <= tol || dj.is_nan() {
+                return Err(LinalgError::Singular);
+            }
+            d[j] = dj;
+
+            for i in j + 1..n {
+                let mut sum = arr[(i, j)];
+                for k in 0..j {
+                    sum = sum - l[(i, k)] * l[(j, k)] * d[k];
+                }
+                l[(i, j)] = sum / dj;
+            }
+        }
+
+        Ok(LDL { l, d, tol })
+    }
+
+    /// Return the unit lower triangular factor *L*
+    pub fn l(&self) -> Array2<F> {
+        self.l.clone()
+    }
+
+    /// Return the diagonal of *D*
+    pub fn d(&self) -> Array1<F> {
+        self.d.clone()
+    }
+
+    /// Solve `A x = b`
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.l.nrows();
+        assert_eq!(b.len(), n);
+        let mut x = b.to_owned();
+
+        for i in 0..n {
+            for k in 0..i {
+                x[i] = x[i] - self.l[(i, k)] * x[k];
+            }
+        }
+        for i in 0..n {
+            x[i] = x[i] / self.d[i];
+        }
+        for i in (0..n).rev() {
+            for k in i + 1..n {
+                x[i] = x[i] - self.l[(k, i)] * x[k];
+            }
+        }
+
+        x
+    }
+
+    /// Solve `A X = B` for every column of *B*
+    ///
+    /// # Panics
+    /// Panics if the number of rows of *b* does not match the dimension of *A*
+    pub fn solve_many(&self, b: &Array2<F>) -> Array2<F> {
+        let mut x = Array2::zeros(b.raw_dim());
+
+        for (col, mut out) in b.columns().into_iter().zip(x.columns_mut()) {
+            out.assign(&self.solve(&col.to_owned()));
+        }
+
+        x
+    }
+
+    /// Return the inverse of *A*
+    pub fn inverse(&self) -> Array2<F> {
+        let n = self.l.nrows();
+
+        self.solve_many(&Array2::from_diag_elem(n, F::one()))
+    }
+
+    /// Return the determinant of *A*
+    pub fn determinant(&self) -> F {
+        self.d.iter().fold(F::one(), |acc, d| acc * *d)
+    }
+
+    /// Return the natural logarithm of the absolute value of the determinant of *A*
+    pub fn log_determinant(&self) -> F {
+        self.d.iter().fold(F::zero(), |acc, d| acc + d.abs().ln())
+    }
+
+    /// Update the decomposition to the one of `A + x x^T` in O(n^2) operations
+    ///
+    /// Returns [`LinalgError::Singular`] and leaves the decomposition unchanged if the
+    /// updated matrix has a vanishing leading principal minor.
+    pub fn update(&mut self, x: &Array1<F>) -> Result<(), LinalgError> {
+        self.rank_one(x, F::one())
+    }
+
+    /// Update the decomposition to the one of `A - x x^T` in O(n^2) operations
+    ///
+    /// Returns [`LinalgError::Singular`] and leaves the decomposition unchanged if the
+    /// downdated matrix has a vanishing leading principal minor.
+    pub fn downdate(&mut self, x: &Array1<F>) -> Result<(), LinalgError> {
+        self.rank_one(x, -F::one())
+    }
+
+    fn rank_one(&mut self, x: &Array1<F>, alpha: F) -> Result<(), LinalgError> {
+        let n = self.l.nrows();
+        assert_eq!(x.len(), n);
+        let mut l = self.l.clone();
+        let mut d = self.d.clone();
+        let mut w = x.to_owned();
+        let mut alpha = alpha;
+
+        for j in 0..n {
+            let p = w[j];
+            let dj = d[j] + alpha * p * p;
+            if dj.abs() <= self.tol || dj.is_nan() {
+                return Err(LinalgError::Singular);
+            }
+            let beta = p * alpha / dj;
+            alpha = d[j] * alpha / dj;
+            d[j] = dj;
+            for r in j + 1..n {
+                w[r] = w[r] - p * l[(r, j)];
+                l[(r, j)] = l[(r, j)] + beta * w[r];
+            }
+        }
+
+        self.l = l;
+        self.d = d;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    fn spd() -> Array2<f64> {
+        array![
+            [6.0, 2.0, 1.0, 0.5],
+            [2.0, 5.0, 2.0, 1.0],
+            [1.0, 2.0, 4.0, 1.5],
+            [0.5, 1.0, 1.5, 3.0]
+        ]
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let arr = spd();
+        let chol = Cholesky::new(&arr).unwrap();
+
+        assert_close(&chol.l().dot(&chol.l().t()), &arr, 1e-12);
+        assert_close(&chol.inverse().dot(&arr), &Array2::eye(4), 1e-12);
+
+        let b = array![1.0, -2.0, 0.5, 3.0];
+        let x = chol.solve(&b);
+        assert!(arr
+            .dot(&x)
+            .iter()
+            .zip(b.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+
+        let det = crate::linalg::lu::LU::new(&arr).unwrap().determinant();
+        assert!((chol.log_determinant() - det.ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky_blocked() {
+        // several blocks and a partial last block
+        let n = 150;
+        let b = Array2::from_shape_fn((n, n), |(i, j)| ((i * i + 3 * j * j + i * j) as f64).sin());
+        let arr = b.dot(&b.t()) + Array2::<f64>::eye(n);
+        let chol = Cholesky::new(&arr).unwrap();
+
+        assert_close(&chol.l().dot(&chol.l().t()), &arr, 1e-10);
+    }
+
+    #[test]
+    fn test_cholesky_not_positive_definite() {
+        let arr = array![[1.0, 2.0], [2.0, 1.0]];
+
+        assert_eq!(
+            Cholesky::new(&arr).unwrap_err(),
+            LinalgError::NotPositiveDefinite
+        );
+    }
+
+    #[test]
+    fn test_cholesky_update_downdate() {
+        let arr = spd();
+        let x = array![0.5, -1.0, 2.0, 0.3];
+        let updated = &arr
+            + &x.view()
+                .insert_axis(Axis(1))
+                .dot(&x.view().insert_axis(Axis(0)));
+
+        let mut chol = Cholesky::new(&arr).unwrap();
+        chol.update(&x);
+        assert_close(&chol.l(), &Cholesky::new(&updated).unwrap().l(), 1e-12);
+
+        chol.downdate(&x).unwrap();
+        assert_close(&chol.l(), &Cholesky::new(&arr).unwrap().l(), 1e-12);
+
+        let l = chol.l();
+        assert_eq!(
+            chol.downdate(&array![0.0, 0.0, 0.0, 2.0]),
+            Err(LinalgError::NotPositiveDefinite)
+        );
+        assert_eq!(chol.l(), l);
+    }
+
+    #[test]
+    fn test_ldl_indefinite() {
+        let arr = array![[4.0, 2.0, -2.0], [2.0, -3.0, 1.0], [-2.0, 1.0, 5.0]];
+        let ldl = LDL::new(&arr).unwrap();
+
+        let d = Array2::from_diag(&ldl.d());
+        assert_close(&ldl.l().dot(&d).dot(&ldl.l().t()), &arr, 1e-12);
+        assert_close(&ldl.inverse().dot(&arr), &Array2::eye(3), 1e-12);
+
+        let det = crate::linalg::lu::LU::new(&arr).unwrap().determinant();
+        assert!((ldl.determinant() - det).abs() < 1e-10);
+        assert!((ldl.log_determinant() - det.abs().ln()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ldl_update_downdate() {
+        let arr = array![[4.0, 2.0, -2.0], [2.0, -3.0, 1.0], [-2.0, 1.0, 5.0]];
+        let x = array![1.0, 0.5, -1.0];
+        let updated = &arr
+            + &x.view()
+                .insert_axis(Axis(1))
+                .dot(&x.view().insert_axis(Axis(0)));
+
+        let mut ldl = LDL::new(&arr).unwrap();
+        ldl.update(&x).unwrap();
+        let reference = LDL::new(&updated).unwrap();
+        assert_close(&ldl.l(), &reference.l(), 1e-12);
+        assert!((&ldl.d() - &reference.d()).iter().all(|e| e.abs() < 1e-12));
+
+        ldl.downdate(&x).unwrap();
+        let b = array![1.0, 2.0, 3.0];
+        let x = ldl.solve(&b);
+        assert!(arr
+            .dot(&x)
+            .iter()
+            .zip(b.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+}
@@ -0,0 +1,1039 @@
+//! Eigenvalue problems
+
+use ndarray::prelude::*;
+use num::{Complex, Float, One, Zero};
+
+use crate::linalg::scalar::Scalar;
+use crate::linalg::LinalgError;
+
+/// Eigendecomposition `A = V diag(values) V^T` of a real symmetric matrix
+///
+/// The eigenvalues are sorted in ascending order and the orthonormal eigenvectors are stored in
+/// the corresponding columns of *V*. Only the lower triangle of *A* is referenced.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::eigen::SymmetricEigen;
+///
+/// let arr: Array2<f64> = array![[2.0, 1.0], [1.0, 2.0]];
+/// let eig = SymmetricEigen::new(&arr).unwrap();
+///
+/// assert!((eig.values()[0] - 1.0).abs() < 1e-12);
+/// assert!((eig.values()[1] - 3.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymmetricEigen<F> {
+    values: Array1<F>,
+    vectors: Array2<F>,
+}
+
+impl<F: Float> SymmetricEigen<F> {
+    /// Decompose *arr* with the cyclic Jacobi method for small matrices (n <= 10) and with
+    /// Householder tridiagonalization and the implicit QL algorithm otherwise
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if arr.nrows() <= 10 {
+            Self::jacobi(arr)
+        } else {
+            Self::tridiagonal_ql(arr)
+        }
+    }
+
+    /// Decompose *arr* with cyclic Jacobi rotations
+    ///
+    /// Returns [`LinalgError::NoConvergence`] if the off-diagonal elements do not vanish
+    /// after 50 sweeps.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    ///
+    /// # References
+    ///  - [Wikipedia: Jacobi eigenvalue algorithm](https://en.wikipedia.org/wiki/Jacobi_eigenvalue_algorithm)
+    ///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 8.5
+    pub fn jacobi(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let mut a = symmetrize(arr);
+        let mut v = Array2::from_diag_elem(n, F::one());
+        let two = F::one() + F::one();
+        let norm2 = a.iter().fold(F::zero(), |acc, e| acc + *e * *e);
+
+        let mut converged = false;
+        for _sweep in 0..50 {
+            let mut off = F::zero();
+            for p in 0..n {
+                for q in p + 1..n {
+                    off = off + a[(p, q)] * a[(p, q)];
+                }
+            }
+            if off <= F::epsilon() * F::epsilon() * norm2 {
+                converged = true;
+                break;
+            }
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    if a[(p, q)] == F::zero() {
+                        continue;
+                    }
+                    let theta = (a[(q, q)] - a[(p, p)]) / (two * a[(p, q)]);
+                    let t = if theta >= F::zero() {
+                        F::one() / (theta + (theta * theta + F::one()).sqrt())
+                    } else {
+                        -F::one() / (-theta + (theta * theta + F::one()).sqrt())
+                    };
+                    let c = F::one() / (t * t + F::one()).sqrt();
+                    let s = t * c;
+
+                    // A <- J^T A J
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    a[(p, q)] = F::zero();
+                    a[(q, p)] = F::zero();
+
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(LinalgError::NoConvergence);
+        }
+
+        Ok(Self::sorted(a.diag().to_owned(), v))
+    }
+
+    /// Decompose *arr* by reducing it to tridiagonal form with Householder reflections and
+    /// diagonalizing the tridiagonal matrix with the implicit QL algorithm
+    ///
+    /// Returns [`LinalgError::NoConvergence`] if an eigenvalue does not converge within
+    /// 30 iterations.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    ///
+    /// # References
+    ///  - [Wikipedia: Householder transformation](https://en.wikipedia.org/wiki/Householder_transformation#Tridiagonalization)
+    ///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., sections 11.4 and 11.5
+    pub fn tridiagonal_ql(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let (mut d, mut e, mut z) = tridiagonalize(&symmetrize(arr));
+        tridiagonal_ql_implicit(&mut d, &mut e, &mut z)?;
+
+        Ok(Self::sorted(d, z))
+    }
+
+    /// Return the eigenvalues in ascending order
+    pub fn values(&self) -> Array1<F> {
+        self.values.clone()
+    }
+
+    /// Return the eigenvectors as columns
+    pub fn vectors(&self) -> Array2<F> {
+        self.vectors.clone()
+    }
+
+    fn sorted(values: Array1<F>, vectors: Array2<F>) -> Self {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by(|i, j| values[*i].partial_cmp(&values[*j]).unwrap());
+
+        SymmetricEigen {
+            values: order.iter().map(|i| values[*i]).collect(),
+            vectors: vectors.select(Axis(1), &order),
+        }
+    }
+}
+
+/// Eigendecomposition `A = V diag(values) V^H` of a Hermitian matrix
+///
+/// The eigenvalues are real and sorted in ascending order, the orthonormal eigenvectors are
+/// stored in the corresponding columns of *V*. Only the lower triangle of *A* is referenced. For
+/// real symmetric matrices this is the Jacobi method of [`SymmetricEigen`].
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+/// use num::Complex;
+///
+/// use scialg::linalg::eigen::HermitianEigen;
+///
+/// // Pauli matrix sigma_y
+/// let arr = array![
+///     [Complex::new(0.0_f64, 0.0), Complex::new(0.0, -1.0)],
+///     [Complex::new(0.0, 1.0), Complex::new(0.0, 0.0)]
+/// ];
+/// let eig = HermitianEigen::new(&arr).unwrap();
+///
+/// assert!((eig.values()[0] + 1.0).abs() < 1e-12);
+/// assert!((eig.values()[1] - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HermitianEigen<F: Scalar> {
+    values: Array1<F::Real>,
+    vectors: Array2<F>,
+}
+
+impl<F: Scalar> HermitianEigen<F> {
+    /// Decompose *arr* with cyclic complex Jacobi rotations
+    ///
+    /// Each off-diagonal element is first made real by a phase on its column and then
+    /// annihilated by a real rotation. Returns [`LinalgError::NoConvergence`] if the
+    /// off-diagonal elements do not vanish after 50 sweeps.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    ///
+    /// # References
+    ///  - [Wikipedia: Jacobi eigenvalue algorithm](https://en.wikipedia.org/wiki/Jacobi_eigenvalue_algorithm)
+    ///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 8.5
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let mut a = Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => arr[(i, j)],
+            std::cmp::Ordering::Equal => F::from_real(arr[(i, i)].re()),
+            std::cmp::Ordering::Less => arr[(j, i)].conj(),
+        });
+        let mut v = Array2::from_diag_elem(n, F::one());
+        let one = F::Real::one();
+        let two = one + one;
+        let eps = F::Real::epsilon();
+        let norm2 = a
+            .iter()
+            .fold(F::Real::zero(), |acc, e| acc + e.modulus_squared());
+
+        let mut converged = false;
+        for _sweep in 0..50 {
+            let mut off = F::Real::zero();
+            for p in 0..n {
+                for q in p + 1..n {
+                    off = off + a[(p, q)].modulus_squared();
+                }
+            }
+            if off <= eps * eps * norm2 {
+                converged = true;
+                break;
+            }
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    let abs_apq = a[(p, q)].modulus();
+                    if abs_apq == F::Real::zero() {
+                        continue;
+                    }
+
+                    // A <- D^H A D with D = diag(.., conj(phase of a_pq), ..) makes a_pq real
+                    let phase = a[(p, q)].conj().scale(one / abs_apq);
+                    for k in 0..n {
+                        a[(k, q)] = a[(k, q)] * phase;
+                    }
+                    for k in 0..n {
+                        a[(q, k)] = a[(q, k)] * phase.conj();
+                    }
+                    for k in 0..n {
+                        v[(k, q)] = v[(k, q)] * phase;
+                    }
+
+                    let theta = (a[(q, q)].re() - a[(p, p)].re()) / (two * abs_apq);
+                    let t = if theta >= F::Real::zero() {
+                        one / (theta + (theta * theta + one).sqrt())
+                    } else {
+                        -one / (-theta + (theta * theta + one).sqrt())
+                    };
+                    let c = one / (t * t + one).sqrt();
+                    let s = t * c;
+
+                    // A <- J^T A J
+                    for k in 0..n {
+                        let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                        a[(k, p)] = akp.scale(c) - akq.scale(s);
+                        a[(k, q)] = akp.scale(s) + akq.scale(c);
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                        a[(p, k)] = apk.scale(c) - aqk.scale(s);
+                        a[(q, k)] = apk.scale(s) + aqk.scale(c);
+                    }
+                    a[(p, q)] = F::zero();
+                    a[(q, p)] = F::zero();
+
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = vkp.scale(c) - vkq.scale(s);
+                        v[(k, q)] = vkp.scale(s) + vkq.scale(c);
+                    }
+                }
+            }
+        }
+        if !converged {
+            return Err(LinalgError::NoConvergence);
+        }
+
+        let values: Vec<F::Real> = a.diag().iter().map(|e| e.re()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|i, j| values[*i].partial_cmp(&values[*j]).unwrap());
+
+        Ok(HermitianEigen {
+            values: order.iter().map(|i| values[*i]).collect(),
+            vectors: v.select(Axis(1), &order),
+        })
+    }
+
+    /// Return the eigenvalues in ascending order
+    pub fn values(&self) -> Array1<F::Real> {
+        self.values.clone()
+    }
+
+    /// Return the eigenvectors as columns
+    pub fn vectors(&self) -> Array2<F> {
+        self.vectors.clone()
+    }
+}
+
+/// Eigendecomposition of a general real square matrix
+///
+/// The eigenvalues are complex in general; complex eigenvalues come in conjugate pairs. They are
+/// sorted by ascending real part and then by ascending imaginary part. If requested, the
+/// eigenvectors are stored in the corresponding columns of a complex matrix, normalized to unit
+/// length.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::eigen::Eigen;
+///
+/// // rotation by 90 degrees
+/// let arr: Array2<f64> = array![[0.0, -1.0], [1.0, 0.0]];
+/// let eig = Eigen::new(&arr, false).unwrap();
+///
+/// assert!((eig.values()[0].im + 1.0).abs() < 1e-12);
+/// assert!((eig.values()[1].im - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Eigen {
+    values: Array1<Complex<f64>>,
+    vectors: Option<Array2<Complex<f64>>>,
+}
+
+impl Eigen {
+    /// Compute the eigenvalues of *arr* and, if *vectors* is true, its eigenvectors
+    ///
+    /// The matrix is balanced, reduced to upper Hessenberg form with Householder reflections and
+    /// brought to real Schur form with the shifted Francis double step QR algorithm. The
+    /// eigenvectors are computed by inverse iteration on the original matrix. For repeated
+    /// eigenvalues they span the eigenspace only if the matrix is not defective.
+    ///
+    /// Returns [`LinalgError::NoConvergence`] if *arr* has non-finite entries or an eigenvalue
+    /// does not converge within 30 iterations.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    ///
+    /// # References
+    ///  - [Wikipedia: QR algorithm](https://en.wikipedia.org/wiki/QR_algorithm)
+    ///  - [Wikipedia: Inverse iteration](https://en.wikipedia.org/wiki/Inverse_iteration)
+    ///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., sections 11.6 and 11.7
+    pub fn new(arr: &Array2<f64>, vectors: bool) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        if !arr.iter().all(|e| e.is_finite()) {
+            return Err(LinalgError::NoConvergence);
+        }
+
+        let mut h = arr.to_owned();
+        balance(&mut h);
+        hessenberg(&mut h);
+        let mut values = hessenberg_qr(&mut h)?;
+        values.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+
+        let vectors = if vectors {
+            let n = arr.nrows();
+            let mut v = Array2::zeros((n, n));
+            for (j, lambda) in values.iter().enumerate() {
+                v.column_mut(j).assign(&inverse_iteration(arr, *lambda, j));
+            }
+            Some(v)
+        } else {
+            None
+        };
+
+        Ok(Eigen {
+            values: Array1::from_vec(values),
+            vectors,
+        })
+    }
+
+    /// Return the eigenvalues
+    pub fn values(&self) -> Array1<Complex<f64>> {
+        self.values.clone()
+    }
+
+    /// Return the eigenvectors as columns, if they were requested
+    pub fn vectors(&self) -> Option<Array2<Complex<f64>>> {
+        self.vectors.clone()
+    }
+}
+
+/// Return the eigenvalues of the general real square matrix *arr*
+///
+/// See [`Eigen::new`] for details.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::eigen::eigenvalues;
+///
+/// // Jacobian of the damped oscillator x'' + 0.5 x' + 4 x = 0
+/// let jacobian: Array2<f64> = array![[0.0, 1.0], [-4.0, -0.5]];
+/// let stable = eigenvalues(&jacobian).unwrap().iter().all(|e| e.re < 0.0);
+///
+/// assert!(stable);
+/// ```
+pub fn eigenvalues(arr: &Array2<f64>) -> Result<Array1<Complex<f64>>, LinalgError> {
+    Eigen::new(arr, false).map(|eig| eig.values)
+}
+
+/// Return the symmetric matrix defined by the lower triangle of *arr*
+fn symmetrize<F: Float>(arr: &Array2<F>) -> Array2<F> {
+    let n = arr.nrows();
+    Array2::from_shape_fn(
+        (n, n),
+        |(i, j)| if i >= j { arr[(i, j)] } else { arr[(j, i)] },
+    )
+}
+
+/// Reduce the symmetric *arr* to `Q T Q^T` with tridiagonal *T*, returning the diagonal,
+/// the subdiagonal (padded with a trailing zero) and *Q*
+fn tridiagonalize<F: Float>(arr: &Array2<F>) -> (Array1<F>, Array1<F>, Array2<F>) {
+    let n = arr.nrows();
+    let mut a = arr.to_owned();
+    let mut q = Array2::from_diag_elem(n, F::one());
+    let two = F::one() + F::one();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Array1<F> = a.slice(s![k + 1.., k]).to_owned();
+        let alpha = v.iter().fold(F::zero(), |acc, x| acc + *x * *x).sqrt();
+        if alpha == F::zero() {
+            continue;
+        }
+        let alpha = if v[0] > F::zero() { -alpha } else { alpha };
+        v[0] = v[0] - alpha;
+        let beta = two / v.iter().fold(F::zero(), |acc, x| acc + *x * *x);
+
+        // A <- H A H on the trailing block with H = I - beta v v^T
+        let m = n - k - 1;
+        let p: Array1<F> = (0..m)
+            .map(|i| beta * (0..m).fold(F::zero(), |acc, j| acc + a[(k + 1 + i, k + 1 + j)] * v[j]))
+            .collect();
+        let pv = p
+            .iter()
+            .zip(v.iter())
+            .fold(F::zero(), |acc, (x, y)| acc + *x * *y);
+        let w: Array1<F> = (0..m).map(|i| p[i] - beta * pv / two * v[i]).collect();
+        for i in 0..m {
+            for j in 0..m {
+                a[(k + 1 + i, k + 1 + j)] = a[(k + 1 + i, k + 1 + j)] - v[i] * w[j] - w[i] * v[j];
+            }
+        }
+        a[(k + 1, k)] = alpha;
+        a[(k, k + 1)] = alpha;
+        for i in k + 2..n {
+            a[(i, k)] = F::zero();
+            a[(k, i)] = F::zero();
+        }
+
+        // Q <- Q H
+        for i in 0..n {
+            let dot = (0..m).fold(F::zero(), |acc, j| acc + q[(i, k + 1 + j)] * v[j]);
+            for j in 0..m {
+                q[(i, k + 1 + j)] = q[(i, k + 1 + j)] - beta * dot * v[j];
+            }
+        }
+    }
+
+    let d = a.diag().to_owned();
+    let e = (0..n)
+        .map(|i| if i + 1 < n { a[(i + 1, i)] } else { F::zero() })
+        .collect();
+
+    (d, e, q)
+}
+
+/// Diagonalize the symmetric tridiagonal matrix with diagonal *d* and subdiagonal *e*
+/// (`e[i]` couples `i` and `i + 1`) in place, accumulating the rotations into the columns of *z*
+fn tridiagonal_ql_implicit<F: Float>(
+    d: &mut Array1<F>,
+    e: &mut Array1<F>,
+    z: &mut Array2<F>,
+) -> Result<(), LinalgError> {
+    let n = d.len();
+    let two = F::one() + F::one();
+
+    for l in 0..n {
+        let mut iter = 0;
+        loop {
+            let mut m = l;
+            while m + 1 < n {
+                let dd = d[m].abs() + d[m + 1].abs();
+                if e[m].abs() <= F::epsilon() * dd {
+                    break;
+                }
+                m += 1;
+            }
+            if m == l {
+                break;
+            }
+            iter += 1;
+            if iter > 30 {
+                return Err(LinalgError::NoConvergence);
+            }
+
+            // Wilkinson shift
+            let mut g = (d[l + 1] - d[l]) / (two * e[l]);
+            let mut r = g.hypot(F::one());
+            g = d[m] - d[l] + e[l] / (g + if g >= F::zero() { r } else { -r });
+            let (mut s, mut c, mut p) = (F::one(), F::one(), F::zero());
+
+            let mut deflated = false;
+            for i in (l..m).rev() {
+                let f = s * e[i];
+                let b = c * e[i];
+                r = f.hypot(g);
+                e[i + 1] = r;
+                if r == F::zero() {
+                    d[i + 1] = d[i + 1] - p;
+                    e[m] = F::zero();
+                    deflated = true;
+                    break;
+                }
+                s = f / r;
+                c = g / r;
+                g = d[i + 1] - p;
+                r = (d[i] - g) * s + two * c * b;
+                p = s * r;
+                d[i + 1] = g + p;
+                g = c * r - b;
+
+                for k in 0..z.nrows() {
+                    let f = z[(k, i + 1)];
+                    z[(k, i + 1)] = s * z[(k, i)] + c * f;
+                    z[(k, i)] = c * z[(k, i)] - s * f;
+                }
+            }
+            if deflated {
+                continue;
+            }
+            d[l] = d[l] - p;
+            e[l] = g;
+            e[m] = F::zero();
+        }
+    }
+
+    Ok(())
+}
+
+/// Balance *arr* in place by a diagonal similarity transform with powers of two, so that the
+/// norms of corresponding rows and columns are of the same order
+fn balance(arr: &mut Array2<f64>) {
+    let n = arr.nrows();
+    let radix = 2.0;
+    let mut done = false;
+
+    while !done {
+        done = true;
+        for i in 0..n {
+            let mut c = 0.0;
+            let mut r = 0.0;
+            for j in (0..n).filter(|j| *j != i) {
+                c += arr[(j, i)].abs();
+                r += arr[(i, j)].abs();
+            }
+            if c == 0.0 || r == 0.0 {
+                continue;
+            }
+
+            let s = c + r;
+            // the scaling steps are capped, so that they end even if c or r overflow
+            let mut f = 1.0;
+            for _ in 0..f64::MAX_EXP {
+                if c >= r / radix {
+                    break;
+                }
+                f *= radix;
+                c *= radix * radix;
+            }
+            for _ in 0..f64::MAX_EXP {
+                if c <= r * radix {
+                    break;
+                }
+                f /= radix;
+                c /= radix * radix;
+            }
+            if (c + r) / f < 0.95 * s {
+                done = false;
+                arr.row_mut(i).mapv_inplace(|e| e / f);
+                arr.column_mut(i).mapv_inplace(|e| e * f);
+            }
+        }
+    }
+}
+
+/// Reduce *arr* in place to upper Hessenberg form with Householder reflections
+fn hessenberg(arr: &mut Array2<f64>) {
+    let n = arr.nrows();
+
+    for k in 0..n.saturating_sub(2) {
+        let mut v: Array1<f64> = arr.slice(s![k + 1.., k]).to_owned();
+        let alpha = v.dot(&v).sqrt();
+        if alpha == 0.0 {
+            continue;
+        }
+        let alpha = if v[0] > 0.0 { -alpha } else { alpha };
+        v[0] -= alpha;
+        let beta = 2.0 / v.dot(&v);
+
+        // A <- H A
+        for j in k..n {
+            let dot = beta * v.dot(&arr.slice(s![k + 1.., j]));
+            arr.slice_mut(s![k + 1.., j]).scaled_add(-dot, &v);
+        }
+        // A <- A H
+        for i in 0..n {
+            let dot = beta * v.dot(&arr.slice(s![i, k + 1..]));
+            arr.slice_mut(s![i, k + 1..]).scaled_add(-dot, &v);
+        }
+
+        arr[(k + 1, k)] = alpha;
+        arr.slice_mut(s![k + 2.., k]).fill(0.0);
+    }
+}
+
+/// Compute the eigenvalues of the upper Hessenberg matrix *h* with the shifted Francis double
+/// step QR algorithm, destroying *h*
+fn hessenberg_qr(h: &mut Array2<f64>) -> Result<Vec<Complex<f64>>, LinalgError> {
+    let n = h.nrows() as isize;
+    let mut values = vec![Complex::new(0.0, 0.0); n as usize];
+
+    macro_rules! a {
+        ($i:expr, $j:expr) => {
+            h[[($i) as usize, ($j) as usize]]
+        };
+    }
+
+    let mut anorm = 0.0;
+    for i in 0..n {
+        for j in (i - 1).max(0)..n {
+            anorm += a!(i, j).abs();
+        }
+    }
+
+    let mut nn = n - 1;
+    let mut t = 0.0;
+    while nn >= 0 {
+        let mut its = 0;
+        loop {
+            // look for a single small subdiagonal element
+            let mut l = nn;
+            while l > 0 {
+                let mut s = a!(l - 1, l - 1).abs() + a!(l, l).abs();
+                if s == 0.0 {
+                    s = anorm;
+                }
+                if a!(l, l - 1).abs() <= f64::EPSILON * s {
+                    a!(l, l - 1) = 0.0;
+                    break;
+                }
+                l -= 1;
+            }
+
+            let mut x = a!(nn, nn);
+            if l == nn {
+                // one root found
+                values[nn as usize] = Complex::new(x + t, 0.0);
+                nn -= 1;
+                break;
+            }
+
+            let mut y = a!(nn - 1, nn - 1);
+            let mut w = a!(nn, nn - 1) * a!(nn - 1, nn);
+            if l == nn - 1 {
+                // two roots found
+                let p = 0.5 * (y - x);
+                let q = p * p + w;
+                let z = q.abs().sqrt();
+                x += t;
+                if q >= 0.0 {
+                    let z = p + z.copysign(p);
+                    values[(nn - 1) as usize] = Complex::new(x + z, 0.0);
+                    values[nn as usize] =
+                        Complex::new(if z != 0.0 { x - w / z } else { x + z }, 0.0);
+                } else {
+                    values[(nn - 1) as usize] = Complex::new(x + p, z);
+                    values[nn as usize] = Complex::new(x + p, -z);
+                }
+                nn -= 2;
+                break;
+            }
+
+            if its == 30 {
+                return Err(LinalgError::NoConvergence);
+            }
+            if its == 10 || its == 20 {
+                // exceptional shift
+                t += x;
+                for i in 0..=nn {
+                    a!(i, i) -= x;
+                }
+                let s = a!(nn, nn - 1).abs() + a!(nn - 1, nn - 2).abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            its += 1;
+
+            // look for two consecutive small subdiagonal elements
+            let (mut p, mut q, mut r);
+            let mut m = nn - 2;
+            loop {
+                let z = a!(m, m);
+                r = x - z;
+                let s = y - z;
+                p = (r * s - w) / a!(m + 1, m) + a!(m, m + 1);
+                q = a!(m + 1, m + 1) - z - r - s;
+                r = a!(m + 2, m + 1);
+                let s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                let u = a!(m, m - 1).abs() * (q.abs() + r.abs());
+                let v = p.abs() * (a!(m - 1, m - 1).abs() + z.abs() + a!(m + 1, m + 1).abs());
+                if u <= f64::EPSILON * v {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in m..nn - 1 {
+                a!(i + 2, i) = 0.0;
+                if i != m {
+                    a!(i + 2, i - 1) = 0.0;
+                }
+            }
+
+            // double QR step on rows l..=nn and columns m..=nn
+            for k in m..nn {
+                if k != m {
+                    p = a!(k, k - 1);
+                    q = a!(k + 1, k - 1);
+                    r = if k + 1 != nn { a!(k + 2, k - 1) } else { 0.0 };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x != 0.0 {
+                        p /= x;
+                        q /= x;
+                        r /= x;
+                    }
+                }
+                let s = (p * p + q * q + r * r).sqrt().copysign(p);
+                if s == 0.0 {
+                    continue;
+                }
+                if k == m {
+                    if l != m {
+                        a!(k, k - 1) = -a!(k, k - 1);
+                    }
+                } else {
+                    a!(k, k - 1) = -s * x;
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                let z = r / s;
+                q /= p;
+                r /= p;
+                for j in k..=nn {
+                    let mut p = a!(k, j) + q * a!(k + 1, j);
+                    if k + 1 != nn {
+                        p += r * a!(k + 2, j);
+                        a!(k + 2, j) -= p * z;
+                    }
+                    a!(k + 1, j) -= p * y;
+                    a!(k, j) -= p * x;
+                }
+                for i in l..=nn.min(k + 3) {
+                    let mut p = x * a!(i, k) + y * a!(i, k + 1);
+                    if k + 1 != nn {
+                        p += z * a!(i, k + 2);
+                        a!(i, k + 2) -= p * r;
+                    }
+                    a!(i, k + 1) -= p * q;
+                    a!(i, k) -= p;
+                }
+            }
+
+            if l + 1 >= nn {
+                break;
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// Compute the eigenvector of *arr* belonging to the eigenvalue *lambda* by inverse iteration,
+/// using a start vector that depends on *seed*
+fn inverse_iteration(arr: &Array2<f64>, lambda: Complex<f64>, seed: usize) -> Array1<Complex<f64>> {
+    let n = arr.nrows();
+    let norm = arr
+        .iter()
+        .fold(0.0, |acc: f64, e| acc.max(e.abs()))
+        .max(f64::MIN_POSITIVE);
+    let tiny = f64::EPSILON * norm;
+
+    // LU decomposition with partial pivoting of A - lambda I, perturbing zero pivots
+    let mut lu = arr.mapv(|e| Complex::new(e, 0.0));
+    for i in 0..n {
+        lu[(i, i)] -= lambda;
+    }
+    let mut perm: Vec<usize> = (0..n).collect();
+    for k in 0..n {
+        let p = (k..n)
+            .max_by(|i, j| lu[(*i, k)].norm().total_cmp(&lu[(*j, k)].norm()))
+            .unwrap();
+        if p != k {
+            perm.swap(p, k);
+            for j in 0..n {
+                lu.swap((p, j), (k, j));
+            }
+        }
+        if lu[(k, k)].norm() < tiny {
+            lu[(k, k)] = Complex::new(tiny, 0.0);
+        }
+        for i in k + 1..n {
+            let factor = lu[(i, k)] / lu[(k, k)];
+            lu[(i, k)] = factor;
+            for j in k + 1..n {
+                let ukj = lu[(k, j)];
+                lu[(i, j)] -= factor * ukj;
+            }
+        }
+    }
+
+    let mut x: Array1<Complex<f64>> = (0..n)
+        .map(|i| Complex::new(1.0 + ((i * 7 + seed * 13) % 11) as f64 / 11.0, 0.0))
+        .collect();
+    for _ in 0..3 {
+        let mut y: Array1<Complex<f64>> = perm.iter().map(|p| x[*p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                let yj = y[j];
+                y[i] -= lu[(i, j)] * yj;
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                let yj = y[j];
+                y[i] -= lu[(i, j)] * yj;
+            }
+            y[i] /= lu[(i, i)];
+        }
+        let len = y.iter().map(|e| e.norm_sqr()).sum::<f64>().sqrt();
+        x = y.mapv(|e| e / len);
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    fn symmetric(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            let (i, j) = (i.min(j) as f64, i.max(j) as f64);
+            (1.3 * i + 0.7 * j).sin() + if i == j { i } else { 0.0 }
+        })
+    }
+
+    fn check(arr: &Array2<f64>, eig: &SymmetricEigen<f64>) {
+        let n = arr.nrows();
+        let v = eig.vectors();
+        assert_close(&v.t().dot(&v), &Array2::eye(n), 1e-12);
+        assert_close(
+            &arr.dot(&v),
+            &(&v * &eig.values().view().insert_axis(Axis(0))),
+            1e-10,
+        );
+        assert!(eig.values().windows(2).into_iter().all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_jacobi() {
+        let arr = symmetric(6);
+        check(&arr, &SymmetricEigen::jacobi(&arr).unwrap());
+    }
+
+    #[test]
+    fn test_tridiagonal_ql() {
+        let arr = symmetric(25);
+        let eig = SymmetricEigen::tridiagonal_ql(&arr).unwrap();
+        check(&arr, &eig);
+
+        let jacobi = SymmetricEigen::jacobi(&arr).unwrap();
+        let diff = &eig.values() - &jacobi.values();
+        assert!(diff.iter().all(|e| e.abs() < 1e-10));
+    }
+
+    #[test]
+    fn test_symmetric_eigen_degenerate() {
+        // eigenvalues 0, 0 and 3
+        let arr = Array2::from_elem((3, 3), 1.0);
+
+        for eig in [
+            SymmetricEigen::jacobi(&arr).unwrap(),
+            SymmetricEigen::tridiagonal_ql(&arr).unwrap(),
+        ] {
+            check(&arr, &eig);
+            assert!((eig.values()[2] - 3.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eigen_diagonal() {
+        let arr = array![[3.0, 0.0, 0.0], [0.0, -1.0, 0.0], [0.0, 0.0, 2.0]];
+        let eig = SymmetricEigen::new(&arr).unwrap();
+
+        assert_eq!(eig.values(), array![-1.0, 2.0, 3.0]);
+        assert_eq!(
+            SymmetricEigen::tridiagonal_ql(&arr).unwrap().values(),
+            array![-1.0, 2.0, 3.0]
+        );
+    }
+
+    fn check_general(arr: &Array2<f64>, eig: &Eigen) {
+        let a = arr.mapv(|e| Complex::new(e, 0.0));
+        let v = eig.vectors().unwrap();
+        for (j, lambda) in eig.values().iter().enumerate() {
+            let x = v.column(j);
+            let residual = a.dot(&x) - x.mapv(|e| e * lambda);
+            assert!(residual.iter().all(|e| e.norm() < 1e-9));
+            assert!((x.iter().map(|e| e.norm_sqr()).sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_eigen_real() {
+        // upper triangular: eigenvalues on the diagonal
+        let arr = array![[4.0, 1.0, -2.0], [0.0, 1.0, 3.0], [0.0, 0.0, -2.0]];
+        let eig = Eigen::new(&arr, true).unwrap();
+
+        let expected = [-2.0, 1.0, 4.0];
+        for (e, x) in eig.values().iter().zip(expected) {
+            assert!((e.re - x).abs() < 1e-12 && e.im.abs() < 1e-12);
+        }
+        check_general(&arr, &eig);
+    }
+
+    #[test]
+    fn test_eigen_complex() {
+        let arr = Array2::from_shape_fn((12, 12), |(i, j)| {
+            ((2 * i + 3 * j) as f64).cos() + if i + 1 == j { 2.0 } else { 0.0 }
+        });
+        let eig = Eigen::new(&arr, true).unwrap();
+        check_general(&arr, &eig);
+
+        let values = eig.values();
+        assert!(values.iter().any(|e| e.im.abs() > 1e-3));
+        // trace equals the sum of the eigenvalues
+        let sum: Complex<f64> = values.iter().sum();
+        assert!((sum.re - arr.diag().sum()).abs() < 1e-10);
+        assert!(sum.im.abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_eigen_symmetric() {
+        let arr = symmetric(15);
+        let general = eigenvalues(&arr).unwrap();
+        let symmetric = SymmetricEigen::new(&arr).unwrap().values();
+
+        for (g, s) in general.iter().zip(symmetric.iter()) {
+            assert!((g.re - s).abs() < 1e-10 && g.im.abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_eigen_badly_scaled() {
+        let arr = array![[1.0, 1e6, 0.0], [1e-6, 2.0, 1e6], [0.0, 1e-6, 3.0]];
+        let eig = Eigen::new(&arr, true).unwrap();
+        check_general(&arr, &eig);
+    }
+
+    #[test]
+    fn test_eigen_non_finite() {
+        for e in [f64::INFINITY, f64::NAN] {
+            let arr = array![[1.0, 2.0], [e, 1.0]];
+            assert_eq!(
+                Eigen::new(&arr, true).unwrap_err(),
+                LinalgError::NoConvergence
+            );
+            assert_eq!(eigenvalues(&arr), Err(LinalgError::NoConvergence));
+        }
+    }
+
+    #[test]
+    fn test_hermitian_eigen() {
+        let c = |re, im| Complex::new(re, im);
+        let arr = array![
+            [c(4.0, 0.0), c(1.0, -2.0), c(0.0, 1.0), c(2.0, 0.0)],
+            [c(1.0, 2.0), c(3.0, 0.0), c(1.0, 1.0), c(0.0, -1.0)],
+            [c(0.0, -1.0), c(1.0, -1.0), c(-2.0, 0.0), c(0.5, 0.5)],
+            [c(2.0, 0.0), c(0.0, 1.0), c(0.5, -0.5), c(1.0, 0.0)]
+        ];
+        let eig = HermitianEigen::new(&arr).unwrap();
+        let (values, v) = (eig.values(), eig.vectors());
+
+        assert!(values.windows(2).into_iter().all(|w| w[0] <= w[1]));
+        let vh = v.t().mapv(|e: Complex<f64>| e.conj());
+        let ident = vh.dot(&v) - Array2::<Complex<f64>>::eye(4);
+        assert!(ident.iter().all(|e| e.norm() < 1e-12));
+        let lambda = Array2::from_diag(&values.mapv(|e| c(e, 0.0)));
+        let diff = v.dot(&lambda).dot(&vh) - &arr;
+        assert!(diff.iter().all(|e| e.norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_hermitian_eigen_real() {
+        let arr = symmetric(6);
+        let eig = HermitianEigen::new(&arr).unwrap();
+        let v = eig.vectors();
+
+        assert_close(&v.t().dot(&v), &Array2::eye(6), 1e-12);
+        let expected = SymmetricEigen::tridiagonal_ql(&arr).unwrap().values();
+        assert!((&eig.values() - &expected).iter().all(|e| e.abs() < 1e-12));
+    }
+}
@@ -0,0 +1,295 @@
+//! Dense matrix multiplication
+//!
+//! The product is computed in blocks that fit into the caches: panels of both operands are
+//! packed into contiguous buffers, and a register-tiled micro-kernel accumulates small tiles of
+//! the result. With the `rayon` feature enabled, row blocks of the result are computed in
+//! parallel.
+
+use ndarray::prelude::*;
+
+use crate::linalg::scalar::Scalar;
+
+/// Rows of the register tile
+const MR: usize = 4;
+/// Columns of the register tile
+const NR: usize = 4;
+/// Rows of a packed block of *A*
+const MC: usize = 64;
+/// Depth of the packed blocks of *A* and *B*
+const KC: usize = 256;
+/// Columns of a packed block of *B*
+const NC: usize = 512;
+/// Number of multiply-adds below which the product is computed on a single thread
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 1 << 18;
+
+/// Compute `C <- alpha A B + beta C`
+///
+/// If *beta* is zero, *C* is overwritten without being read, so it may contain NaNs.
+///
+/// # Panics
+/// Panics if the dimensions of *A*, *B* and *C* do not match
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::gemm::gemm;
+///
+/// let a: Array2<f64> = array![[1.0, 2.0], [3.0, 4.0]];
+/// let b = array![[1.0, 0.0], [1.0, 1.0]];
+/// let mut c = Array2::eye(2);
+/// gemm(2.0, a.view(), b.view(), 1.0, c.view_mut());
+///
+/// assert_eq!(c, array![[7.0, 4.0], [14.0, 9.0]]);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Matrix multiplication algorithm](https://en.wikipedia.org/wiki/Matrix_multiplication_algorithm#Communication-avoiding_and_distributed_algorithms)
+///  - K. Goto and R. A. van de Geijn, "Anatomy of High-Performance Matrix Multiplication",
+///    ACM Trans. Math. Softw. 34 (2008)
+pub fn gemm<F: Scalar>(
+    alpha: F,
+    a: ArrayView2<F>,
+    b: ArrayView2<F>,
+    beta: F,
+    mut c: ArrayViewMut2<F>,
+) {
+    let (m, k) = a.dim();
+    let n = b.ncols();
+    assert_eq!(b.nrows(), k, "inner dimensions do not match");
+    assert_eq!(c.dim(), (m, n), "dimensions of the result do not match");
+
+    if beta == F::zero() {
+        c.fill(F::zero());
+    } else if beta != F::one() {
+        c.mapv_inplace(|e| e * beta);
+    }
+    if m == 0 || n == 0 || k == 0 || alpha == F::zero() {
+        return;
+    }
+
+    for pc in (0..k).step_by(KC) {
+        let kc = KC.min(k - pc);
+        for jc in (0..n).step_by(NC) {
+            let nc = NC.min(n - jc);
+            let packed_b = pack_b(b.slice(s![pc..pc + kc, jc..jc + nc]));
+            let a_panel = a.slice(s![.., pc..pc + kc]);
+            let c_panel = c.slice_mut(s![.., jc..jc + nc]);
+
+            #[cfg(feature = "rayon")]
+            if m * nc * kc >= PARALLEL_THRESHOLD {
+                parallel_blocks(alpha, a_panel, &packed_b, c_panel);
+                continue;
+            }
+            serial_blocks(alpha, a_panel, &packed_b, c_panel);
+        }
+    }
+}
+
+/// Return the matrix product `a b`
+///
+/// # Panics
+/// Panics if the number of columns of *a* does not match the number of rows of *b*
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::gemm::matmul;
+///
+/// let a: Array2<f64> = array![[1.0, 2.0, 3.0]];
+/// let b = array![[1.0], [0.0], [-1.0]];
+///
+/// assert_eq!(matmul(&a, &b), array![[-2.0]]);
+/// ```
+pub fn matmul<F: Scalar>(a: &Array2<F>, b: &Array2<F>) -> Array2<F> {
+    let mut res = Array2::zeros((a.nrows(), b.ncols()));
+    gemm(F::one(), a.view(), b.view(), F::zero(), res.view_mut());
+
+    res
+}
+
+/// Multiply the row blocks of *a* with the packed block of *B* one after another
+fn serial_blocks<F: Scalar>(alpha: F, a: ArrayView2<F>, packed_b: &[F], mut c: ArrayViewMut2<F>) {
+    for ic in (0..a.nrows()).step_by(MC) {
+        let mc = MC.min(a.nrows() - ic);
+        let packed_a = pack_a(a.slice(s![ic..ic + mc, ..]));
+        macro_kernel(
+            alpha,
+            &packed_a,
+            packed_b,
+            a.ncols(),
+            c.slice_mut(s![ic..ic + mc, ..]),
+        );
+    }
+}
+
+/// Multiply the row blocks of *a* with the packed block of *B* in parallel by recursively
+/// splitting the rows
+#[cfg(feature = "rayon")]
+fn parallel_blocks<F: Scalar>(alpha: F, a: ArrayView2<F>, packed_b: &[F], c: ArrayViewMut2<F>) {
+    let m = a.nrows();
+    if m <= MC {
+        serial_blocks(alpha, a, packed_b, c);
+        return;
+    }
+
+    let mid = (m / MC / 2).max(1) * MC;
+    let (a_top, a_bottom) = a.split_at(Axis(0), mid);
+    let (c_top, c_bottom) = c.split_at(Axis(0), mid);
+    rayon::join(
+        || parallel_blocks(alpha, a_top, packed_b, c_top),
+        || parallel_blocks(alpha, a_bottom, packed_b, c_bottom),
+    );
+}
+
+/// Pack *a* into panels of MR rows, each stored column by column and padded with zeros
+fn pack_a<F: Scalar>(a: ArrayView2<F>) -> Vec<F> {
+    let (mc, kc) = a.dim();
+    let panels = mc.div_ceil(MR);
+    let mut packed = vec![F::zero(); panels * MR * kc];
+
+    for (panel, chunk) in packed.chunks_mut(MR * kc).enumerate() {
+        let ir = panel * MR;
+        for p in 0..kc {
+            for i in 0..MR.min(mc - ir) {
+                chunk[p * MR + i] = a[(ir + i, p)];
+            }
+        }
+    }
+
+    packed
+}
+
+/// Pack *b* into panels of NR columns, each stored row by row and padded with zeros
+fn pack_b<F: Scalar>(b: ArrayView2<F>) -> Vec<F> {
+    let (kc, nc) = b.dim();
+    let panels = nc.div_ceil(NR);
+    let mut packed = vec![F::zero(); panels * NR * kc];
+
+    for (panel, chunk) in packed.chunks_mut(NR * kc).enumerate() {
+        let jr = panel * NR;
+        for p in 0..kc {
+            for j in 0..NR.min(nc - jr) {
+                chunk[p * NR + j] = b[(p, jr + j)];
+            }
+        }
+    }
+
+    packed
+}
+
+/// Add `alpha A B` to *c* for packed blocks of *A* and *B* with depth *kc*
+fn macro_kernel<F: Scalar>(
+    alpha: F,
+    packed_a: &[F],
+    packed_b: &[F],
+    kc: usize,
+    mut c: ArrayViewMut2<F>,
+) {
+    let (mc, nc) = c.dim();
+
+    for (jp, b_panel) in packed_b.chunks(NR * kc).enumerate() {
+        let jr = jp * NR;
+        for (ip, a_panel) in packed_a.chunks(MR * kc).enumerate() {
+            let ir = ip * MR;
+            let acc = micro_kernel(a_panel, b_panel);
+            for (i, row) in acc.iter().enumerate().take(MR.min(mc - ir)) {
+                for (j, e) in row.iter().enumerate().take(NR.min(nc - jr)) {
+                    let cij = &mut c[(ir + i, jr + j)];
+                    *cij = *cij + alpha * *e;
+                }
+            }
+        }
+    }
+}
+
+/// Return the MR x NR tile product of a packed panel of *A* and a packed panel of *B*
+#[inline(always)]
+fn micro_kernel<F: Scalar>(a_panel: &[F], b_panel: &[F]) -> [[F; NR]; MR] {
+    let mut acc = [[F::zero(); NR]; MR];
+
+    for (a, b) in a_panel.chunks_exact(MR).zip(b_panel.chunks_exact(NR)) {
+        for (row, ai) in acc.iter_mut().zip(a) {
+            for (e, bj) in row.iter_mut().zip(b) {
+                *e = *e + *ai * *bj;
+            }
+        }
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(a: &Array2<f64>, b: &Array2<f64>) -> Array2<f64> {
+        Array2::from_shape_fn((a.nrows(), b.ncols()), |(i, j)| {
+            (0..a.ncols()).map(|k| a[(i, k)] * b[(k, j)]).sum()
+        })
+    }
+
+    fn sample(m: usize, n: usize, seed: f64) -> Array2<f64> {
+        Array2::from_shape_fn((m, n), |(i, j)| ((i * n + j) as f64 * seed).sin())
+    }
+
+    #[test]
+    fn test_matmul_edge_sizes() {
+        // sizes that are not multiples of the tile and block sizes
+        for (m, k, n) in [
+            (1, 1, 1),
+            (3, 5, 2),
+            (67, 13, 9),
+            (5, 300, 7),
+            (70, 260, 520),
+        ] {
+            let a = sample(m, k, 0.37);
+            let b = sample(k, n, 0.71);
+
+            let diff = matmul(&a, &b) - naive(&a, &b);
+            assert!(diff.iter().all(|e| e.abs() < 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_gemm_alpha_beta() {
+        let a = sample(20, 30, 0.3);
+        let b = sample(30, 10, 0.5);
+        let c0 = sample(20, 10, 0.9);
+
+        let mut c = c0.clone();
+        gemm(-0.5, a.view(), b.view(), 2.0, c.view_mut());
+        let expected = naive(&a, &b) * -0.5 + &c0 * 2.0;
+        assert!((&c - &expected).iter().all(|e| e.abs() < 1e-12));
+
+        // beta = 0 ignores the previous content
+        let mut c = Array2::from_elem((20, 10), f64::NAN);
+        gemm(1.0, a.view(), b.view(), 0.0, c.view_mut());
+        assert!((&c - &naive(&a, &b)).iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_gemm_views() {
+        // transposed and strided operands
+        let a = sample(40, 30, 0.2);
+        let b = sample(40, 25, 0.4);
+        let mut c = Array2::zeros((60, 25));
+
+        gemm(1.0, a.t(), b.view(), 0.0, c.slice_mut(s![..;2, ..]));
+        let expected = naive(&a.t().to_owned(), &b);
+        assert!((&c.slice(s![..;2, ..]) - &expected)
+            .iter()
+            .all(|e| e.abs() < 1e-12));
+        assert!(c.slice(s![1..;2, ..]).iter().all(|e| *e == 0.0));
+    }
+
+    #[test]
+    fn test_matmul_empty() {
+        let a: Array2<f64> = Array2::zeros((3, 0));
+        let b: Array2<f64> = Array2::zeros((0, 4));
+
+        assert_eq!(matmul(&a, &b), Array2::zeros((3, 4)));
+    }
+}
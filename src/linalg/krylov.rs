@@ -0,0 +1,795 @@
+//! Iterative Krylov subspace solvers for large linear systems
+//!
+//! The solvers only need matrix-vector products, which are provided through the
+//! [`LinearOperator`] trait. It is implemented for dense matrices and for closures, so
+//! matrix-free operators can be passed directly:
+//!
+//! ```
+//! use ndarray::Array1;
+//!
+//! use scialg::linalg::krylov::{conjugate_gradient, Identity};
+//!
+//! // 1D Laplacian with Dirichlet boundaries, never stored as a matrix
+//! let laplacian = |x: &Array1<f64>| {
+//!     let n = x.len();
+//!     Array1::from_shape_fn(n, |i| {
+//!         let left = if i > 0 { x[i - 1] } else { 0.0 };
+//!         let right = if i + 1 < n { x[i + 1] } else { 0.0 };
+//!         2.0 * x[i] - left - right
+//!     })
+//! };
+//! let b = Array1::from_elem(100, 1.0);
+//! let sol = conjugate_gradient(&laplacian, &b, &Identity, 1e-10, 200).unwrap();
+//!
+//! let residual = &laplacian(&sol.x) - &b;
+//! assert!(residual.iter().all(|e| e.abs() < 1e-7));
+//! ```
+
+use ndarray::prelude::*;
+use num::Float;
+
+use crate::linalg::sparse::CsrMatrix;
+use crate::linalg::LinalgError;
+
+/// Linear map `x -> A x` on vectors
+pub trait LinearOperator<F> {
+    /// Return the product `A x`
+    fn matvec(&self, x: &Array1<F>) -> Array1<F>;
+}
+
+impl<F: Float + 'static> LinearOperator<F> for Array2<F> {
+    fn matvec(&self, x: &Array1<F>) -> Array1<F> {
+        assert_eq!(self.ncols(), x.len());
+
+        self.dot(x)
+    }
+}
+
+impl<F, T> LinearOperator<F> for T
+where
+    T: Fn(&Array1<F>) -> Array1<F>,
+{
+    fn matvec(&self, x: &Array1<F>) -> Array1<F> {
+        self(x)
+    }
+}
+
+/// Preconditioner *M* approximating *A*, applied as `r -> M^-1 r`
+pub trait Preconditioner<F> {
+    /// Return the solution *z* of `M z = r`
+    fn apply(&self, r: &Array1<F>) -> Array1<F>;
+}
+
+/// Preconditioner that does nothing
+#[derive(Debug, Copy, Clone)]
+pub struct Identity;
+
+impl<F: Float> Preconditioner<F> for Identity {
+    fn apply(&self, r: &Array1<F>) -> Array1<F> {
+        r.clone()
+    }
+}
+
+/// Jacobi preconditioner `M = diag(A)`
+#[derive(Debug, Clone)]
+pub struct Jacobi<F> {
+    inv_diag: Array1<F>,
+}
+
+impl<F: Float> Jacobi<F> {
+    /// Build the preconditioner from the diagonal of *arr*
+    ///
+    /// Returns [`LinalgError::Singular`] if the diagonal contains a zero and
+    /// [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        Self::from_diagonal(&arr.diag().to_owned())
+    }
+
+    /// Build the preconditioner from the diagonal *diag* of a matrix
+    ///
+    /// Returns [`LinalgError::Singular`] if *diag* contains a zero.
+    pub fn from_diagonal(diag: &Array1<F>) -> Result<Self, LinalgError> {
+        if diag.iter().any(|e| *e == F::zero()) {
+            return Err(LinalgError::Singular);
+        }
+
+        Ok(Jacobi {
+            inv_diag: diag.mapv(|e| F::one() / e),
+        })
+    }
+}
+
+impl<F: Float> Preconditioner<F> for Jacobi<F> {
+    fn apply(&self, r: &Array1<F>) -> Array1<F> {
+        r * &self.inv_diag
+    }
+}
+
+/// Incomplete LU preconditioner `M = L U` without fill-in (ILU(0))
+///
+/// The factors are restricted to the sparsity pattern of *A* and stored in compressed sparse
+/// row format, so building and applying the preconditioner costs O(nnz) per row of *A*
+/// instead of O(n^2).
+///
+/// # References
+///  - [Wikipedia: Incomplete LU factorization](https://en.wikipedia.org/wiki/Incomplete_LU_factorization)
+///  - Y. Saad, *Iterative Methods for Sparse Linear Systems*, 2nd ed., section 10.3
+#[derive(Debug, Clone)]
+pub struct IncompleteLU<F> {
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    /// strictly lower entries of the unit lower triangular *L* and upper entries of *U*
+    values: Vec<F>,
+    /// position of the diagonal entry of each row
+    diag: Vec<usize>,
+}
+
+impl<F: Float> IncompleteLU<F> {
+    /// Factorize *arr* on its sparsity pattern
+    ///
+    /// Returns [`LinalgError::Singular`] if a pivot is zero or missing from the pattern and
+    /// [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &CsrMatrix<F>) -> Result<Self, LinalgError> {
+        if arr.nrows() != arr.ncols() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let (indptr, indices) = (arr.indptr().to_vec(), arr.indices().to_vec());
+        let mut values = arr.data().to_vec();
+        let diag = diagonal_positions(&indptr, &indices).ok_or(LinalgError::Singular)?;
+
+        // IKJ variant of Gaussian elimination, dropping all fill-in
+        let mut position = vec![usize::MAX; n];
+        for i in 0..n {
+            let row = indptr[i]..indptr[i + 1];
+            for p in row.clone() {
+                position[indices[p]] = p;
+            }
+            for p in indptr[i]..diag[i] {
+                let k = indices[p];
+                let factor = values[p] / values[diag[k]];
+                values[p] = factor;
+                for q in diag[k] + 1..indptr[k + 1] {
+                    let pos = position[indices[q]];
+                    if pos != usize::MAX {
+                        values[pos] = values[pos] - factor * values[q];
+                    }
+                }
+            }
+            for p in row {
+                position[indices[p]] = usize::MAX;
+            }
+            if values[diag[i]] == F::zero() {
+                return Err(LinalgError::Singular);
+            }
+        }
+
+        Ok(IncompleteLU {
+            indptr,
+            indices,
+            values,
+            diag,
+        })
+    }
+
+    /// Factorize the dense *arr* on its nonzero pattern
+    ///
+    /// See [`IncompleteLU::new`] for the errors.
+    pub fn from_dense(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        Self::new(&CsrMatrix::from_dense(arr))
+    }
+}
+
+impl<F: Float> Preconditioner<F> for IncompleteLU<F> {
+    fn apply(&self, r: &Array1<F>) -> Array1<F> {
+        let n = self.diag.len();
+        let mut z = r.clone();
+
+        for i in 0..n {
+            for p in self.indptr[i]..self.diag[i] {
+                z[i] = z[i] - self.values[p] * z[self.indices[p]];
+            }
+        }
+        for i in (0..n).rev() {
+            for p in self.diag[i] + 1..self.indptr[i + 1] {
+                z[i] = z[i] - self.values[p] * z[self.indices[p]];
+            }
+            z[i] = z[i] / self.values[self.diag[i]];
+        }
+
+        z
+    }
+}
+
+/// Incomplete Cholesky preconditioner `M = L L^T` without fill-in (IC(0))
+///
+/// The factor is restricted to the sparsity pattern of the lower triangle of *A* and stored
+/// in compressed sparse row format.
+///
+/// # References
+///  - [Wikipedia: Incomplete Cholesky factorization](https://en.wikipedia.org/wiki/Incomplete_Cholesky_factorization)
+#[derive(Debug, Clone)]
+pub struct IncompleteCholesky<F> {
+    /// rows of *L*, each ending with its diagonal entry
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<F>,
+}
+
+impl<F: Float> IncompleteCholesky<F> {
+    /// Factorize the symmetric *arr* on its sparsity pattern, referencing only its lower
+    /// triangle
+    ///
+    /// Returns [`LinalgError::NotPositiveDefinite`] if a nonpositive pivot is encountered,
+    /// which can happen even for positive definite matrices.
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &CsrMatrix<F>) -> Result<Self, LinalgError> {
+        if arr.nrows() != arr.ncols() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let mut indptr = vec![0];
+        let mut indices: Vec<usize> = Vec::new();
+        let mut values = Vec::new();
+
+        for i in 0..n {
+            let start = indices.len();
+            let mut diagonal = F::zero();
+            for p in arr.indptr()[i]..arr.indptr()[i + 1] {
+                let j = arr.indices()[p];
+                if j == i {
+                    diagonal = arr.data()[p];
+                }
+                if j >= i {
+                    continue;
+                }
+                // l_ij = (a_ij - sum_k l_ik l_jk) / l_jj over the common pattern k < j
+                let row_j = indptr[j]..indptr[j + 1];
+                let mut sum = arr.data()[p];
+                let (mut a, mut b) = (start, row_j.start);
+                while a < indices.len() && b < row_j.end - 1 {
+                    match indices[a].cmp(&indices[b]) {
+                        std::cmp::Ordering::Less => a += 1,
+                        std::cmp::Ordering::Greater => b += 1,
+                        std::cmp::Ordering::Equal => {
+                            sum = sum - values[a] * values[b];
+                            a += 1;
+                            b += 1;
+                        }
+                    }
+                }
+                indices.push(j);
+                values.push(sum / values[row_j.end - 1]);
+            }
+
+            let d = values[start..]
+                .iter()
+                .fold(diagonal, |acc: F, l| acc - *l * *l);
+            if d <= F::zero() || d.is_nan() {
+                return Err(LinalgError::NotPositiveDefinite);
+            }
+            indices.push(i);
+            values.push(d.sqrt());
+            indptr.push(indices.len());
+        }
+
+        Ok(IncompleteCholesky {
+            indptr,
+            indices,
+            values,
+        })
+    }
+
+    /// Factorize the dense symmetric *arr* on the nonzero pattern of its lower triangle
+    ///
+    /// See [`IncompleteCholesky::new`] for the errors.
+    pub fn from_dense(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        Self::new(&CsrMatrix::from_dense(arr))
+    }
+}
+
+impl<F: Float> Preconditioner<F> for IncompleteCholesky<F> {
+    fn apply(&self, r: &Array1<F>) -> Array1<F> {
+        let n = self.indptr.len() - 1;
+        let mut z = r.clone();
+
+        // L y = r, row by row
+        for i in 0..n {
+            let last = self.indptr[i + 1] - 1;
+            for p in self.indptr[i]..last {
+                z[i] = z[i] - self.values[p] * z[self.indices[p]];
+            }
+            z[i] = z[i] / self.values[last];
+        }
+        // L^T z = y, scattering each row of L as a column of L^T
+        for i in (0..n).rev() {
+            let last = self.indptr[i + 1] - 1;
+            z[i] = z[i] / self.values[last];
+            for p in self.indptr[i]..last {
+                z[self.indices[p]] = z[self.indices[p]] - self.values[p] * z[i];
+            }
+        }
+
+        z
+    }
+}
+
+/// Return the position of the diagonal entry of every row, or None if one is missing
+fn diagonal_positions(indptr: &[usize], indices: &[usize]) -> Option<Vec<usize>> {
+    (0..indptr.len() - 1)
+        .map(|i| (indptr[i]..indptr[i + 1]).find(|p| indices[*p] == i))
+        .collect()
+}
+
+/// Converged solution of an iterative solver
+#[derive(Debug, Clone)]
+pub struct IterativeSolution<F> {
+    /// Solution vector
+    pub x: Array1<F>,
+    /// Number of iterations
+    pub iterations: usize,
+    /// Euclidean norm of the final residual `b - A x`
+    pub residual: F,
+}
+
+/// Solve `A x = b` for symmetric positive definite *A* with the preconditioned conjugate
+/// gradient method
+///
+/// The iteration starts at zero and stops once `|b - A x| <= tol |b|`. The preconditioner
+/// must be symmetric positive definite as well.
+///
+/// Returns [`LinalgError::NoConvergence`] if the tolerance is not reached within *max_iter*
+/// iterations.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::krylov::{conjugate_gradient, Jacobi};
+///
+/// let arr: Array2<f64> = array![[4.0, 1.0], [1.0, 3.0]];
+/// let b = array![1.0, 2.0];
+/// let precond = Jacobi::new(&arr).unwrap();
+/// let sol = conjugate_gradient(&arr, &b, &precond, 1e-12, 10).unwrap();
+///
+/// assert!((sol.x[0] - 1.0 / 11.0).abs() < 1e-12);
+/// assert!((sol.x[1] - 7.0 / 11.0).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Conjugate gradient method](https://en.wikipedia.org/wiki/Conjugate_gradient_method)
+///  - Y. Saad, *Iterative Methods for Sparse Linear Systems*, 2nd ed., section 9.2
+pub fn conjugate_gradient<F, A, P>(
+    a: &A,
+    b: &Array1<F>,
+    precond: &P,
+    tol: F,
+    max_iter: usize,
+) -> Result<IterativeSolution<F>, LinalgError>
+where
+    F: Float,
+    A: LinearOperator<F> + ?Sized,
+    P: Preconditioner<F> + ?Sized,
+{
+    let threshold = tol * norm(b);
+    let mut x = Array1::zeros(b.len());
+    let mut r = b.clone();
+    let mut res = norm(&r);
+    if res <= threshold {
+        return Ok(IterativeSolution {
+            x,
+            iterations: 0,
+            residual: res,
+        });
+    }
+
+    let mut z = precond.apply(&r);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+
+    for iter in 1..=max_iter {
+        let ap = a.matvec(&p);
+        let alpha = rz / dot(&p, &ap);
+        axpy(&mut x, alpha, &p);
+        axpy(&mut r, -alpha, &ap);
+
+        res = norm(&r);
+        if res <= threshold {
+            return Ok(IterativeSolution {
+                x,
+                iterations: iter,
+                residual: res,
+            });
+        }
+
+        z = precond.apply(&r);
+        let rz_new = dot(&r, &z);
+        let beta = rz_new / rz;
+        rz = rz_new;
+        p = &z + &p.mapv(|e| e * beta);
+    }
+
+    Err(LinalgError::NoConvergence)
+}
+
+/// Solve `A x = b` for general *A* with the right preconditioned stabilized biconjugate
+/// gradient method (BiCGSTAB)
+///
+/// The iteration starts at zero and stops once `|b - A x| <= tol |b|`.
+///
+/// Returns [`LinalgError::NoConvergence`] if the tolerance is not reached within *max_iter*
+/// iterations or the method breaks down.
+///
+/// # References
+///  - [Wikipedia: Biconjugate gradient stabilized method](https://en.wikipedia.org/wiki/Biconjugate_gradient_stabilized_method)
+///  - H. A. van der Vorst, "Bi-CGSTAB: A Fast and Smoothly Converging Variant of Bi-CG for the
+///    Solution of Nonsymmetric Linear Systems", SIAM J. Sci. Stat. Comput. 13 (1992)
+pub fn bicgstab<F, A, P>(
+    a: &A,
+    b: &Array1<F>,
+    precond: &P,
+    tol: F,
+    max_iter: usize,
+) -> Result<IterativeSolution<F>, LinalgError>
+where
+    F: Float,
+    A: LinearOperator<F> + ?Sized,
+    P: Preconditioner<F> + ?Sized,
+{
+    let threshold = tol * norm(b);
+    let mut x = Array1::zeros(b.len());
+    let mut r = b.clone();
+    let mut res = norm(&r);
+    if res <= threshold {
+        return Ok(IterativeSolution {
+            x,
+            iterations: 0,
+            residual: res,
+        });
+    }
+
+    let r_hat = r.clone();
+    let mut rho = F::one();
+    let mut alpha = F::one();
+    let mut omega = F::one();
+    let mut v: Array1<F> = Array1::zeros(b.len());
+    let mut p: Array1<F> = Array1::zeros(b.len());
+
+    for iter in 1..=max_iter {
+        let rho_new = dot(&r_hat, &r);
+        if rho_new == F::zero() || omega == F::zero() {
+            break;
+        }
+        let beta = rho_new / rho * alpha / omega;
+        rho = rho_new;
+        p = &r + &(&p - &v.mapv(|e| e * omega)).mapv(|e| e * beta);
+
+        let p_hat = precond.apply(&p);
+        v = a.matvec(&p_hat);
+        alpha = rho / dot(&r_hat, &v);
+        let mut s = r.clone();
+        axpy(&mut s, -alpha, &v);
+        axpy(&mut x, alpha, &p_hat);
+
+        res = norm(&s);
+        if res <= threshold {
+            return Ok(IterativeSolution {
+                x,
+                iterations: iter,
+                residual: res,
+            });
+        }
+
+        let s_hat = precond.apply(&s);
+        let t = a.matvec(&s_hat);
+        omega = dot(&t, &s) / dot(&t, &t);
+        axpy(&mut x, omega, &s_hat);
+        r = s;
+        axpy(&mut r, -omega, &t);
+
+        res = norm(&r);
+        if res <= threshold {
+            return Ok(IterativeSolution {
+                x,
+                iterations: iter,
+                residual: res,
+            });
+        }
+    }
+
+    Err(LinalgError::NoConvergence)
+}
+
+/// Solve `A x = b` for general *A* with the right preconditioned generalized minimal residual
+/// method, restarted after *restart* iterations (GMRES(m))
+///
+/// The iteration starts at zero and stops once `|b - A x| <= tol |b|`. The iteration count
+/// includes the inner iterations of all restart cycles.
+///
+/// Returns [`LinalgError::NoConvergence`] if the tolerance is not reached within *max_iter*
+/// iterations.
+///
+/// # Panics
+/// Panics if *restart* is zero
+///
+/// # References
+///  - [Wikipedia: Generalized minimal residual method](https://en.wikipedia.org/wiki/Generalized_minimal_residual_method)
+///  - Y. Saad, *Iterative Methods for Sparse Linear Systems*, 2nd ed., section 9.3
+pub fn gmres<F, A, P>(
+    a: &A,
+    b: &Array1<F>,
+    precond: &P,
+    restart: usize,
+    tol: F,
+    max_iter: usize,
+) -> Result<IterativeSolution<F>, LinalgError>
+where
+    F: Float,
+    A: LinearOperator<F> + ?Sized,
+    P: Preconditioner<F> + ?Sized,
+{
+    assert!(restart > 0);
+    let threshold = tol * norm(b);
+    let mut x = Array1::zeros(b.len());
+    let mut iterations = 0;
+
+    loop {
+        let r = b - &a.matvec(&x);
+        let beta = norm(&r);
+        if beta <= threshold {
+            return Ok(IterativeSolution {
+                x,
+                iterations,
+                residual: beta,
+            });
+        }
+        if iterations >= max_iter {
+            return Err(LinalgError::NoConvergence);
+        }
+
+        // Arnoldi process with modified Gram-Schmidt, reducing the Hessenberg matrix to
+        // triangular form with Givens rotations along the way
+        let mut v = vec![r.mapv(|e| e / beta)];
+        let mut z = Vec::with_capacity(restart);
+        let mut h = Array2::zeros((restart + 1, restart));
+        let mut cs = vec![F::zero(); restart];
+        let mut sn = vec![F::zero(); restart];
+        let mut g = Array1::zeros(restart + 1);
+        g[0] = beta;
+
+        let mut k = 0;
+        while k < restart && iterations < max_iter {
+            z.push(precond.apply(&v[k]));
+            let mut w = a.matvec(&z[k]);
+            for (i, vi) in v.iter().enumerate() {
+                h[(i, k)] = dot(&w, vi);
+                axpy(&mut w, -h[(i, k)], vi);
+            }
+            let h_next = norm(&w);
+            h[(k + 1, k)] = h_next;
+
+            for i in 0..k {
+                let (hik, hjk) = (h[(i, k)], h[(i + 1, k)]);
+                h[(i, k)] = cs[i] * hik + sn[i] * hjk;
+                h[(i + 1, k)] = -sn[i] * hik + cs[i] * hjk;
+            }
+            let denom = h[(k, k)].hypot(h[(k + 1, k)]);
+            cs[k] = h[(k, k)] / denom;
+            sn[k] = h[(k + 1, k)] / denom;
+            h[(k, k)] = denom;
+            h[(k + 1, k)] = F::zero();
+            g[k + 1] = -sn[k] * g[k];
+            g[k] = cs[k] * g[k];
+
+            k += 1;
+            iterations += 1;
+            if g[k].abs() <= threshold || h_next == F::zero() {
+                break;
+            }
+            v.push(w.mapv(|e| e / h_next));
+        }
+
+        // x <- x + Z y with H y = g
+        let mut y = Array1::zeros(k);
+        for i in (0..k).rev() {
+            let mut sum = g[i];
+            for j in i + 1..k {
+                sum = sum - h[(i, j)] * y[j];
+            }
+            y[i] = sum / h[(i, i)];
+        }
+        for (zi, yi) in z.iter().zip(y.iter()) {
+            axpy(&mut x, *yi, zi);
+        }
+    }
+}
+
+fn dot<F: Float>(a: &Array1<F>, b: &Array1<F>) -> F {
+    a.iter()
+        .zip(b.iter())
+        .fold(F::zero(), |acc, (x, y)| acc + *x * *y)
+}
+
+fn norm<F: Float>(a: &Array1<F>) -> F {
+    dot(a, a).sqrt()
+}
+
+/// y <- y + alpha x
+fn axpy<F: Float>(y: &mut Array1<F>, alpha: F, x: &Array1<F>) {
+    y.zip_mut_with(x, |a, b| *a = *a + alpha * *b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2D Laplacian on an n x n grid with Dirichlet boundaries
+    fn poisson(n: usize) -> Array2<f64> {
+        let size = n * n;
+        Array2::from_shape_fn((size, size), |(i, j)| {
+            let (ix, iy, jx, jy) = (i % n, i / n, j % n, j / n);
+            if i == j {
+                4.0
+            } else if (ix == jx && iy.abs_diff(jy) == 1) || (iy == jy && ix.abs_diff(jx) == 1) {
+                -1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// 1D convection-diffusion with a reaction term, which is not symmetric
+    fn convection_diffusion(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn((n, n), |(i, j)| {
+            if i == j {
+                2.5
+            } else if i == j + 1 {
+                -1.5
+            } else if j == i + 1 {
+                -0.5
+            } else {
+                0.0
+            }
+        })
+    }
+
+    fn residual(a: &Array2<f64>, x: &Array1<f64>, b: &Array1<f64>) -> f64 {
+        norm(&(b - &a.dot(x)))
+    }
+
+    #[test]
+    fn test_conjugate_gradient() {
+        let a = poisson(10);
+        let b = Array1::from_shape_fn(100, |i| (i as f64).sin());
+
+        let plain = conjugate_gradient(&a, &b, &Identity, 1e-10, 500).unwrap();
+        assert!(residual(&a, &plain.x, &b) <= 1e-10 * norm(&b));
+
+        let sparse = CsrMatrix::from_dense(&a);
+        let precond = IncompleteCholesky::new(&sparse).unwrap();
+        let ic = conjugate_gradient(&sparse, &b, &precond, 1e-10, 500).unwrap();
+        assert!(residual(&a, &ic.x, &b) <= 1e-10 * norm(&b));
+        assert!(ic.iterations < plain.iterations);
+    }
+
+    #[test]
+    fn test_conjugate_gradient_no_convergence() {
+        let a = poisson(10);
+        let b = Array1::from_elem(100, 1.0);
+
+        assert_eq!(
+            conjugate_gradient(&a, &b, &Identity, 1e-12, 3).unwrap_err(),
+            LinalgError::NoConvergence
+        );
+    }
+
+    #[test]
+    fn test_bicgstab() {
+        let a = convection_diffusion(80);
+        let b = Array1::from_elem(80, 1.0);
+
+        let sol = bicgstab(&a, &b, &Identity, 1e-10, 500).unwrap();
+        assert!(residual(&a, &sol.x, &b) <= 1e-10 * norm(&b));
+
+        let precond = IncompleteLU::from_dense(&a).unwrap();
+        let sol = bicgstab(&a, &b, &precond, 1e-10, 500).unwrap();
+        assert!(residual(&a, &sol.x, &b) <= 1e-10 * norm(&b));
+        // ILU(0) of a tridiagonal matrix is exact
+        assert!(sol.iterations <= 2);
+    }
+
+    #[test]
+    fn test_gmres() {
+        let a = convection_diffusion(80);
+        let b = Array1::from_shape_fn(80, |i| (i as f64 * 0.3).cos());
+
+        for restart in [10, 30, 80] {
+            let sol = gmres(&a, &b, &Identity, restart, 1e-10, 2000).unwrap();
+            assert!(residual(&a, &sol.x, &b) <= 1e-10 * norm(&b));
+        }
+
+        let precond = Jacobi::new(&a).unwrap();
+        let sol = gmres(&a, &b, &precond, 20, 1e-10, 2000).unwrap();
+        assert!(residual(&a, &sol.x, &b) <= 1e-10 * norm(&b));
+        assert!((sol.residual - residual(&a, &sol.x, &b)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_matrix_free() {
+        let a = convection_diffusion(50);
+        let op = |x: &Array1<f64>| a.dot(x);
+        let b = Array1::from_elem(50, 1.0);
+
+        let dense = gmres(&a, &b, &Identity, 50, 1e-12, 100).unwrap();
+        let free = gmres(&op, &b, &Identity, 50, 1e-12, 100).unwrap();
+        assert!(dense
+            .x
+            .iter()
+            .zip(free.x.iter())
+            .all(|(x, y)| (x - y).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_zero_rhs() {
+        let a = poisson(3);
+        let b = Array1::zeros(9);
+
+        let sol = conjugate_gradient(&a, &b, &Identity, 1e-10, 10).unwrap();
+        assert_eq!(sol.iterations, 0);
+        assert_eq!(sol.x, Array1::zeros(9));
+    }
+
+    #[test]
+    fn test_incomplete_factorizations_of_full_pattern() {
+        // without dropped fill-in the incomplete factorizations are exact
+        let a = array![
+            [6.0, 2.0, 1.0, 0.5],
+            [2.0, 5.0, 2.0, 1.0],
+            [1.0, 2.0, 4.0, 1.5],
+            [0.5, 1.0, 1.5, 3.0]
+        ];
+        let r = array![1.0, -2.0, 0.5, 3.0];
+
+        let z = IncompleteCholesky::from_dense(&a).unwrap().apply(&r);
+        assert!((a.dot(&z) - &r).iter().all(|e| e.abs() < 1e-12));
+
+        let mut b = a.clone();
+        b[(0, 3)] = -1.0;
+        b[(3, 1)] = 4.0;
+        let z = IncompleteLU::from_dense(&b).unwrap().apply(&r);
+        assert!((b.dot(&z) - &r).iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_incomplete_lu_missing_diagonal() {
+        let a = CsrMatrix::from_dense(&array![[0.0, 1.0], [1.0, 0.0]]);
+
+        assert_eq!(IncompleteLU::new(&a).unwrap_err(), LinalgError::Singular);
+    }
+
+    #[test]
+    fn test_jacobi_singular() {
+        let a = array![[0.0, 1.0], [1.0, 0.0]];
+
+        assert_eq!(Jacobi::new(&a).unwrap_err(), LinalgError::Singular);
+    }
+
+    #[test]
+    fn test_preconditioner_non_square() {
+        let a = Array2::<f64>::ones((2, 3));
+
+        assert_eq!(Jacobi::new(&a).unwrap_err(), LinalgError::NonSquare);
+        assert_eq!(
+            IncompleteLU::from_dense(&a).unwrap_err(),
+            LinalgError::NonSquare
+        );
+        assert_eq!(
+            IncompleteCholesky::from_dense(&a).unwrap_err(),
+            LinalgError::NonSquare
+        );
+    }
+}
@@ -0,0 +1,482 @@
+//! LU decomposition with partial pivoting
+
+use ndarray::prelude::*;
+use num::{Float, NumCast, One, Zero};
+
+use crate::linalg::gemm::gemm;
+use crate::linalg::scalar::Scalar;
+use crate::linalg::LinalgError;
+
+/// Number of columns factorized before the trailing matrix is updated
+const BLOCK: usize = 64;
+
+/// LU decomposition `P A = L U` of a square matrix with row pivoting
+///
+/// *L* is unit lower triangular and stored together with *U* in a single matrix. The entries
+/// may be real or complex.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::lu::LU;
+///
+/// let arr: Array2<f64> = array![[0.0, 2.0, 1.0], [1.0, 1.0, 0.0], [3.0, 0.0, 1.0]];
+/// let lu = LU::new(&arr).unwrap();
+///
+/// let x = lu.solve(&array![4.0, 2.0, 5.0]);
+///
+/// assert!((&x - &array![1.0, 1.0, 2.0]).iter().all(|e| e.abs() < 1e-12));
+/// assert!((lu.determinant() + 5.0).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: LU decomposition](https://en.wikipedia.org/wiki/LU_decomposition)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 3.4
+#[derive(Debug, Clone)]
+pub struct LU<F: Scalar> {
+    lu: Array2<F>,
+    perm: Vec<usize>,
+    parity: F,
+    norm: F::Real,
+}
+
+impl<F: Scalar> LU<F> {
+    /// Decompose *arr*
+    ///
+    /// Returns [`LinalgError::NonSquare`] if *arr* is not square and [`LinalgError::Singular`]
+    /// if a pivot vanishes relative to the norm of *arr*.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        if !arr.is_square() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let mut lu = arr.to_owned();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut parity = F::one();
+        let norm = norm_1(arr);
+        let tol = F::Real::epsilon() * norm;
+
+        // right-looking blocked elimination: factorize a panel of columns, then update the
+        // trailing matrix with a single matrix product
+        for k0 in (0..n).step_by(BLOCK) {
+            let k1 = n.min(k0 + BLOCK);
+
+            for k in k0..k1 {
+                let p = (k..n).fold(k, |p, i| {
+                    if lu[(i, k)].modulus() > lu[(p, k)].modulus() {
+                        i
+                    } else {
+                        p
+                    }
+                });
+                if lu[(p, k)].modulus() <= tol {
+                    return Err(LinalgError::Singular);
+                }
+                if p != k {
+                    for j in 0..n {
+                        lu.swap((k, j), (p, j));
+                    }
+                    perm.swap(k, p);
+                    parity = -parity;
+                }
+
+                let pivot = lu[(k, k)];
+                for i in k + 1..n {
+                    let factor = lu[(i, k)] / pivot;
+                    lu[(i, k)] = factor;
+                    for j in k + 1..k1 {
+                        lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                    }
+                }
+            }
+            if k1 == n {
+                break;
+            }
+
+            // U12 <- L11^-1 A12
+            for k in k0..k1 {
+                for i in k + 1..k1 {
+                    let factor = lu[(i, k)];
+                    for j in k1..n {
+                        lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                    }
+                }
+            }
+            // A22 <- A22 - L21 U12
+            let (l21, u12, a22) =
+                lu.multi_slice_mut((s![k1.., k0..k1], s![k0..k1, k1..], s![k1.., k1..]));
+            gemm(-F::one(), l21.view(), u12.view(), F::one(), a22);
+        }
+
+        Ok(LU {
+            lu,
+            perm,
+            parity,
+            norm,
+        })
+    }
+
+    /// Return the unit lower triangular factor *L*
+    pub fn l(&self) -> Array2<F> {
+        let n = self.lu.nrows();
+        Array2::from_shape_fn((n, n), |(i, j)| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[(i, j)],
+            std::cmp::Ordering::Equal => F::one(),
+            std::cmp::Ordering::Less => F::zero(),
+        })
+    }
+
+    /// Return the upper triangular factor *U*
+    pub fn u(&self) -> Array2<F> {
+        let n = self.lu.nrows();
+        Array2::from_shape_fn(
+            (n, n),
+            |(i, j)| {
+                if i <= j {
+                    self.lu[(i, j)]
+                } else {
+                    F::zero()
+                }
+            },
+        )
+    }
+
+    /// Return the row permutation, row *i* of `P A` is row `perm[i]` of *A*
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Solve `A x = b`
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.lu.nrows();
+        assert_eq!(b.len(), n);
+
+        let mut x: Array1<F> = self.perm.iter().map(|p| b[*p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] = x[i] - self.lu[(i, j)] * x[j];
+            }
+            x[i] = x[i] / self.lu[(i, i)];
+        }
+
+        x
+    }
+
+    /// Solve `A^H x = b`, which is `A^T x = b` for real matrices
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve_adjoint(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.lu.nrows();
+        assert_eq!(b.len(), n);
+
+        // A^H = U^H L^H P
+        let mut y = b.clone();
+        for i in 0..n {
+            for j in 0..i {
+                y[i] = y[i] - self.lu[(j, i)].conj() * y[j];
+            }
+            y[i] = y[i] / self.lu[(i, i)].conj();
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                y[i] = y[i] - self.lu[(j, i)].conj() * y[j];
+            }
+        }
+
+        let mut x = Array1::zeros(n);
+        for (i, p) in self.perm.iter().enumerate() {
+            x[*p] = y[i];
+        }
+
+        x
+    }
+
+    /// Solve `A X = B` for every column of *B*
+    ///
+    /// # Panics
+    /// Panics if the number of rows of *b* does not match the dimension of *A*
+    pub fn solve_many(&self, b: &Array2<F>) -> Array2<F> {
+        let mut x = Array2::zeros(b.raw_dim());
+
+        for (col, mut out) in b.columns().into_iter().zip(x.columns_mut()) {
+            out.assign(&self.solve(&col.to_owned()));
+        }
+
+        x
+    }
+
+    /// Return the determinant of *A*
+    pub fn determinant(&self) -> F {
+        self.lu.diag().iter().fold(self.parity, |acc, d| acc * *d)
+    }
+
+    /// Return the inverse of *A*
+    pub fn inverse(&self) -> Array2<F> {
+        let n = self.lu.nrows();
+
+        self.solve_many(&Array2::from_diag_elem(n, F::one()))
+    }
+
+    /// Return the condition number `|A|_1 |A^-1|_1` in the 1-norm
+    ///
+    /// This is the exact condition number, not an estimate: the inverse is formed explicitly,
+    /// which costs `O(n^3)`. Use [`condition_estimate`](LU::condition_estimate) for large
+    /// matrices.
+    pub fn condition_number(&self) -> F::Real {
+        self.norm * norm_1(&self.inverse())
+    }
+
+    /// Return an estimate of the condition number `|A|_1 |A^-1|_1` in the 1-norm
+    ///
+    /// `|A^-1|_1` is estimated from a few solves with *A* and `A^H` in `O(n^2)` operations. The
+    /// estimate is a lower bound and rarely off by more than a small factor.
+    ///
+    /// # Example
+    /// ```
+    /// use ndarray::Array2;
+    ///
+    /// use scialg::linalg::lu::LU;
+    ///
+    /// let hilbert = Array2::from_shape_fn((6, 6), |(i, j)| 1.0 / (i + j + 1) as f64);
+    /// let lu = LU::new(&hilbert).unwrap();
+    ///
+    /// let estimate = lu.condition_estimate();
+    /// let exact = lu.condition_number();
+    /// assert!(estimate <= exact * (1.0 + 1e-8) && estimate >= exact / 3.0);
+    /// ```
+    ///
+    /// # References
+    ///  - W. W. Hager, *Condition estimates*, SIAM J. Sci. Stat. Comput. 5 (1984)
+    ///  - N. J. Higham, *FORTRAN codes for estimating the one-norm of a real or complex matrix,
+    ///    with applications to condition estimation*, ACM Trans. Math. Softw. 14 (1988)
+    pub fn condition_estimate(&self) -> F::Real {
+        self.norm * self.inverse_norm_estimate()
+    }
+
+    /// Estimate `|A^-1|_1` by maximizing `|A^-1 x|_1` over the unit ball with a gradient
+    /// ascent on its vertices, safeguarded by Higham's alternating test vector
+    fn inverse_norm_estimate(&self) -> F::Real {
+        let n = self.lu.nrows();
+        if n == 0 {
+            return F::Real::zero();
+        }
+        let one = F::Real::one();
+        let dim = <F::Real as NumCast>::from(n).unwrap();
+        let norm = |v: &Array1<F>| v.iter().fold(F::Real::zero(), |acc, e| acc + e.modulus());
+
+        let mut x = Array1::from_elem(n, F::from_real(one / dim));
+        let mut estimate = F::Real::zero();
+        for iter in 0..5 {
+            let y = self.solve(&x);
+            let norm_y = norm(&y);
+            if iter > 0 && norm_y <= estimate {
+                break;
+            }
+            estimate = norm_y;
+
+            // the subgradient of |y|_1 determines the next vertex
+            let signs = y.mapv(|e| {
+                let abs = e.modulus();
+                if abs == F::Real::zero() {
+                    F::one()
+                } else {
+                    e.scale(one / abs)
+                }
+            });
+            let z = self.solve_adjoint(&signs);
+            let (j, z_max) =
+                z.iter()
+                    .map(|e| e.modulus())
+                    .enumerate()
+                    .fold(
+                        (0, -one),
+                        |best, cur| if cur.1 > best.1 { cur } else { best },
+                    );
+            let z_x = z
+                .iter()
+                .zip(x.iter())
+                .fold(F::zero(), |acc, (zi, xi)| acc + zi.conj() * *xi)
+                .re();
+            if iter > 0 && z_max <= z_x {
+                break;
+            }
+            x.fill(F::zero());
+            x[j] = F::one();
+        }
+
+        // alternating vector that catches matrices for which the ascent stalls
+        let b = Array1::from_shape_fn(n, |i| {
+            let magnitude = if n == 1 {
+                one
+            } else {
+                one + <F::Real as NumCast>::from(i).unwrap() / (dim - one)
+            };
+            F::from_real(if i % 2 == 0 { magnitude } else { -magnitude })
+        });
+        let alternative = (one + one) * norm(&self.solve(&b)) / ((one + one + one) * dim);
+
+        estimate.max(alternative)
+    }
+}
+
+/// Return the 1-norm (maximum absolute column sum) of *arr*
+pub(crate) fn norm_1<F: Scalar>(arr: &Array2<F>) -> F::Real {
+    arr.columns()
+        .into_iter()
+        .map(|col| col.iter().fold(F::Real::zero(), |acc, e| acc + e.modulus()))
+        .fold(F::Real::zero(), F::Real::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    #[test]
+    fn test_lu_factors() {
+        let arr = array![
+            [2.0, -1.0, 0.0, 3.0],
+            [0.0, 0.0, 4.0, 1.0],
+            [-6.0, 2.0, 1.0, 0.0],
+            [1.0, 5.0, -2.0, 2.0]
+        ];
+        let lu = LU::new(&arr).unwrap();
+
+        let pa = Array2::from_shape_fn((4, 4), |(i, j)| arr[(lu.permutation()[i], j)]);
+        assert_close(&lu.l().dot(&lu.u()), &pa, 1e-12);
+    }
+
+    #[test]
+    fn test_lu_blocked() {
+        // several blocks and a partial last block
+        let n = 150;
+        let arr =
+            Array2::from_shape_fn((n, n), |(i, j)| ((i * i + 3 * j * j + i * j) as f64).sin());
+        let lu = LU::new(&arr).unwrap();
+
+        let pa = Array2::from_shape_fn((n, n), |(i, j)| arr[(lu.permutation()[i], j)]);
+        assert_close(&lu.l().dot(&lu.u()), &pa, 1e-10);
+    }
+
+    #[test]
+    fn test_lu_zero_diagonal() {
+        // gauss_jordan without pivoting fails on this matrix
+        let arr = array![[0.0, 1.0], [1.0, 0.0]];
+        let lu = LU::new(&arr).unwrap();
+
+        assert_eq!(lu.solve(&array![2.0, 3.0]), array![3.0, 2.0]);
+        assert_eq!(lu.determinant(), -1.0);
+        assert_eq!(lu.inverse(), arr);
+    }
+
+    #[test]
+    fn test_lu_solve_many() {
+        let arr = array![[4.0, 3.0, 2.0], [2.0, 1.0, 3.0], [3.0, 2.0, 1.0]];
+        let b = array![[1.0, 0.0], [2.0, 5.0], [3.0, -1.0]];
+        let lu = LU::new(&arr).unwrap();
+
+        let x = lu.solve_many(&b);
+
+        assert_close(&arr.dot(&x), &b, 1e-12);
+        assert_close(&lu.inverse().dot(&arr), &Array2::eye(3), 1e-12);
+    }
+
+    #[test]
+    fn test_lu_singular() {
+        let arr = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+
+        assert_eq!(LU::new(&arr).unwrap_err(), LinalgError::Singular);
+    }
+
+    #[test]
+    fn test_lu_condition_number() {
+        let arr: Array2<f64> = array![[1.0, 0.0], [0.0, 1e-6]];
+        assert!((LU::new(&arr).unwrap().condition_number() - 1e6).abs() < 1e-6);
+
+        let hilbert = Array2::from_shape_fn((5, 5), |(i, j)| 1.0 / (i + j + 1) as f64);
+        let cond = LU::new(&hilbert).unwrap().condition_number();
+        assert!((cond / 943656.0 - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_lu_complex() {
+        let c = |re, im| num::Complex::new(re, im);
+        let arr = array![
+            [c(0.0, 0.0), c(2.0, -1.0), c(1.0, 0.0)],
+            [c(1.0, 1.0), c(0.0, 3.0), c(-1.0, 0.0)],
+            [c(4.0, 0.0), c(1.0, 1.0), c(0.0, -2.0)]
+        ];
+        let b = array![c(1.0, 0.0), c(0.0, 1.0), c(2.0, -1.0)];
+        let lu = LU::new(&arr).unwrap();
+
+        let residual = arr.dot(&lu.solve(&b)) - &b;
+        assert!(residual.iter().all(|e| e.norm() < 1e-12));
+        let ident = lu.inverse().dot(&arr) - Array2::<num::Complex<f64>>::eye(3);
+        assert!(ident.iter().all(|e| e.norm() < 1e-12));
+
+        // cofactor expansion along the first row
+        let det = -c(2.0, -1.0) * (c(1.0, 1.0) * c(0.0, -2.0) + c(4.0, 0.0))
+            + (c(1.0, 1.0) * c(1.0, 1.0) - c(0.0, 3.0) * c(4.0, 0.0));
+        assert!((lu.determinant() - det).norm() < 1e-12);
+    }
+
+    #[test]
+    fn test_lu_non_square() {
+        let arr: Array2<f64> = Array2::zeros((2, 3));
+
+        assert_eq!(LU::new(&arr).unwrap_err(), LinalgError::NonSquare);
+    }
+
+    #[test]
+    fn test_lu_solve_adjoint() {
+        let c = |re, im| num::Complex::new(re, im);
+        let arr = array![
+            [c(1.0, 1.0), c(2.0, 0.0), c(0.0, -1.0)],
+            [c(0.0, 2.0), c(1.0, 0.0), c(3.0, 0.0)],
+            [c(-1.0, 0.0), c(0.0, 1.0), c(1.0, 1.0)]
+        ];
+        let b = array![c(1.0, 0.0), c(-2.0, 1.0), c(0.0, 3.0)];
+        let x = LU::new(&arr).unwrap().solve_adjoint(&b);
+
+        let adjoint = arr.t().mapv(|e| e.conj());
+        assert!((adjoint.dot(&x) - &b).iter().all(|e| e.norm() < 1e-12));
+    }
+
+    #[test]
+    fn test_lu_condition_estimate() {
+        let samples: [Array2<f64>; 3] = [
+            Array2::from_shape_fn((8, 8), |(i, j)| 1.0 / (i + j + 1) as f64),
+            Array2::from_shape_fn((30, 30), |(i, j)| {
+                (((i * 30 + j) as f64 * 12.9898).sin() * 43758.5453).fract()
+            }),
+            // upper triangular with growing inverse
+            Array2::from_shape_fn((20, 20), |(i, j)| match i.cmp(&j) {
+                std::cmp::Ordering::Equal => 1.0,
+                std::cmp::Ordering::Less => -1.0,
+                std::cmp::Ordering::Greater => 0.0,
+            }),
+        ];
+        for arr in samples {
+            let lu = LU::new(&arr).unwrap();
+            let exact = lu.condition_number();
+            let estimate = lu.condition_estimate();
+
+            assert!(estimate <= exact * (1.0 + 1e-8));
+            assert!(estimate >= exact / 3.0);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Functions of square matrices
+//!
+//! The functions are defined through their power series, e.g. `expm(A) = I + A + A^2 / 2 + ...`,
+//! and agree with the scalar functions applied to the eigenvalues of *A*.
+
+use ndarray::prelude::*;
+
+use crate::linalg::eigen::SymmetricEigen;
+use crate::linalg::gemm::matmul;
+use crate::linalg::lu::{norm_1, LU};
+use crate::linalg::scalar::RealScalar;
+use crate::linalg::LinalgError;
+
+/// Coefficients of the [13/13] Padé approximant of the exponential
+const PADE_13: [f64; 14] = [
+    64764752532480000.0,
+    32382376266240000.0,
+    7771770303897600.0,
+    1187353796428800.0,
+    129060195264000.0,
+    10559470521600.0,
+    670442572800.0,
+    33522128640.0,
+    1323241920.0,
+    40840800.0,
+    960960.0,
+    16380.0,
+    182.0,
+    1.0,
+];
+
+/// Return the matrix exponential of *arr*
+///
+/// The matrix is scaled by a power of two until its 1-norm is small enough for the [13/13]
+/// Padé approximant to be accurate to machine precision, and the result is squared back.
+/// If *arr* has non-finite entries every entry of the result is NaN.
+///
+/// # Panics
+/// Panics if *arr* is not a square matrix
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::matrix_function::expm;
+///
+/// // y' = A y is a rotation, so exp(A t) is a rotation matrix
+/// let t = 0.5;
+/// let arr: Array2<f64> = array![[0.0, -t], [t, 0.0]];
+/// let rot = expm(&arr);
+///
+/// assert!((rot[(0, 0)] - t.cos()).abs() < 1e-14);
+/// assert!((rot[(1, 0)] - t.sin()).abs() < 1e-14);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Matrix exponential](https://en.wikipedia.org/wiki/Matrix_exponential)
+///  - N. J. Higham, "The Scaling and Squaring Method for the Matrix Exponential Revisited",
+///    SIAM J. Matrix Anal. Appl. 26 (2005)
+pub fn expm<F: RealScalar>(arr: &Array2<F>) -> Array2<F> {
+    assert!(arr.is_square());
+    let n = arr.nrows();
+    let b: Vec<F> = PADE_13.iter().map(|c| F::from(*c).unwrap()).collect();
+    let theta = F::from(5.371920351148152).unwrap();
+
+    let norm = norm_1(arr);
+    if !norm.is_finite() {
+        return Array2::from_elem((n, n), F::nan());
+    }
+    let squarings = if norm > theta {
+        (norm / theta).log2().ceil().to_i32().unwrap()
+    } else {
+        0
+    };
+    let a = arr.mapv(|e| e / F::from(2.0).unwrap().powi(squarings));
+
+    let ident = Array2::from_diag_elem(n, F::one());
+    let a2 = matmul(&a, &a);
+    let a4 = matmul(&a2, &a2);
+    let a6 = matmul(&a4, &a2);
+    let combine = |c6: F, c4: F, c2: F, c0: F| {
+        a6.mapv(|e| e * c6) + a4.mapv(|e| e * c4) + a2.mapv(|e| e * c2) + ident.mapv(|e| e * c0)
+    };
+
+    let u = matmul(
+        &a,
+        &(matmul(&a6, &combine(b[13], b[11], b[9], F::zero())) + combine(b[7], b[5], b[3], b[1])),
+    );
+    let v = matmul(&a6, &combine(b[12], b[10], b[8], F::zero())) + combine(b[6], b[4], b[2], b[0]);
+
+    // the denominator is well conditioned for the scaled matrix, unless rounding broke it
+    let mut res = match LU::new(&(&v - &u)) {
+        Ok(lu) => lu.solve_many(&(&v + &u)),
+        Err(_) => return Array2::from_elem((n, n), F::nan()),
+    };
+    for _ in 0..squarings {
+        res = matmul(&res, &res);
+    }
+
+    res
+}
+
+/// Return the principal square root of *arr*, whose eigenvalues have positive real part
+///
+/// Uses the product form of the Denman-Beavers iteration with determinant scaling, which needs
+/// one matrix inverse per step. Returns
+/// [`LinalgError::Singular`] if *arr* is singular and [`LinalgError::NoConvergence`] if the
+/// iteration does not converge, e.g. because *arr* has negative real eigenvalues and no real
+/// principal square root.
+///
+/// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::matrix_function::sqrtm;
+///
+/// let arr: Array2<f64> = array![[4.0, 1.0], [0.0, 9.0]];
+/// let root = sqrtm(&arr).unwrap();
+///
+/// assert!((&root - &array![[2.0, 0.2], [0.0, 3.0]]).iter().all(|e| e.abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - [Wikipedia: Square root of a matrix](https://en.wikipedia.org/wiki/Square_root_of_a_matrix#By_Denman%E2%80%93Beavers_iteration)
+///  - N. J. Higham, *Functions of Matrices*, SIAM (2008), section 6.3
+pub fn sqrtm<F: RealScalar>(arr: &Array2<F>) -> Result<Array2<F>, LinalgError> {
+    if !arr.is_square() {
+        return Err(LinalgError::NonSquare);
+    }
+    let n = arr.nrows();
+    let half = F::from(0.5).unwrap();
+    let tol = F::epsilon().sqrt();
+
+    LU::new(arr)?;
+
+    // M_k = Y_k Z_k tends to the identity, so only M_k needs to be inverted
+    let ident = Array2::from_diag_elem(n, F::one());
+    let mut y = arr.to_owned();
+    let mut m = arr.to_owned();
+    let mut scaling = true;
+    let mut close = false;
+    for _ in 0..100 {
+        // iterates of a nonsingular matrix only become singular if the iteration breaks down
+        let lu = LU::new(&m).map_err(|_| LinalgError::NoConvergence)?;
+        let mu = if scaling {
+            lu.determinant()
+                .abs()
+                .powf(-F::one() / F::from(2 * n).unwrap())
+        } else {
+            F::one()
+        };
+        let mu2 = mu * mu;
+        let m_inv = lu.inverse();
+
+        let y_next = matmul(&y, &(&ident + &m_inv.mapv(|e| e / mu2))).mapv(|e| e * half * mu);
+        m = (&ident + &(m.mapv(|e| e * mu2) + m_inv.mapv(|e| e / mu2)).mapv(|e| e * half))
+            .mapv(|e| e * half);
+        let change = norm_1(&(&y_next - &y)) / norm_1(&y_next);
+        y = y_next;
+
+        // the iteration converges quadratically, so one more step reaches machine precision
+        if close {
+            return Ok(y);
+        }
+        close = change <= tol;
+        scaling = change > F::from(1e-2).unwrap();
+    }
+
+    Err(LinalgError::NoConvergence)
+}
+
+/// Return the principal logarithm of *arr*, whose eigenvalues have imaginary part in (-π, π)
+///
+/// Uses inverse scaling and squaring: square roots are taken until *arr* is close to the
+/// identity, where the diagonal Padé approximant of `log(I + X)` is evaluated by Gauss-Legendre
+/// quadrature. Returns the errors of [`sqrtm`] if *arr* has no real principal logarithm.
+///
+/// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::matrix_function::{expm, logm};
+///
+/// let arr: Array2<f64> = array![[2.0, 1.0], [0.5, 3.0]];
+/// let log = logm(&arr).unwrap();
+///
+/// assert!((&expm(&log) - &arr).iter().all(|e| e.abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - [Wikipedia: Logarithm of a matrix](https://en.wikipedia.org/wiki/Logarithm_of_a_matrix)
+///  - N. J. Higham, *Functions of Matrices*, SIAM (2008), section 11.5
+pub fn logm<F: RealScalar>(arr: &Array2<F>) -> Result<Array2<F>, LinalgError> {
+    if !arr.is_square() {
+        return Err(LinalgError::NonSquare);
+    }
+    let n = arr.nrows();
+    let ident = Array2::from_diag_elem(n, F::one());
+
+    let mut x = arr.to_owned();
+    let mut roots = 0;
+    while norm_1(&(&x - &ident)) > F::from(0.25).unwrap() {
+        if roots == 64 {
+            return Err(LinalgError::NoConvergence);
+        }
+        x = sqrtm(&x)?;
+        roots += 1;
+    }
+    x = x - &ident;
+
+    // log(I + X) = int_0^1 X (I + t X)^-1 dt
+    let mut res: Array2<F> = Array2::zeros((n, n));
+    for (t, w) in gauss_legendre(8) {
+        let t = F::from(t).unwrap();
+        let w = F::from(w).unwrap();
+        let term = LU::new(&(&ident + &x.mapv(|e| e * t)))?.solve_many(&x);
+        res = res + term.mapv(|e| e * w);
+    }
+
+    Ok(res.mapv(|e| e * F::from(2.0).unwrap().powi(roots)))
+}
+
+/// Return *arr* raised to the integer power *n*
+///
+/// Uses binary exponentiation, inverting *arr* first for negative *n*. Returns
+/// [`LinalgError::Singular`] if *n* is negative and *arr* is singular.
+///
+/// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::matrix_function::powi;
+///
+/// // Fibonacci numbers
+/// let arr: Array2<f64> = array![[1.0, 1.0], [1.0, 0.0]];
+///
+/// assert_eq!(powi(&arr, 10).unwrap()[(0, 1)], 55.0);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Exponentiation by squaring](https://en.wikipedia.org/wiki/Exponentiation_by_squaring)
+pub fn powi<F: RealScalar>(arr: &Array2<F>, n: i32) -> Result<Array2<F>, LinalgError> {
+    if !arr.is_square() {
+        return Err(LinalgError::NonSquare);
+    }
+
+    let mut base = if n < 0 {
+        LU::new(arr)?.inverse()
+    } else {
+        arr.to_owned()
+    };
+    let mut res = Array2::from_diag_elem(arr.nrows(), F::one());
+    let mut exp = n.unsigned_abs();
+    while exp > 0 {
+        if exp % 2 == 1 {
+            res = matmul(&res, &base);
+        }
+        exp /= 2;
+        if exp > 0 {
+            base = matmul(&base, &base);
+        }
+    }
+
+    Ok(res)
+}
+
+/// Return *arr* raised to the real power *p*
+///
+/// Integer powers are computed with [`powi`], all others as `expm(p logm(A))`, which is the
+/// principal power and has the same failure modes as [`logm`].
+///
+/// Returns [`LinalgError::NonSquare`] if *arr* is not square.
+pub fn powf<F: RealScalar>(arr: &Array2<F>, p: F) -> Result<Array2<F>, LinalgError> {
+    if p.fract() == F::zero() && p.abs() <= F::from(i32::MAX).unwrap() {
+        return powi(arr, p.to_i32().unwrap());
+    }
+
+    Ok(expm(&logm(arr)?.mapv(|e| e * p)))
+}
+
+/// Return the nodes and weights of the *n*-point Gauss-Legendre rule on [0, 1] using the
+/// Golub-Welsch algorithm
+fn gauss_legendre(n: usize) -> Vec<(f64, f64)> {
+    let jacobi = Array2::from_shape_fn((n, n), |(i, j)| {
+        let k = i.max(j) as f64;
+        if i.abs_diff(j) == 1 {
+            k / (4.0 * k * k - 1.0).sqrt()
+        } else {
+            0.0
+        }
+    });
+    let eig = SymmetricEigen::new(&jacobi).expect("Jacobi matrix is symmetric");
+    let vectors = eig.vectors();
+
+    eig.values()
+        .iter()
+        .enumerate()
+        .map(|(i, x)| ((x + 1.0) / 2.0, vectors[(0, i)].powi(2)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    fn sample() -> Array2<f64> {
+        array![[4.0, 1.0, 0.5], [1.0, 3.0, -0.5], [0.2, 0.3, 2.0]]
+    }
+
+    #[test]
+    fn test_expm_diagonal_and_nilpotent() {
+        let diag = array![[1.0, 0.0], [0.0, -2.0]];
+        assert_close(
+            &expm(&diag),
+            &array![[1.0_f64.exp(), 0.0], [0.0, (-2.0_f64).exp()]],
+            1e-14,
+        );
+
+        let nilpotent = array![[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 0.0]];
+        assert_close(
+            &expm(&nilpotent),
+            &array![[1.0, 1.0, 0.5], [0.0, 1.0, 1.0], [0.0, 0.0, 1.0]],
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn test_expm_large_norm() {
+        // exp(A) for A = t [[0, -1], [1, 0]] with scaling and squaring
+        let t = 30.0_f64;
+        let arr = array![[0.0, -t], [t, 0.0]];
+        let expected = array![[t.cos(), -t.sin()], [t.sin(), t.cos()]];
+        assert_close(&expm(&arr), &expected, 1e-12);
+
+        // sums of the generator of a Markov chain stay one
+        let generator: Array2<f64> = array![[-3.0, 2.0, 1.0], [4.0, -5.0, 1.0], [0.5, 0.5, -1.0]];
+        let transition = expm(&generator.mapv(|e| e * 10.0));
+        assert!(transition
+            .rows()
+            .into_iter()
+            .all(|r| (r.sum() - 1.0).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_expm_non_finite() {
+        for e in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let res = expm(&array![[1.0, e], [0.0, 1.0]]);
+            assert_eq!(res.dim(), (2, 2));
+            assert!(res.iter().all(|e| e.is_nan()));
+        }
+    }
+
+    #[test]
+    fn test_sqrtm() {
+        let arr = sample();
+        let root = sqrtm(&arr).unwrap();
+        assert_close(&root.dot(&root), &arr, 1e-12);
+
+        let negative = array![[-1.0, 0.0], [0.0, 1.0]];
+        assert_eq!(sqrtm(&negative).unwrap_err(), LinalgError::NoConvergence);
+    }
+
+    #[test]
+    fn test_logm() {
+        let arr = sample();
+        assert_close(&expm(&logm(&arr).unwrap()), &arr, 1e-12);
+
+        let diag = array![[1.0, 0.0], [0.0, 100.0]];
+        assert_close(
+            &logm(&diag).unwrap(),
+            &array![[0.0, 0.0], [0.0, 100.0_f64.ln()]],
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_powi() {
+        let arr = sample();
+
+        assert_eq!(powi(&arr, 0).unwrap(), Array2::eye(3));
+        assert_close(&powi(&arr, 3).unwrap(), &arr.dot(&arr).dot(&arr), 1e-12);
+        assert_close(
+            &powi(&arr, -2).unwrap().dot(&arr.dot(&arr)),
+            &Array2::eye(3),
+            1e-12,
+        );
+        assert_eq!(
+            powi(&array![[1.0, 2.0], [2.0, 4.0]], -1).unwrap_err(),
+            LinalgError::Singular
+        );
+    }
+
+    #[test]
+    fn test_powf() {
+        let arr = sample();
+
+        assert_close(&powf(&arr, 0.5).unwrap(), &sqrtm(&arr).unwrap(), 1e-12);
+        assert_close(&powf(&arr, 2.0).unwrap(), &arr.dot(&arr), 1e-12);
+        let third = powf(&arr, 1.0 / 3.0).unwrap();
+        assert_close(&third.dot(&third).dot(&third), &arr, 1e-12);
+    }
+
+    #[test]
+    fn test_gauss_legendre() {
+        // exact for polynomials up to degree 15
+        let integral: f64 = gauss_legendre(8).iter().map(|(x, w)| w * x.powi(15)).sum();
+        assert!((integral - 1.0 / 16.0).abs() < 1e-14);
+    }
+}
@@ -0,0 +1,653 @@
+//! QR decompositions and linear least squares
+
+use ndarray::prelude::*;
+use num::Float;
+
+use crate::linalg::LinalgError;
+
+/// QR decomposition `A = Q R` of an m x n matrix using Householder reflections
+///
+/// *Q* is stored compactly as the product of `min(m, n)` Householder reflectors, so a least
+/// squares solve takes O(mn) memory and O(mn^2) operations. The m x m factor is only formed
+/// when it is requested or when the decomposition is first updated with Givens rotations,
+/// which happens when *A* changes by a rank one matrix or by adding and removing rows.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::qr::QR;
+///
+/// let arr: Array2<f64> = array![[1.0, 1.0], [1.0, 2.0], [1.0, 3.0]];
+/// let qr = QR::new(&arr);
+///
+/// // least squares fit of a line through (1, 1), (2, 2), (3, 2)
+/// let x = qr.solve(&array![1.0, 2.0, 2.0]).unwrap();
+///
+/// assert!((x[0] - 2.0 / 3.0).abs() < 1e-12);
+/// assert!((x[1] - 0.5).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: QR decomposition](https://en.wikipedia.org/wiki/QR_decomposition)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., sections 5.1.6, 5.2
+///    and 6.5
+#[derive(Debug, Clone)]
+pub struct QR<F> {
+    q: Orthogonal<F>,
+    r: Array2<F>,
+}
+
+impl<F: Float> QR<F> {
+    /// Decompose *arr*
+    pub fn new(arr: &Array2<F>) -> Self {
+        let (reflectors, r, _) = householder(arr, false);
+
+        QR {
+            q: Orthogonal::Reflectors(reflectors),
+            r,
+        }
+    }
+
+    /// Return the orthogonal m x m factor *Q*
+    pub fn q(&self) -> Array2<F> {
+        match &self.q {
+            Orthogonal::Reflectors(h) => h.form(self.r.nrows()),
+            Orthogonal::Explicit(q) => q.clone(),
+        }
+    }
+
+    /// Return the upper triangular m x n factor *R*
+    pub fn r(&self) -> Array2<F> {
+        self.r.clone()
+    }
+
+    /// Return the first `min(m, n)` columns of *Q*
+    pub fn thin_q(&self) -> Array2<F> {
+        let k = self.r.nrows().min(self.r.ncols());
+        match &self.q {
+            Orthogonal::Reflectors(h) => h.form(k),
+            Orthogonal::Explicit(q) => q.slice(s![.., ..k]).to_owned(),
+        }
+    }
+
+    /// Return the first `min(m, n)` rows of *R*
+    pub fn thin_r(&self) -> Array2<F> {
+        let k = self.r.nrows().min(self.r.ncols());
+        self.r.slice(s![..k, ..]).to_owned()
+    }
+
+    /// Solve the linear least squares problem `min |A x - b|` for *A* with full column rank
+    ///
+    /// Returns [`LinalgError::Singular`] if *A* has fewer rows than columns or is rank deficient.
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the number of rows of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Result<Array1<F>, LinalgError> {
+        let (m, n) = self.r.dim();
+        assert_eq!(b.len(), m);
+        if m < n {
+            return Err(LinalgError::Singular);
+        }
+
+        let max = self
+            .r
+            .diag()
+            .iter()
+            .fold(F::zero(), |acc, d| acc.max(d.abs()));
+        let tol = F::epsilon() * F::from(m.max(n)).unwrap() * max;
+        if self.r.diag().iter().any(|d| d.abs() <= tol) {
+            return Err(LinalgError::Singular);
+        }
+
+        let qtb = match &self.q {
+            Orthogonal::Reflectors(h) => h.transpose_dot(b),
+            Orthogonal::Explicit(q) => transpose_dot(q, b),
+        };
+        Ok(back_substitution(&self.r, &qtb, n))
+    }
+
+    /// Update the decomposition to the one of `A + u v^T` in O(m^2 + mn) operations
+    ///
+    /// # Panics
+    /// Panics if the dimensions of *u* and *v* do not match *A*
+    pub fn rank_one_update(&mut self, u: &Array1<F>, v: &Array1<F>) {
+        let (m, n) = self.r.dim();
+        assert_eq!(u.len(), m);
+        assert_eq!(v.len(), n);
+        if m == 0 {
+            return;
+        }
+        let q = self.q.explicit();
+        let mut w = transpose_dot(q, u);
+
+        // reduce w to a multiple of e_1, which turns R into upper Hessenberg form
+        for k in (1..m).rev() {
+            let (c, s) = givens(w[k - 1], w[k]);
+            w[k - 1] = c * w[k - 1] + s * w[k];
+            w[k] = F::zero();
+            rotate_rows(&mut self.r, k - 1, k, c, s);
+            rotate_cols(q, k - 1, k, c, s);
+        }
+
+        for j in 0..n {
+            self.r[(0, j)] = self.r[(0, j)] + w[0] * v[j];
+        }
+
+        self.hessenberg_to_triangular();
+    }
+
+    /// Update the decomposition to the one of *A* with *row* inserted at index *k*
+    ///
+    /// # Panics
+    /// Panics if `k > m` or the length of *row* does not match the number of columns of *A*
+    pub fn insert_row(&mut self, k: usize, row: &Array1<F>) {
+        let (m, n) = self.r.dim();
+        assert!(k <= m);
+        assert_eq!(row.len(), n);
+
+        // [row; A] = diag(1, Q) [row; R] where [row; R] is upper Hessenberg
+        let mut q = Array2::zeros((m + 1, m + 1));
+        q[(0, 0)] = F::one();
+        q.slice_mut(s![1.., 1..]).assign(self.q.explicit());
+        let mut r = Array2::zeros((m + 1, n));
+        r.row_mut(0).assign(row);
+        r.slice_mut(s![1.., ..]).assign(&self.r);
+
+        self.q = Orthogonal::Explicit(q);
+        self.r = r;
+        self.hessenberg_to_triangular();
+
+        // move the new row from the top to index k
+        let mut order: Vec<usize> = (1..=m).collect();
+        order.insert(k, 0);
+        let q = self.q.explicit();
+        *q = q.select(Axis(0), &order);
+    }
+
+    /// Update the decomposition to the one of *A* with row *k* removed
+    ///
+    /// # Panics
+    /// Panics if `k >= m`
+    pub fn delete_row(&mut self, k: usize) {
+        let m = self.r.nrows();
+        assert!(k < m);
+        let q = self.q.explicit();
+
+        // rotate row k of Q into a multiple of e_1
+        for j in (1..m).rev() {
+            let (c, s) = givens(q[(k, j - 1)], q[(k, j)]);
+            rotate_cols(q, j - 1, j, c, s);
+            rotate_rows(&mut self.r, j - 1, j, c, s);
+            q[(k, j)] = F::zero();
+        }
+
+        let rows: Vec<usize> = (0..m).filter(|i| *i != k).collect();
+        *q = q.select(Axis(0), &rows).slice(s![.., 1..]).to_owned();
+        self.r = self.r.slice(s![1.., ..]).to_owned();
+    }
+
+    /// Restore triangular form of an upper Hessenberg *R* with Givens rotations
+    fn hessenberg_to_triangular(&mut self) {
+        let (m, n) = self.r.dim();
+        let q = self.q.explicit();
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let (c, s) = givens(self.r[(k, k)], self.r[(k + 1, k)]);
+            rotate_rows(&mut self.r, k, k + 1, c, s);
+            rotate_cols(q, k, k + 1, c, s);
+            self.r[(k + 1, k)] = F::zero();
+        }
+    }
+}
+
+/// QR decomposition with column pivoting `A P = Q R`
+///
+/// The columns are chosen by decreasing remaining norm, so the magnitude of the diagonal of *R*
+/// decreases and reveals the numerical rank of *A*. Like in [`QR`], *Q* is stored as
+/// Householder reflectors.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::qr::ColPivQR;
+///
+/// let arr: Array2<f64> = array![[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]];
+///
+/// assert_eq!(ColPivQR::new(&arr).rank(), 2);
+/// ```
+///
+/// # References
+///  - [Wikipedia: QR decomposition](https://en.wikipedia.org/wiki/QR_decomposition#Column_pivoting)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 5.4.2
+#[derive(Debug, Clone)]
+pub struct ColPivQR<F> {
+    reflectors: Reflectors<F>,
+    r: Array2<F>,
+    perm: Vec<usize>,
+    rank: usize,
+}
+
+impl<F: Float> ColPivQR<F> {
+    /// Decompose *arr* and determine its numerical rank with the default tolerance
+    /// `eps * max(m, n) * |R[0, 0]|`
+    pub fn new(arr: &Array2<F>) -> Self {
+        let (m, n) = arr.dim();
+        let (reflectors, r, perm) = householder(arr, true);
+        let tol = F::epsilon()
+            * F::from(m.max(n)).unwrap()
+            * r.diag().first().map_or(F::zero(), |d| d.abs());
+        let rank = r.diag().iter().take_while(|d| d.abs() > tol).count();
+
+        ColPivQR {
+            reflectors,
+            r,
+            perm,
+            rank,
+        }
+    }
+
+    /// Return the orthogonal m x m factor *Q*
+    pub fn q(&self) -> Array2<F> {
+        self.reflectors.form(self.r.nrows())
+    }
+
+    /// Return the upper triangular m x n factor *R*
+    pub fn r(&self) -> Array2<F> {
+        self.r.clone()
+    }
+
+    /// Return the column permutation, column *j* of `A P` is column `perm[j]` of *A*
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Return the numerical rank of *A*
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Return the basic solution of the linear least squares problem `min |A x - b|`,
+    /// which has at most `rank` non-zero entries
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the number of rows of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.r.ncols();
+        assert_eq!(b.len(), self.r.nrows());
+
+        let qtb = self.reflectors.transpose_dot(b);
+        let z = back_substitution(&self.r, &qtb, self.rank);
+
+        let mut x = Array1::zeros(n);
+        for (j, zj) in z.iter().enumerate() {
+            x[self.perm[j]] = *zj;
+        }
+
+        x
+    }
+}
+
+/// Solve the linear least squares problem `min |A x - b|` without forming the normal equations
+///
+/// A column pivoted QR decomposition is used, so rank deficient *A* are handled as well and
+/// the basic solution is returned.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::qr::lstsq;
+///
+/// let a: Array2<f64> = array![[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+/// let x = lstsq(&a, &array![1.0, 1.0, 3.0]);
+///
+/// assert!((x[0] - 4.0 / 3.0).abs() < 1e-12);
+/// assert!((x[1] - 4.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn lstsq<F: Float>(a: &Array2<F>, b: &Array1<F>) -> Array1<F> {
+    ColPivQR::new(a).solve(b)
+}
+
+/// Orthogonal factor of a [`QR`] decomposition
+#[derive(Debug, Clone)]
+enum Orthogonal<F> {
+    /// Compact form straight from the factorization
+    Reflectors(Reflectors<F>),
+    /// Explicit m x m matrix, which the Givens updates work on
+    Explicit(Array2<F>),
+}
+
+impl<F: Float> Orthogonal<F> {
+    /// Form the explicit matrix if necessary and return it
+    fn explicit(&mut self) -> &mut Array2<F> {
+        if let Orthogonal::Reflectors(h) = self {
+            *self = Orthogonal::Explicit(h.form(h.v.nrows()));
+        }
+        match self {
+            Orthogonal::Explicit(q) => q,
+            Orthogonal::Reflectors(_) => unreachable!("the reflectors were just formed"),
+        }
+    }
+}
+
+/// Householder reflectors `H_k = I - beta_k v_k v_k^T` with `Q = H_0 H_1 ... H_{k-1}`
+///
+/// Column *k* of *v* holds `v_k`, which vanishes above row *k*, like the compact storage of
+/// LAPACK's `geqrf`.
+#[derive(Debug, Clone)]
+struct Reflectors<F> {
+    v: Array2<F>,
+    beta: Vec<F>,
+}
+
+impl<F: Float> Reflectors<F> {
+    /// Apply `H_k` to *x* in place
+    fn reflect(&self, k: usize, mut x: ArrayViewMut1<F>) {
+        let m = self.v.nrows();
+        let dot = (k..m).fold(F::zero(), |acc, i| acc + self.v[(i, k)] * x[i]);
+        let factor = self.beta[k] * dot;
+        for i in k..m {
+            x[i] = x[i] - factor * self.v[(i, k)];
+        }
+    }
+
+    /// Return `Q^T b` in O(mk) operations
+    fn transpose_dot(&self, b: &Array1<F>) -> Array1<F> {
+        let mut x = b.clone();
+        for k in 0..self.beta.len() {
+            self.reflect(k, x.view_mut());
+        }
+
+        x
+    }
+
+    /// Return the first *cols* columns of *Q* in O(m cols k) operations
+    fn form(&self, cols: usize) -> Array2<F> {
+        let m = self.v.nrows();
+        let mut q = Array2::from_shape_fn(
+            (m, cols),
+            |(i, j)| {
+                if i == j {
+                    F::one()
+                } else {
+                    F::zero()
+                }
+            },
+        );
+
+        // accumulate backwards, so H_k leaves the unit columns left of k untouched
+        for k in (0..self.beta.len()).rev() {
+            for col in q.columns_mut().into_iter().skip(k) {
+                self.reflect(k, col);
+            }
+        }
+
+        q
+    }
+}
+
+/// Householder QR of *arr*, optionally with column pivoting
+fn householder<F: Float>(
+    arr: &Array2<F>,
+    pivoting: bool,
+) -> (Reflectors<F>, Array2<F>, Vec<usize>) {
+    let (m, n) = arr.dim();
+    let steps = n.min(m);
+    let mut r = arr.to_owned();
+    let mut v = Array2::zeros((m, steps));
+    let mut beta = vec![F::zero(); steps];
+    let mut perm: Vec<usize> = (0..n).collect();
+
+    for k in 0..steps {
+        if pivoting {
+            let norm2 = |j: usize| {
+                r.slice(s![k.., j])
+                    .iter()
+                    .fold(F::zero(), |acc, e| acc + *e * *e)
+            };
+            let p = (k..n).fold(k, |p, j| if norm2(j) > norm2(p) { j } else { p });
+            if p != k {
+                for i in 0..m {
+                    r.swap((i, k), (i, p));
+                }
+                perm.swap(k, p);
+            }
+        }
+
+        // reflector v with H = I - beta v v^T mapping r[k.., k] onto a multiple of e_1
+        let mut vk = r.slice(s![k.., k]).to_owned();
+        let alpha = vk.iter().fold(F::zero(), |acc, e| acc + *e * *e).sqrt();
+        if alpha == F::zero() {
+            // H_k = I
+            continue;
+        }
+        let alpha = if vk[0] > F::zero() { -alpha } else { alpha };
+        vk[0] = vk[0] - alpha;
+        let vnorm2 = vk.iter().fold(F::zero(), |acc, e| acc + *e * *e);
+        beta[k] = (F::one() + F::one()) / vnorm2;
+
+        for j in k..n {
+            let dot = (0..m - k).fold(F::zero(), |acc, i| acc + vk[i] * r[(k + i, j)]);
+            for i in 0..m - k {
+                r[(k + i, j)] = r[(k + i, j)] - beta[k] * dot * vk[i];
+            }
+        }
+        v.slice_mut(s![k.., k]).assign(&vk);
+
+        r[(k, k)] = alpha;
+        for i in k + 1..m {
+            r[(i, k)] = F::zero();
+        }
+    }
+
+    (Reflectors { v, beta }, r, perm)
+}
+
+/// Return `q^T b`
+fn transpose_dot<F: Float>(q: &Array2<F>, b: &Array1<F>) -> Array1<F> {
+    q.columns()
+        .into_iter()
+        .map(|col| {
+            col.iter()
+                .zip(b.iter())
+                .fold(F::zero(), |acc, (a, b)| acc + *a * *b)
+        })
+        .collect()
+}
+
+/// Solve the leading k x k upper triangular system of *r* for the first k entries of *b*
+fn back_substitution<F: Float>(r: &Array2<F>, b: &Array1<F>, k: usize) -> Array1<F> {
+    let mut x = Array1::zeros(k);
+
+    for i in (0..k).rev() {
+        let mut sum = b[i];
+        for j in i + 1..k {
+            sum = sum - r[(i, j)] * x[j];
+        }
+        x[i] = sum / r[(i, i)];
+    }
+
+    x
+}
+
+/// Return *(c, s)* such that the rotation `[c s; -s c]` maps *(a, b)* onto *(r, 0)*
+fn givens<F: Float>(a: F, b: F) -> (F, F) {
+    if b == F::zero() {
+        return (F::one(), F::zero());
+    }
+    let r = a.hypot(b);
+
+    (a / r, b / r)
+}
+
+fn rotate_rows<F: Float>(arr: &mut Array2<F>, i: usize, k: usize, c: F, s: F) {
+    for j in 0..arr.ncols() {
+        let (x, y) = (arr[(i, j)], arr[(k, j)]);
+        arr[(i, j)] = c * x + s * y;
+        arr[(k, j)] = c * y - s * x;
+    }
+}
+
+fn rotate_cols<F: Float>(arr: &mut Array2<F>, i: usize, k: usize, c: F, s: F) {
+    for j in 0..arr.nrows() {
+        let (x, y) = (arr[(j, i)], arr[(j, k)]);
+        arr[(j, i)] = c * x + s * y;
+        arr[(j, k)] = c * y - s * x;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    fn assert_upper_triangular(r: &Array2<f64>) {
+        for ((i, j), e) in r.indexed_iter() {
+            if i > j {
+                assert!(e.abs() < 1e-12);
+            }
+        }
+    }
+
+    fn sample() -> Array2<f64> {
+        array![
+            [2.0, -1.0, 0.5],
+            [1.0, 3.0, -2.0],
+            [0.0, 1.0, 4.0],
+            [-1.0, 2.0, 1.0],
+            [3.0, 0.0, 1.0]
+        ]
+    }
+
+    #[test]
+    fn test_qr_factors() {
+        let arr = sample();
+        let qr = QR::new(&arr);
+
+        assert_close(&qr.q().dot(&qr.r()), &arr, 1e-12);
+        assert_close(&qr.q().t().dot(&qr.q()), &Array2::eye(5), 1e-12);
+        assert_close(&qr.thin_q().dot(&qr.thin_r()), &arr, 1e-12);
+        assert_upper_triangular(&qr.r());
+
+        // wide matrices work as well
+        let wide = arr.t().to_owned();
+        let qr = QR::new(&wide);
+        assert_close(&qr.q().dot(&qr.r()), &wide, 1e-12);
+    }
+
+    #[test]
+    fn test_qr_least_squares() {
+        let arr = sample();
+        let b = array![1.0, 2.0, -1.0, 0.5, 3.0];
+        let x = QR::new(&arr).solve(&b).unwrap();
+
+        // the residual is orthogonal to the columns of A
+        let residual = &arr.dot(&x) - &b;
+        assert!(arr.t().dot(&residual).iter().all(|e| e.abs() < 1e-12));
+
+        let singular = array![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+        assert_eq!(
+            QR::new(&singular).solve(&array![1.0, 2.0, 3.0]),
+            Err(LinalgError::Singular)
+        );
+    }
+
+    #[test]
+    fn test_qr_tall() {
+        // Q stays in compact form, so this needs neither an m x m matrix nor O(m^2) time
+        let m = 20_000;
+        let arr = Array2::from_shape_fn((m, 2), |(i, j)| (i as f64 / m as f64).powi(j as i32));
+        let b = arr.dot(&array![1.0, 2.0]);
+        let qr = QR::new(&arr);
+
+        let x = qr.solve(&b).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-10);
+        assert!((x[1] - 2.0).abs() < 1e-10);
+
+        let thin_q = qr.thin_q();
+        assert_eq!(thin_q.dim(), (m, 2));
+        assert_close(&thin_q.t().dot(&thin_q), &Array2::eye(2), 1e-12);
+        assert_close(&thin_q.dot(&qr.thin_r()), &arr, 1e-12);
+    }
+
+    #[test]
+    fn test_col_piv_qr_rank_deficient() {
+        // third column is the sum of the first two
+        let arr = array![
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 2.0],
+            [2.0, -1.0, 1.0]
+        ];
+        let qr = ColPivQR::new(&arr);
+        assert_eq!(qr.rank(), 2);
+
+        let ap = arr.select(Axis(1), qr.permutation());
+        assert_close(&qr.q().dot(&qr.r()), &ap, 1e-12);
+
+        let b = array![1.0, 2.0, 3.0, 0.0];
+        let x = lstsq(&arr, &b);
+        let residual = &arr.dot(&x) - &b;
+        assert!(arr.t().dot(&residual).iter().all(|e| e.abs() < 1e-12));
+        assert_eq!(x.iter().filter(|e| **e == 0.0).count(), 1);
+    }
+
+    #[test]
+    fn test_qr_rank_one_update() {
+        let arr = sample();
+        let u = array![1.0, -1.0, 2.0, 0.0, 0.5];
+        let v = array![0.3, 1.0, -2.0];
+
+        let mut qr = QR::new(&arr);
+        qr.rank_one_update(&u, &v);
+
+        let updated = &arr
+            + &u.view()
+                .insert_axis(Axis(1))
+                .dot(&v.view().insert_axis(Axis(0)));
+        assert_close(&qr.q().dot(&qr.r()), &updated, 1e-12);
+        assert_close(&qr.q().t().dot(&qr.q()), &Array2::eye(5), 1e-12);
+        assert_upper_triangular(&qr.r());
+    }
+
+    #[test]
+    fn test_qr_insert_delete_row() {
+        let arr = sample();
+        let row = array![1.0, 1.0, -1.0];
+
+        let mut qr = QR::new(&arr);
+        qr.insert_row(2, &row);
+
+        let mut rows: Vec<_> = arr.rows().into_iter().collect();
+        rows.insert(2, row.view());
+        let inserted = ndarray::stack(Axis(0), &rows).unwrap();
+        assert_close(&qr.q().dot(&qr.r()), &inserted, 1e-12);
+        assert_upper_triangular(&qr.r());
+
+        qr.delete_row(4);
+        let deleted = inserted.select(Axis(0), &[0, 1, 2, 3, 5]);
+        assert_close(&qr.q().dot(&qr.r()), &deleted, 1e-12);
+        assert_close(&qr.q().t().dot(&qr.q()), &Array2::eye(5), 1e-12);
+        assert_upper_triangular(&qr.r());
+    }
+
+    #[test]
+    fn test_qr_delete_last_row() {
+        let arr: Array2<f64> = array![[3.0, 4.0]];
+        let mut qr = QR::new(&arr);
+        qr.delete_row(0);
+        assert_eq!(qr.r().dim(), (0, 2));
+
+        qr.rank_one_update(&Array1::zeros(0), &array![1.0, 2.0]);
+        assert_eq!(qr.r().dim(), (0, 2));
+
+        qr.insert_row(0, &array![1.0, 2.0]);
+        assert_close(&qr.q().dot(&qr.r()), &array![[1.0, 2.0]], 1e-12);
+    }
+}
@@ -0,0 +1,123 @@
+//! Scalar fields of the linear algebra algorithms
+//!
+//! Algorithms that only need field arithmetic, conjugation and the modulus are generic over
+//! [`Scalar`] and work for real as well as complex matrices. Algorithms that need an ordering,
+//! such as Cholesky decompositions or matrix functions, are restricted to [`RealScalar`].
+
+use std::fmt::Debug;
+use std::ops::Neg;
+
+use num::{Complex, Float, Num};
+
+/// Element of the real or complex field
+///
+/// # Example
+/// ```
+/// use num::Complex;
+///
+/// use scialg::linalg::scalar::Scalar;
+///
+/// let z = Complex::new(3.0, 4.0);
+///
+/// assert_eq!(Scalar::modulus(z), 5.0);
+/// assert_eq!(Scalar::conj(z), Complex::new(3.0, -4.0));
+/// assert_eq!(Scalar::modulus(-2.0), 2.0);
+/// ```
+pub trait Scalar: Num + Neg<Output = Self> + Copy + Debug + Send + Sync + 'static {
+    /// Underlying real field
+    type Real: RealScalar;
+
+    /// Embed a real number
+    fn from_real(re: Self::Real) -> Self;
+
+    /// Return the real part
+    fn re(self) -> Self::Real;
+
+    /// Return the imaginary part, which is zero for real numbers
+    fn im(self) -> Self::Real;
+
+    /// Return the complex conjugate
+    fn conj(self) -> Self;
+
+    /// Return the absolute value
+    fn modulus(self) -> Self::Real;
+
+    /// Return the squared absolute value
+    fn modulus_squared(self) -> Self::Real;
+
+    /// Multiply by a real number
+    fn scale(self, factor: Self::Real) -> Self {
+        self * Self::from_real(factor)
+    }
+}
+
+/// Element of the real field
+///
+/// Implemented for every [`Scalar`] that is its own real field and a [`Float`].
+pub trait RealScalar: Scalar<Real = Self> + Float {}
+
+impl<T: Scalar<Real = T> + Float> RealScalar for T {}
+
+macro_rules! impl_real_scalar {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                type Real = $t;
+
+                fn from_real(re: $t) -> $t {
+                    re
+                }
+
+                fn re(self) -> $t {
+                    self
+                }
+
+                fn im(self) -> $t {
+                    0.0
+                }
+
+                fn conj(self) -> $t {
+                    self
+                }
+
+                fn modulus(self) -> $t {
+                    self.abs()
+                }
+
+                fn modulus_squared(self) -> $t {
+                    self * self
+                }
+            }
+        )*
+    };
+}
+
+impl_real_scalar!(f32, f64);
+
+impl<T: RealScalar> Scalar for Complex<T> {
+    type Real = T;
+
+    fn from_real(re: T) -> Self {
+        Complex::new(re, T::zero())
+    }
+
+    fn re(self) -> T {
+        self.re
+    }
+
+    fn im(self) -> T {
+        self.im
+    }
+
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+
+    fn modulus(self) -> T {
+        self.norm()
+    }
+
+    fn modulus_squared(self) -> T {
+        self.norm_sqr()
+    }
+}
@@ -0,0 +1,985 @@
+//! Sparse matrices and sparse direct solvers
+//!
+//! Matrices are assembled in coordinate format with [`CooMatrix`] and converted to the
+//! compressed formats [`CsrMatrix`] or [`CscMatrix`] for arithmetic. Both compressed formats
+//! keep the indices within each row (column) sorted and free of duplicates.
+//!
+//! ```
+//! use ndarray::array;
+//!
+//! use scialg::linalg::krylov::LinearOperator;
+//! use scialg::linalg::sparse::CooMatrix;
+//!
+//! let mut coo = CooMatrix::new(2, 3);
+//! coo.push(0, 0, 1.0);
+//! coo.push(1, 2, 2.0);
+//! coo.push(1, 2, 3.0);
+//! let csr = coo.to_csr();
+//!
+//! assert_eq!(csr.nnz(), 2);
+//! assert_eq!(csr.matvec(&array![1.0, 1.0, 1.0]), array![1.0, 5.0]);
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, VecDeque};
+use std::ops::Add;
+
+use ndarray::prelude::*;
+use num::Float;
+
+use crate::linalg::krylov::LinearOperator;
+use crate::linalg::LinalgError;
+
+/// Sparse matrix in coordinate format, used for assembly
+///
+/// Duplicate entries are summed when converting to a compressed format.
+#[derive(Debug, Clone)]
+pub struct CooMatrix<F> {
+    nrows: usize,
+    ncols: usize,
+    rows: Vec<usize>,
+    cols: Vec<usize>,
+    values: Vec<F>,
+}
+
+impl<F: Float> CooMatrix<F> {
+    /// Create an empty *nrows* x *ncols* matrix
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        CooMatrix {
+            nrows,
+            ncols,
+            rows: Vec::new(),
+            cols: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Add *value* to the entry at (*row*, *col*)
+    ///
+    /// # Panics
+    /// Panics if the position is out of bounds
+    pub fn push(&mut self, row: usize, col: usize, value: F) {
+        assert!(row < self.nrows && col < self.ncols);
+
+        self.rows.push(row);
+        self.cols.push(col);
+        self.values.push(value);
+    }
+
+    /// Return the number of rows
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Return the number of columns
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Return the number of stored entries, counting duplicates
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Convert to compressed sparse row format
+    pub fn to_csr(&self) -> CsrMatrix<F> {
+        CsrMatrix(Compressed::from_triplets(
+            self.nrows,
+            self.ncols,
+            &self.rows,
+            &self.cols,
+            &self.values,
+        ))
+    }
+
+    /// Convert to compressed sparse column format
+    pub fn to_csc(&self) -> CscMatrix<F> {
+        CscMatrix(Compressed::from_triplets(
+            self.ncols,
+            self.nrows,
+            &self.cols,
+            &self.rows,
+            &self.values,
+        ))
+    }
+
+    /// Convert to a dense matrix
+    pub fn to_dense(&self) -> Array2<F> {
+        let mut arr = Array2::zeros((self.nrows, self.ncols));
+        for k in 0..self.values.len() {
+            let (i, j) = (self.rows[k], self.cols[k]);
+            arr[(i, j)] = arr[(i, j)] + self.values[k];
+        }
+
+        arr
+    }
+}
+
+/// Sparse matrix in compressed sparse row (CSR) format
+///
+/// # References
+///  - [Wikipedia: Sparse matrix](https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format))
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<F>(Compressed<F>);
+
+impl<F: Float> CsrMatrix<F> {
+    /// Create a sparse matrix from the nonzero entries of *arr*
+    pub fn from_dense(arr: &Array2<F>) -> Self {
+        CsrMatrix(Compressed::from_dense(arr.view()))
+    }
+
+    /// Create the *n* x *n* identity matrix
+    pub fn identity(n: usize) -> Self {
+        CsrMatrix(Compressed::identity(n))
+    }
+
+    /// Return the number of rows
+    pub fn nrows(&self) -> usize {
+        self.0.major
+    }
+
+    /// Return the number of columns
+    pub fn ncols(&self) -> usize {
+        self.0.minor
+    }
+
+    /// Return the number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.0.data.len()
+    }
+
+    /// Return the row pointers; row *i* is stored in `indptr[i]..indptr[i + 1]`
+    pub fn indptr(&self) -> &[usize] {
+        &self.0.indptr
+    }
+
+    /// Return the column indices of the stored entries
+    pub fn indices(&self) -> &[usize] {
+        &self.0.indices
+    }
+
+    /// Return the values of the stored entries
+    pub fn data(&self) -> &[F] {
+        &self.0.data
+    }
+
+    /// Return the entry at (*row*, *col*)
+    ///
+    /// # Panics
+    /// Panics if the position is out of bounds
+    pub fn get(&self, row: usize, col: usize) -> F {
+        self.0.get(row, col)
+    }
+
+    /// Return the transposed matrix
+    pub fn transpose(&self) -> Self {
+        CsrMatrix(self.0.transpose())
+    }
+
+    /// Convert to compressed sparse column format
+    pub fn to_csc(&self) -> CscMatrix<F> {
+        CscMatrix(self.0.transpose())
+    }
+
+    /// Convert to a dense matrix
+    pub fn to_dense(&self) -> Array2<F> {
+        self.0.to_dense()
+    }
+
+    /// Return the product with the dense matrix *rhs*
+    ///
+    /// # Panics
+    /// Panics if the number of rows of *rhs* does not match the number of columns
+    pub fn dot_dense(&self, rhs: &Array2<F>) -> Array2<F> {
+        assert_eq!(self.ncols(), rhs.nrows());
+        let mut res = Array2::zeros((self.nrows(), rhs.ncols()));
+
+        for i in 0..self.nrows() {
+            for (j, a) in self.0.lane(i) {
+                for k in 0..rhs.ncols() {
+                    res[(i, k)] = res[(i, k)] + a * rhs[(j, k)];
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl<F: Float> LinearOperator<F> for CsrMatrix<F> {
+    fn matvec(&self, x: &Array1<F>) -> Array1<F> {
+        assert_eq!(self.ncols(), x.len());
+
+        (0..self.nrows())
+            .map(|i| self.0.lane(i).fold(F::zero(), |acc, (j, a)| acc + a * x[j]))
+            .collect()
+    }
+}
+
+impl<F: Float> Add for &CsrMatrix<F> {
+    type Output = CsrMatrix<F>;
+
+    fn add(self, rhs: Self) -> CsrMatrix<F> {
+        CsrMatrix(self.0.add(&rhs.0))
+    }
+}
+
+/// Sparse matrix in compressed sparse column (CSC) format
+///
+/// # References
+///  - [Wikipedia: Sparse matrix](https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_column_(CSC_or_CCS))
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<F>(Compressed<F>);
+
+impl<F: Float> CscMatrix<F> {
+    /// Create a sparse matrix from the nonzero entries of *arr*
+    pub fn from_dense(arr: &Array2<F>) -> Self {
+        CscMatrix(Compressed::from_dense(arr.t()))
+    }
+
+    /// Create the *n* x *n* identity matrix
+    pub fn identity(n: usize) -> Self {
+        CscMatrix(Compressed::identity(n))
+    }
+
+    /// Return the number of rows
+    pub fn nrows(&self) -> usize {
+        self.0.minor
+    }
+
+    /// Return the number of columns
+    pub fn ncols(&self) -> usize {
+        self.0.major
+    }
+
+    /// Return the number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.0.data.len()
+    }
+
+    /// Return the column pointers; column *j* is stored in `indptr[j]..indptr[j + 1]`
+    pub fn indptr(&self) -> &[usize] {
+        &self.0.indptr
+    }
+
+    /// Return the row indices of the stored entries
+    pub fn indices(&self) -> &[usize] {
+        &self.0.indices
+    }
+
+    /// Return the values of the stored entries
+    pub fn data(&self) -> &[F] {
+        &self.0.data
+    }
+
+    /// Return the entry at (*row*, *col*)
+    ///
+    /// # Panics
+    /// Panics if the position is out of bounds
+    pub fn get(&self, row: usize, col: usize) -> F {
+        self.0.get(col, row)
+    }
+
+    /// Return the transposed matrix
+    pub fn transpose(&self) -> Self {
+        CscMatrix(self.0.transpose())
+    }
+
+    /// Convert to compressed sparse row format
+    pub fn to_csr(&self) -> CsrMatrix<F> {
+        CsrMatrix(self.0.transpose())
+    }
+
+    /// Convert to a dense matrix
+    pub fn to_dense(&self) -> Array2<F> {
+        self.0.to_dense().reversed_axes()
+    }
+
+    /// Return the product with the dense matrix *rhs*
+    ///
+    /// # Panics
+    /// Panics if the number of rows of *rhs* does not match the number of columns
+    pub fn dot_dense(&self, rhs: &Array2<F>) -> Array2<F> {
+        assert_eq!(self.ncols(), rhs.nrows());
+        let mut res = Array2::zeros((self.nrows(), rhs.ncols()));
+
+        for j in 0..self.ncols() {
+            for (i, a) in self.0.lane(j) {
+                for k in 0..rhs.ncols() {
+                    res[(i, k)] = res[(i, k)] + a * rhs[(j, k)];
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl<F: Float> LinearOperator<F> for CscMatrix<F> {
+    fn matvec(&self, x: &Array1<F>) -> Array1<F> {
+        assert_eq!(self.ncols(), x.len());
+        let mut res = Array1::zeros(self.nrows());
+
+        for j in 0..self.ncols() {
+            for (i, a) in self.0.lane(j) {
+                res[i] = res[i] + a * x[j];
+            }
+        }
+
+        res
+    }
+}
+
+impl<F: Float> Add for &CscMatrix<F> {
+    type Output = CscMatrix<F>;
+
+    fn add(self, rhs: Self) -> CscMatrix<F> {
+        CscMatrix(self.0.add(&rhs.0))
+    }
+}
+
+/// Fill-reducing ordering of a sparse symmetric matrix
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OrderingMethod {
+    /// Keep the original order
+    Natural,
+    /// Reverse Cuthill-McKee, which reduces the bandwidth
+    ReverseCuthillMcKee,
+    /// Minimum degree, which greedily eliminates the node with the fewest neighbours
+    MinimumDegree,
+}
+
+/// Return the reverse Cuthill-McKee ordering of the symmetric sparsity pattern of *arr*
+///
+/// The entry `perm[k]` is the original index of the node placed at position *k*. Each connected
+/// component is traversed breadth first from a node of minimal degree, visiting neighbours in
+/// order of increasing degree.
+///
+/// # Panics
+/// Panics if *arr* is not a square matrix
+///
+/// # References
+///  - [Wikipedia: Cuthill-McKee algorithm](https://en.wikipedia.org/wiki/Cuthill%E2%80%93McKee_algorithm)
+pub fn reverse_cuthill_mckee<F: Float>(arr: &CsrMatrix<F>) -> Vec<usize> {
+    let adj = adjacency(arr);
+    let n = adj.len();
+    let degree: Vec<usize> = adj.iter().map(|a| a.len()).collect();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+
+    let mut start: Vec<usize> = (0..n).collect();
+    start.sort_by_key(|i| degree[*i]);
+    for s in start {
+        if visited[s] {
+            continue;
+        }
+        visited[s] = true;
+        let mut queue = VecDeque::from([s]);
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            let mut next: Vec<usize> = adj[v].iter().copied().filter(|u| !visited[*u]).collect();
+            next.sort_by_key(|u| degree[*u]);
+            for u in next {
+                visited[u] = true;
+                queue.push_back(u);
+            }
+        }
+    }
+    order.reverse();
+
+    order
+}
+
+/// Return the minimum degree ordering of the symmetric sparsity pattern of *arr*
+///
+/// The entry `perm[k]` is the original index of the node eliminated at step *k*. The
+/// elimination graph is updated exactly, so this is slower than approximate minimum degree
+/// (AMD) but produces orderings of similar quality.
+///
+/// # Panics
+/// Panics if *arr* is not a square matrix
+///
+/// # References
+///  - [Wikipedia: Minimum degree algorithm](https://en.wikipedia.org/wiki/Minimum_degree_algorithm)
+///  - T. A. Davis, *Direct Methods for Sparse Linear Systems*, SIAM (2006), chapter 7
+pub fn minimum_degree<F: Float>(arr: &CsrMatrix<F>) -> Vec<usize> {
+    let mut adj = adjacency(arr);
+    let n = adj.len();
+    let mut eliminated = vec![false; n];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> =
+        (0..n).map(|i| Reverse((adj[i].len(), i))).collect();
+    let mut order = Vec::with_capacity(n);
+
+    while let Some(Reverse((degree, v))) = heap.pop() {
+        if eliminated[v] || degree != adj[v].len() {
+            continue;
+        }
+        eliminated[v] = true;
+        order.push(v);
+
+        // the neighbours of v become a clique
+        let neighbours: Vec<usize> = adj[v].iter().copied().collect();
+        for u in &neighbours {
+            adj[*u].remove(&v);
+            for w in &neighbours {
+                if w != u {
+                    adj[*u].insert(*w);
+                }
+            }
+            heap.push(Reverse((adj[*u].len(), *u)));
+        }
+        adj[v].clear();
+    }
+
+    order
+}
+
+/// Sparse Cholesky decomposition `P A P^T = L L^T` of a symmetric positive definite matrix
+///
+/// The rows and columns are permuted with a fill-reducing ordering before factorizing, which
+/// keeps the factor *L* sparse.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::linalg::sparse::{CsrMatrix, OrderingMethod, SparseCholesky};
+///
+/// let arr: CsrMatrix<f64> = CsrMatrix::from_dense(&array![[4.0, 1.0, 0.0], [1.0, 4.0, 1.0], [0.0, 1.0, 4.0]]);
+/// let chol = SparseCholesky::new(&arr, OrderingMethod::MinimumDegree).unwrap();
+/// let x = chol.solve(&array![5.0, 6.0, 5.0]);
+///
+/// assert!(x.iter().all(|e| (e - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - T. A. Davis, *Direct Methods for Sparse Linear Systems*, SIAM (2006), chapter 4
+#[derive(Debug, Clone)]
+pub struct SparseCholesky<F> {
+    perm: Vec<usize>,
+    l: CscMatrix<F>,
+}
+
+impl<F: Float> SparseCholesky<F> {
+    /// Decompose *arr* after reordering it with *ordering*
+    ///
+    /// Only the lower triangle of *arr* is referenced. Returns
+    /// [`LinalgError::NotPositiveDefinite`] if *arr* is not positive definite and
+    /// [`LinalgError::NonSquare`] if *arr* is not square.
+    pub fn new(arr: &CsrMatrix<F>, ordering: OrderingMethod) -> Result<Self, LinalgError> {
+        if arr.nrows() != arr.ncols() {
+            return Err(LinalgError::NonSquare);
+        }
+        let n = arr.nrows();
+        let perm = match ordering {
+            OrderingMethod::Natural => (0..n).collect(),
+            OrderingMethod::ReverseCuthillMcKee => reverse_cuthill_mckee(arr),
+            OrderingMethod::MinimumDegree => minimum_degree(arr),
+        };
+        let mut inv_perm = vec![0; n];
+        for (k, p) in perm.iter().enumerate() {
+            inv_perm[*p] = k;
+        }
+
+        // upper triangle of P A P^T by columns, taken from the lower triangle of A
+        let mut upper: Vec<Vec<(usize, F)>> = vec![Vec::new(); n];
+        for i in 0..n {
+            for (j, a) in arr.0.lane(i).filter(|(j, _)| *j <= i) {
+                let (pi, pj) = (inv_perm[i], inv_perm[j]);
+                upper[pi.max(pj)].push((pi.min(pj), a));
+            }
+        }
+        let parent = elimination_tree(&upper);
+
+        // up-looking factorization computing one row of L at a time
+        let mut columns: Vec<Vec<(usize, F)>> = vec![Vec::new(); n];
+        let mut x = vec![F::zero(); n];
+        let mut mark = vec![usize::MAX; n];
+        for k in 0..n {
+            let mut pattern = Vec::new();
+            let mut d = F::zero();
+            mark[k] = k;
+            for (i, a) in &upper[k] {
+                if *i == k {
+                    d = d + *a;
+                    continue;
+                }
+                x[*i] = x[*i] + *a;
+                let mut i = *i;
+                while mark[i] != k {
+                    mark[i] = k;
+                    pattern.push(i);
+                    i = parent[i].expect("row pattern is contained in the elimination tree");
+                }
+            }
+            pattern.sort_unstable();
+
+            for j in pattern {
+                let lkj = x[j] / columns[j][0].1;
+                x[j] = F::zero();
+                for (i, lij) in columns[j].iter().skip(1) {
+                    x[*i] = x[*i] - *lij * lkj;
+                }
+                d = d - lkj * lkj;
+                columns[j].push((k, lkj));
+            }
+            if d <= F::zero() || d.is_nan() {
+                return Err(LinalgError::NotPositiveDefinite);
+            }
+            columns[k].push((k, d.sqrt()));
+        }
+
+        let mut indptr = vec![0];
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        for col in columns {
+            for (i, v) in col {
+                indices.push(i);
+                data.push(v);
+            }
+            indptr.push(indices.len());
+        }
+        let l = CscMatrix(Compressed {
+            major: n,
+            minor: n,
+            indptr,
+            indices,
+            data,
+        });
+
+        Ok(SparseCholesky { perm, l })
+    }
+
+    /// Return the permutation *P*, where `perm[k]` is the original index of row *k* of `P A P^T`
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Return the lower triangular factor *L*
+    pub fn l(&self) -> &CscMatrix<F> {
+        &self.l
+    }
+
+    /// Solve `A x = b`
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the dimension of *A*
+    pub fn solve(&self, b: &Array1<F>) -> Array1<F> {
+        let n = self.perm.len();
+        assert_eq!(b.len(), n);
+        let mut y: Vec<F> = self.perm.iter().map(|p| b[*p]).collect();
+
+        // L z = P b
+        for j in 0..n {
+            let mut col = self.l.0.lane(j);
+            let (_, ljj) = col.next().unwrap();
+            y[j] = y[j] / ljj;
+            for (i, lij) in col {
+                y[i] = y[i] - lij * y[j];
+            }
+        }
+        // L^T w = z
+        for j in (0..n).rev() {
+            let mut col = self.l.0.lane(j);
+            let (_, ljj) = col.next().unwrap();
+            let sum = col.fold(y[j], |acc, (i, lij)| acc - lij * y[i]);
+            y[j] = sum / ljj;
+        }
+
+        let mut x = Array1::zeros(n);
+        for (k, p) in self.perm.iter().enumerate() {
+            x[*p] = y[k];
+        }
+
+        x
+    }
+}
+
+/// Compressed storage shared by CSR (major = rows) and CSC (major = columns)
+#[derive(Debug, Clone, PartialEq)]
+struct Compressed<F> {
+    major: usize,
+    minor: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    data: Vec<F>,
+}
+
+impl<F: Float> Compressed<F> {
+    fn from_triplets(major: usize, minor: usize, maj: &[usize], min: &[usize], val: &[F]) -> Self {
+        let mut entries: Vec<(usize, usize, F)> =
+            (0..val.len()).map(|k| (maj[k], min[k], val[k])).collect();
+        entries.sort_by_key(|(i, j, _)| (*i, *j));
+
+        let mut indptr = vec![0; major + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(entries.len());
+        let mut data: Vec<F> = Vec::with_capacity(entries.len());
+        let mut last = None;
+        for (i, j, v) in entries {
+            if last == Some((i, j)) {
+                let d = data.last_mut().unwrap();
+                *d = *d + v;
+                continue;
+            }
+            last = Some((i, j));
+            indptr[i + 1] += 1;
+            indices.push(j);
+            data.push(v);
+        }
+        for i in 0..major {
+            indptr[i + 1] += indptr[i];
+        }
+
+        Compressed {
+            major,
+            minor,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    fn from_dense(arr: ArrayView2<F>) -> Self {
+        let mut indptr = vec![0];
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+        for row in arr.rows() {
+            for (j, v) in row.iter().enumerate() {
+                if *v != F::zero() {
+                    indices.push(j);
+                    data.push(*v);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed {
+            major: arr.nrows(),
+            minor: arr.ncols(),
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    fn identity(n: usize) -> Self {
+        Compressed {
+            major: n,
+            minor: n,
+            indptr: (0..=n).collect(),
+            indices: (0..n).collect(),
+            data: vec![F::one(); n],
+        }
+    }
+
+    /// Iterate over the (minor index, value) pairs of lane *i*
+    fn lane(&self, i: usize) -> impl Iterator<Item = (usize, F)> + '_ {
+        let range = self.indptr[i]..self.indptr[i + 1];
+        self.indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.data[range].iter().copied())
+    }
+
+    fn get(&self, i: usize, j: usize) -> F {
+        assert!(i < self.major && j < self.minor);
+        let range = self.indptr[i]..self.indptr[i + 1];
+
+        match self.indices[range.clone()].binary_search(&j) {
+            Ok(k) => self.data[range.start + k],
+            Err(_) => F::zero(),
+        }
+    }
+
+    /// Return the same matrix compressed along the other axis
+    fn transpose(&self) -> Self {
+        let mut indptr = vec![0; self.minor + 1];
+        for j in &self.indices {
+            indptr[j + 1] += 1;
+        }
+        for j in 0..self.minor {
+            indptr[j + 1] += indptr[j];
+        }
+
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.data.len()];
+        let mut data = vec![F::zero(); self.data.len()];
+        for i in 0..self.major {
+            for (j, v) in self.lane(i) {
+                indices[next[j]] = i;
+                data[next[j]] = v;
+                next[j] += 1;
+            }
+        }
+
+        Compressed {
+            major: self.minor,
+            minor: self.major,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    fn add(&self, rhs: &Self) -> Self {
+        assert_eq!((self.major, self.minor), (rhs.major, rhs.minor));
+        let mut indptr = vec![0];
+        let mut indices = Vec::new();
+        let mut data = Vec::new();
+
+        for i in 0..self.major {
+            let mut a = self.lane(i).peekable();
+            let mut b = rhs.lane(i).peekable();
+            loop {
+                let entry = match (a.peek(), b.peek()) {
+                    (Some((ja, va)), Some((jb, vb))) if ja == jb => {
+                        let entry = (*ja, *va + *vb);
+                        a.next();
+                        b.next();
+                        entry
+                    }
+                    (Some((ja, _)), Some((jb, _))) if ja < jb => a.next().unwrap(),
+                    (_, Some(_)) => b.next().unwrap(),
+                    (Some(_), None) => a.next().unwrap(),
+                    (None, None) => break,
+                };
+                indices.push(entry.0);
+                data.push(entry.1);
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed {
+            major: self.major,
+            minor: self.minor,
+            indptr,
+            indices,
+            data,
+        }
+    }
+
+    /// Return the dense matrix with major index along the rows
+    fn to_dense(&self) -> Array2<F> {
+        let mut arr = Array2::zeros((self.major, self.minor));
+        for i in 0..self.major {
+            for (j, v) in self.lane(i) {
+                arr[(i, j)] = v;
+            }
+        }
+
+        arr
+    }
+}
+
+/// Return the neighbours of each node in the symmetrized sparsity pattern of *arr*, ignoring
+/// the diagonal
+fn adjacency<F: Float>(arr: &CsrMatrix<F>) -> Vec<BTreeSet<usize>> {
+    assert_eq!(arr.nrows(), arr.ncols());
+    let mut adj = vec![BTreeSet::new(); arr.nrows()];
+
+    for i in 0..arr.nrows() {
+        for (j, _) in arr.0.lane(i).filter(|(j, _)| *j != i) {
+            adj[i].insert(j);
+            adj[j].insert(i);
+        }
+    }
+
+    adj
+}
+
+/// Return the elimination tree of the symmetric matrix given by the columns *upper* of its
+/// upper triangle
+fn elimination_tree<F>(upper: &[Vec<(usize, F)>]) -> Vec<Option<usize>> {
+    let n = upper.len();
+    let mut parent = vec![None; n];
+    let mut ancestor: Vec<Option<usize>> = vec![None; n];
+
+    for (k, col) in upper.iter().enumerate() {
+        for (i, _) in col {
+            let mut i = Some(*i);
+            while let Some(node) = i.filter(|node| *node < k) {
+                let next = ancestor[node];
+                ancestor[node] = Some(k);
+                if next.is_none() {
+                    parent[node] = Some(k);
+                }
+                i = next;
+            }
+        }
+    }
+
+    parent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Array2<f64> {
+        array![
+            [1.0, 0.0, 2.0, 0.0],
+            [0.0, 0.0, 3.0, 0.0],
+            [4.0, 0.0, 0.0, 5.0]
+        ]
+    }
+
+    /// 2D Laplacian on an n x n grid with Dirichlet boundaries
+    fn poisson(n: usize) -> CsrMatrix<f64> {
+        let mut coo = CooMatrix::new(n * n, n * n);
+        for iy in 0..n {
+            for ix in 0..n {
+                let i = iy * n + ix;
+                coo.push(i, i, 4.0);
+                if ix > 0 {
+                    coo.push(i, i - 1, -1.0);
+                }
+                if ix + 1 < n {
+                    coo.push(i, i + 1, -1.0);
+                }
+                if iy > 0 {
+                    coo.push(i, i - n, -1.0);
+                }
+                if iy + 1 < n {
+                    coo.push(i, i + n, -1.0);
+                }
+            }
+        }
+
+        coo.to_csr()
+    }
+
+    #[test]
+    fn test_coo_duplicates() {
+        let mut coo = CooMatrix::new(2, 2);
+        coo.push(1, 0, 1.0);
+        coo.push(0, 1, 2.0);
+        coo.push(1, 0, 3.0);
+
+        let csr = coo.to_csr();
+        assert_eq!(csr.nnz(), 2);
+        assert_eq!(csr.indptr(), &[0, 1, 2]);
+        assert_eq!(csr.to_dense(), array![[0.0, 2.0], [4.0, 0.0]]);
+        assert_eq!(coo.to_csc().to_dense(), coo.to_dense());
+    }
+
+    #[test]
+    fn test_conversions() {
+        let arr = sample();
+        let csr = CsrMatrix::from_dense(&arr);
+        let csc = CscMatrix::from_dense(&arr);
+
+        assert_eq!(csr.to_dense(), arr);
+        assert_eq!(csc.to_dense(), arr);
+        assert_eq!(csr.to_csc(), csc);
+        assert_eq!(csc.to_csr(), csr);
+        assert_eq!(csr.get(2, 3), 5.0);
+        assert_eq!(csc.get(2, 3), 5.0);
+        assert_eq!(csr.get(1, 1), 0.0);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let arr = sample();
+
+        assert_eq!(CsrMatrix::from_dense(&arr).transpose().to_dense(), arr.t());
+        assert_eq!(CscMatrix::from_dense(&arr).transpose().to_dense(), arr.t());
+    }
+
+    #[test]
+    fn test_matvec() {
+        let arr = sample();
+        let x = array![1.0, -1.0, 2.0, 0.5];
+
+        assert_eq!(CsrMatrix::from_dense(&arr).matvec(&x), arr.dot(&x));
+        assert_eq!(CscMatrix::from_dense(&arr).matvec(&x), arr.dot(&x));
+    }
+
+    #[test]
+    fn test_add() {
+        let a = sample();
+        let b = array![
+            [0.0, 1.0, -2.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0]
+        ];
+
+        let sum = &CsrMatrix::from_dense(&a) + &CsrMatrix::from_dense(&b);
+        assert_eq!(sum.to_dense(), &a + &b);
+        let sum = &CscMatrix::from_dense(&a) + &CscMatrix::from_dense(&b);
+        assert_eq!(sum.to_dense(), &a + &b);
+    }
+
+    #[test]
+    fn test_dot_dense() {
+        let arr = sample();
+        let rhs = Array2::from_shape_fn((4, 2), |(i, j)| (i + 2 * j) as f64);
+
+        assert_eq!(CsrMatrix::from_dense(&arr).dot_dense(&rhs), arr.dot(&rhs));
+        assert_eq!(CscMatrix::from_dense(&arr).dot_dense(&rhs), arr.dot(&rhs));
+    }
+
+    #[test]
+    fn test_orderings_are_permutations() {
+        let arr = poisson(6);
+
+        for mut perm in [reverse_cuthill_mckee(&arr), minimum_degree(&arr)] {
+            perm.sort_unstable();
+            assert_eq!(perm, (0..36).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_sparse_cholesky() {
+        let arr = poisson(8);
+        let b = Array1::from_shape_fn(64, |i| (i as f64).sin());
+        let dense = arr.to_dense();
+
+        let mut fill = Vec::new();
+        for ordering in [
+            OrderingMethod::Natural,
+            OrderingMethod::ReverseCuthillMcKee,
+            OrderingMethod::MinimumDegree,
+        ] {
+            let chol = SparseCholesky::new(&arr, ordering).unwrap();
+            let x = chol.solve(&b);
+            let residual = &dense.dot(&x) - &b;
+            assert!(residual.iter().all(|e| e.abs() < 1e-12));
+            fill.push(chol.l().nnz());
+        }
+        // minimum degree produces less fill than the natural (banded) ordering
+        assert!(fill[2] < fill[0]);
+    }
+
+    #[test]
+    fn test_sparse_cholesky_factor() {
+        let arr = poisson(3);
+        let chol = SparseCholesky::new(&arr, OrderingMethod::ReverseCuthillMcKee).unwrap();
+
+        let l = chol.l().to_dense();
+        let p = chol.permutation();
+        let dense = arr.to_dense();
+        let permuted = Array2::from_shape_fn((9, 9), |(i, j)| dense[(p[i], p[j])]);
+        let diff = &l.dot(&l.t()) - &permuted;
+        assert!(diff.iter().all(|e| e.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_sparse_cholesky_not_positive_definite() {
+        let arr = CsrMatrix::from_dense(&array![[1.0, 2.0], [2.0, 1.0]]);
+
+        assert_eq!(
+            SparseCholesky::new(&arr, OrderingMethod::Natural).unwrap_err(),
+            LinalgError::NotPositiveDefinite
+        );
+    }
+
+    #[test]
+    fn test_sparse_cholesky_non_square() {
+        let arr = CsrMatrix::from_dense(&array![[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+
+        assert_eq!(
+            SparseCholesky::new(&arr, OrderingMethod::Natural).unwrap_err(),
+            LinalgError::NonSquare
+        );
+    }
+}
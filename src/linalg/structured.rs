@@ -0,0 +1,504 @@
+//! Toeplitz, Vandermonde and circulant linear systems
+//!
+//! These matrices are determined by O(n) numbers, and exploiting their structure solves the
+//! systems in O(n^2) or O(n log n) operations instead of the O(n^3) of a dense factorization.
+
+use num::{Complex, Float};
+
+use crate::fourier::fft;
+use crate::linalg::LinalgError;
+
+/// Solve the Toeplitz system `T x = rhs` with the Levinson recursion in O(n^2) operations
+///
+/// The matrix is constant along its diagonals, `T[i, j] = column[i - j]` for `i >= j` and
+/// `T[i, j] = row[j - i]` for `i <= j`, so `column[0]` and `row[0]` both hold the diagonal. The
+/// recursion does not pivot and requires all leading principal submatrices to be nonsingular,
+/// which holds for symmetric positive definite matrices. Returns [`LinalgError::Singular`] if a
+/// leading principal submatrix is singular and [`LinalgError::DimensionMismatch`] if the lengths
+/// of *column*, *row* and *rhs* differ or `column[0] != row[0]`.
+///
+/// # Example
+/// ```
+/// use scialg::linalg::structured::solve_toeplitz;
+///
+/// // [[4, 1, 0.5], [2, 4, 1], [1, 2, 4]]
+/// let x = solve_toeplitz(&[4.0, 2.0, 1.0], &[4.0, 1.0, 0.5], &[5.5, 7.0, 7.0]).unwrap();
+///
+/// assert!(x.iter().all(|e: &f64| (e - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - [Wikipedia: Levinson recursion](https://en.wikipedia.org/wiki/Levinson_recursion)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 4.7
+pub fn solve_toeplitz<F: Float>(column: &[F], row: &[F], rhs: &[F]) -> Result<Vec<F>, LinalgError> {
+    let n = rhs.len();
+    if column.len() != n || row.len() != n || column.first() != row.first() {
+        return Err(LinalgError::DimensionMismatch);
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if column[0] == F::zero() {
+        return Err(LinalgError::Singular);
+    }
+
+    // forward and backward vectors solve T_k f = e_1 and T_k b = e_k for the leading k x k block
+    let mut f = vec![F::one() / column[0]];
+    let mut b = f.clone();
+    let mut x = vec![rhs[0] / column[0]];
+    for k in 1..n {
+        // last row and first row of T_{k+1} applied to the padded vectors
+        let err_f = (0..k).fold(F::zero(), |acc, i| acc + column[k - i] * f[i]);
+        let err_b = (0..k).fold(F::zero(), |acc, i| acc + row[i + 1] * b[i]);
+        let denom = F::one() - err_f * err_b;
+        if denom.abs() <= F::epsilon() || !denom.is_finite() {
+            return Err(LinalgError::Singular);
+        }
+
+        let mut f_next = vec![F::zero(); k + 1];
+        let mut b_next = vec![F::zero(); k + 1];
+        for i in 0..=k {
+            let fi = if i < k { f[i] } else { F::zero() };
+            let bi = if i > 0 { b[i - 1] } else { F::zero() };
+            f_next[i] = (fi - err_f * bi) / denom;
+            b_next[i] = (bi - err_b * fi) / denom;
+        }
+        f = f_next;
+        b = b_next;
+
+        let err_x = (0..k).fold(F::zero(), |acc, i| acc + column[k - i] * x[i]);
+        x.push(F::zero());
+        for (xi, bi) in x.iter_mut().zip(&b) {
+            *xi = *xi + (rhs[k] - err_x) * *bi;
+        }
+    }
+
+    Ok(x)
+}
+
+/// Autoregressive model fitted by [`levinson_durbin`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearPredictor<F> {
+    /// Coefficients `a_1, ..., a_p` of the prediction `x_t = a_1 x_{t-1} + ... + a_p x_{t-p}`
+    pub coefficients: Vec<F>,
+    /// Reflection (partial autocorrelation) coefficients of the orders `1, ..., p`
+    pub reflection: Vec<F>,
+    /// Variance of the prediction error
+    pub error: F,
+}
+
+/// Solve the Yule-Walker equations for the autoregressive model of order `p = autocorrelation.len() - 1`
+/// with the Levinson-Durbin recursion in O(p^2) operations
+///
+/// *autocorrelation* holds the autocovariances `r_0, ..., r_p` at lags `0, ..., p`, which
+/// define the symmetric Toeplitz system `sum_j a_j r_{|i - j|} = r_i`. Returns
+/// [`LinalgError::NotPositiveDefinite`] if the autocovariance sequence is not positive definite,
+/// i.e. if the prediction error does not stay positive.
+///
+/// # Panics
+/// Panics if *autocorrelation* is empty
+///
+/// # Example
+/// ```
+/// use scialg::linalg::structured::levinson_durbin;
+///
+/// // AR(1) process x_t = 0.5 x_{t-1} + e_t has autocovariances proportional to 0.5^k
+/// let ar = levinson_durbin(&[1.0_f64, 0.5, 0.25, 0.125]).unwrap();
+///
+/// assert!((ar.coefficients[0] - 0.5).abs() < 1e-12);
+/// assert!(ar.coefficients[1..].iter().all(|a: &f64| a.abs() < 1e-12));
+/// assert!((ar.error - 0.75).abs() < 1e-12);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Autoregressive model](https://en.wikipedia.org/wiki/Autoregressive_model#Yule%E2%80%93Walker_equations)
+///  - [Wikipedia: Levinson recursion](https://en.wikipedia.org/wiki/Levinson_recursion)
+pub fn levinson_durbin<F: Float>(autocorrelation: &[F]) -> Result<LinearPredictor<F>, LinalgError> {
+    assert!(!autocorrelation.is_empty());
+    let r = autocorrelation;
+    let p = r.len() - 1;
+
+    let mut a: Vec<F> = Vec::with_capacity(p);
+    let mut reflection = Vec::with_capacity(p);
+    let mut error = r[0];
+    if error <= F::zero() {
+        return Err(LinalgError::NotPositiveDefinite);
+    }
+    for k in 1..=p {
+        let lambda = (0..k - 1).fold(r[k], |acc, j| acc - a[j] * r[k - 1 - j]) / error;
+        let prev = a.clone();
+        for j in 0..k - 1 {
+            a[j] = prev[j] - lambda * prev[k - 2 - j];
+        }
+        a.push(lambda);
+        reflection.push(lambda);
+
+        error = error * (F::one() - lambda * lambda);
+        if error <= F::zero() || !error.is_finite() {
+            return Err(LinalgError::NotPositiveDefinite);
+        }
+    }
+
+    Ok(LinearPredictor {
+        coefficients: a,
+        reflection,
+        error,
+    })
+}
+
+/// Solve the Vandermonde system `sum_j a_j x_i^j = rhs_i` for the monomial coefficients of the
+/// polynomial interpolating the points `(x_i, rhs_i)` with the Björck-Pereyra algorithm in
+/// O(n^2) operations
+///
+/// The algorithm computes Newton divided differences and converts them to the monomial basis.
+/// It is often far more accurate than the condition number of the Vandermonde matrix suggests,
+/// especially for sorted nodes. Returns [`LinalgError::Singular`] if two nodes coincide and
+/// [`LinalgError::DimensionMismatch`] if the lengths of *nodes* and *rhs* differ.
+///
+/// # Example
+/// ```
+/// use scialg::linalg::structured::solve_vandermonde;
+///
+/// // p(x) = 1 - 2x + x^3
+/// let nodes = [-1.0, 0.0, 1.0, 2.0];
+/// let values = nodes.map(|x: f64| 1.0 - 2.0 * x + x.powi(3));
+/// let coeffs = solve_vandermonde(&nodes, &values).unwrap();
+///
+/// for (c, e) in coeffs.iter().zip([1.0, -2.0, 0.0, 1.0]) {
+///     assert!((c - e).abs() < 1e-12);
+/// }
+/// ```
+///
+/// # References
+///  - [Wikipedia: Vandermonde matrix](https://en.wikipedia.org/wiki/Vandermonde_matrix#Inverse_Vandermonde_matrix)
+///  - Å. Björck and V. Pereyra, *Solution of Vandermonde systems of equations*, Math. Comp. 24
+///    (1970)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 4.6
+pub fn solve_vandermonde<F: Float>(nodes: &[F], rhs: &[F]) -> Result<Vec<F>, LinalgError> {
+    let n = nodes.len();
+    if rhs.len() != n {
+        return Err(LinalgError::DimensionMismatch);
+    }
+    let x = nodes;
+    let mut a = rhs.to_vec();
+
+    // divided differences
+    for k in 0..n.saturating_sub(1) {
+        for i in (k + 1..n).rev() {
+            let dx = x[i] - x[i - k - 1];
+            if dx == F::zero() {
+                return Err(LinalgError::Singular);
+            }
+            a[i] = (a[i] - a[i - 1]) / dx;
+        }
+    }
+    // Newton form to monomial form
+    for k in (0..n.saturating_sub(1)).rev() {
+        for i in k..n - 1 {
+            a[i] = a[i] - a[i + 1] * x[k];
+        }
+    }
+
+    Ok(a)
+}
+
+/// Solve the transposed Vandermonde system `sum_i z_i x_i^j = rhs_j` with the Björck-Pereyra
+/// algorithm in O(n^2) operations
+///
+/// This is the system for weights *z* that reproduce the moments *rhs* of the monomials, such as
+/// the weights of an interpolatory quadrature rule with the given nodes. Returns
+/// [`LinalgError::Singular`] if two nodes coincide and [`LinalgError::DimensionMismatch`] if the
+/// lengths of *nodes* and *rhs* differ.
+///
+/// # Example
+/// ```
+/// use scialg::linalg::structured::solve_vandermonde_transposed;
+///
+/// // Simpson's rule integrates 1, x and x^2 exactly on [0, 1]
+/// let weights = solve_vandermonde_transposed(&[0.0_f64, 0.5, 1.0], &[1.0, 0.5, 1.0 / 3.0]).unwrap();
+///
+/// for (w, e) in weights.iter().zip([1.0, 4.0, 1.0]) {
+///     assert!((w - e / 6.0).abs() < 1e-12);
+/// }
+/// ```
+///
+/// # References
+///  - Å. Björck and V. Pereyra, *Solution of Vandermonde systems of equations*, Math. Comp. 24
+///    (1970)
+///  - G. H. Golub and C. F. Van Loan, *Matrix Computations*, 4th ed., section 4.6
+pub fn solve_vandermonde_transposed<F: Float>(
+    nodes: &[F],
+    rhs: &[F],
+) -> Result<Vec<F>, LinalgError> {
+    let n = nodes.len();
+    if rhs.len() != n {
+        return Err(LinalgError::DimensionMismatch);
+    }
+    let x = nodes;
+    let mut z = rhs.to_vec();
+
+    for (k, xk) in x.iter().enumerate().take(n.saturating_sub(1)) {
+        for i in (k + 1..n).rev() {
+            z[i] = z[i] - *xk * z[i - 1];
+        }
+    }
+    for k in (0..n.saturating_sub(1)).rev() {
+        for i in k + 1..n {
+            let dx = x[i] - x[i - k - 1];
+            if dx == F::zero() {
+                return Err(LinalgError::Singular);
+            }
+            z[i] = z[i] / dx;
+        }
+        for i in k..n - 1 {
+            z[i] = z[i] - z[i + 1];
+        }
+    }
+
+    Ok(z)
+}
+
+/// Solve the circulant system `C x = rhs` with `C[i, j] = column[(i - j) mod n]` using fast
+/// Fourier transforms in O(n log n) operations
+///
+/// Circulant matrices are diagonalized by the discrete Fourier transform, and their eigenvalues
+/// are the transform of *column*. Lengths that are not a power of two are transformed with
+/// Bluestein's algorithm on top of [`fft`]. Returns [`LinalgError::Singular`] if an eigenvalue
+/// vanishes relative to the largest one and [`LinalgError::DimensionMismatch`] if the lengths of
+/// *column* and *rhs* differ.
+///
+/// # Example
+/// ```
+/// use scialg::linalg::structured::solve_circulant;
+///
+/// // periodic second difference plus identity
+/// let column = [3.0, -1.0, 0.0, 0.0, 0.0, -1.0];
+/// let x = solve_circulant(&column, &[1.0; 6]).unwrap();
+///
+/// assert!(x.iter().all(|e| (e - 1.0).abs() < 1e-12));
+/// ```
+///
+/// # References
+///  - [Wikipedia: Circulant matrix](https://en.wikipedia.org/wiki/Circulant_matrix#Solving_linear_equations)
+///  - [Wikipedia: Chirp Z-transform](https://en.wikipedia.org/wiki/Chirp_Z-transform#Bluestein's_algorithm)
+pub fn solve_circulant(column: &[f64], rhs: &[f64]) -> Result<Vec<f64>, LinalgError> {
+    let n = column.len();
+    if rhs.len() != n {
+        return Err(LinalgError::DimensionMismatch);
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let to_complex =
+        |v: &[f64]| -> Vec<Complex<f64>> { v.iter().map(|e| Complex::new(*e, 0.0)).collect() };
+
+    let eigenvalues = dft(&to_complex(column));
+    let max = eigenvalues.iter().map(|e| e.norm()).fold(0.0, f64::max);
+    if eigenvalues
+        .iter()
+        .any(|e| e.norm() <= f64::EPSILON * n as f64 * max)
+    {
+        return Err(LinalgError::Singular);
+    }
+
+    let quotient: Vec<_> = dft(&to_complex(rhs))
+        .iter()
+        .zip(&eigenvalues)
+        .map(|(b, lambda)| (b / lambda).conj())
+        .collect();
+
+    // inverse transform as the conjugate of the forward transform of the conjugate
+    Ok(dft(&quotient).iter().map(|e| e.re / n as f64).collect())
+}
+
+/// Return the discrete Fourier transform of *values* of arbitrary length
+///
+/// Powers of two are transformed with [`fft`] directly, other lengths with Bluestein's
+/// algorithm, which writes the transform as a convolution with a chirp that is evaluated with
+/// power-of-two transforms.
+fn dft(values: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let n = values.len();
+    if n.is_power_of_two() {
+        let mut res = values.to_vec();
+        fft(&mut res);
+        return res;
+    }
+
+    // chirp w_k = exp(i pi k^2 / n), with k^2 reduced modulo 2n to keep the phase accurate
+    let chirp: Vec<Complex<f64>> = (0..n)
+        .map(|k| {
+            let phase = ((k * k) % (2 * n)) as f64 * std::f64::consts::PI / n as f64;
+            Complex::from_polar(1.0, phase)
+        })
+        .collect();
+
+    let m = (2 * n - 1).next_power_of_two();
+    let mut a = vec![Complex::new(0.0, 0.0); m];
+    let mut b = vec![Complex::new(0.0, 0.0); m];
+    for k in 0..n {
+        a[k] = values[k] * chirp[k].conj();
+        b[k] = chirp[k];
+        if k > 0 {
+            b[m - k] = chirp[k];
+        }
+    }
+    fft(&mut a);
+    fft(&mut b);
+
+    // circular convolution by the inverse transform of the product
+    let mut conv: Vec<_> = a.iter().zip(&b).map(|(x, y)| (x * y).conj()).collect();
+    fft(&mut conv);
+
+    (0..n)
+        .map(|k| conv[k].conj() / m as f64 * chirp[k].conj())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::prelude::*;
+
+    use super::*;
+    use crate::linalg::solve;
+
+    fn dense_toeplitz(column: &[f64], row: &[f64]) -> Array2<f64> {
+        let n = column.len();
+        Array2::from_shape_fn(
+            (n, n),
+            |(i, j)| {
+                if i >= j {
+                    column[i - j]
+                } else {
+                    row[j - i]
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn test_toeplitz_nonsymmetric() {
+        let column = [5.0, -1.0, 2.0, 0.5, 1.0, -0.3];
+        let row = [5.0, 1.5, -2.0, 0.7, 0.0, 0.2];
+        let rhs = [1.0, 2.0, -1.0, 0.0, 3.0, 1.0];
+
+        let x = solve_toeplitz(&column, &row, &rhs).unwrap();
+        let expected = solve(&dense_toeplitz(&column, &row), &Array1::from(rhs.to_vec())).unwrap();
+
+        assert!(x.iter().zip(&expected).all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_toeplitz_errors() {
+        // the leading 1 x 1 block is singular, although the matrix is not
+        assert_eq!(
+            solve_toeplitz(&[0.0, 1.0], &[0.0, 1.0], &[1.0, 1.0]),
+            Err(LinalgError::Singular)
+        );
+        assert_eq!(
+            solve_toeplitz(&[1.0, 1.0], &[1.0, 1.0], &[1.0, 1.0]),
+            Err(LinalgError::Singular)
+        );
+        assert_eq!(
+            solve_toeplitz(&[1.0, 0.0], &[2.0, 0.0], &[1.0, 1.0]),
+            Err(LinalgError::DimensionMismatch)
+        );
+        assert_eq!(solve_toeplitz::<f64>(&[], &[], &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_levinson_durbin_ar2() {
+        // AR(2) x_t = 0.6 x_{t-1} - 0.2 x_{t-2} + e_t with the autocovariances from the
+        // Yule-Walker equations
+        let (a1, a2) = (0.6, -0.2);
+        let rho1 = a1 / (1.0 - a2);
+        let mut r = vec![1.0, rho1];
+        for k in 2..6 {
+            r.push(a1 * r[k - 1] + a2 * r[k - 2]);
+        }
+
+        let ar = levinson_durbin(&r).unwrap();
+
+        let expected = [a1, a2, 0.0, 0.0, 0.0];
+        assert!(ar
+            .coefficients
+            .iter()
+            .zip(expected)
+            .all(|(a, e)| (a - e).abs() < 1e-12));
+        assert!((ar.reflection[0] - rho1).abs() < 1e-12);
+        assert!((ar.reflection[1] - a2).abs() < 1e-12);
+        assert!((ar.error - (1.0 - a1 * r[1] - a2 * r[2])).abs() < 1e-12);
+
+        // agrees with the general Toeplitz solver
+        let x = solve_toeplitz(&r[..5], &r[..5], &r[1..]).unwrap();
+        assert!(x
+            .iter()
+            .zip(&ar.coefficients)
+            .all(|(a, b)| (a - b).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_levinson_durbin_not_positive_definite() {
+        assert_eq!(
+            levinson_durbin(&[1.0, 1.0, 1.0]),
+            Err(LinalgError::NotPositiveDefinite)
+        );
+        assert_eq!(
+            levinson_durbin(&[1.0, 2.0]),
+            Err(LinalgError::NotPositiveDefinite)
+        );
+    }
+
+    #[test]
+    fn test_vandermonde() {
+        let nodes = [0.1, 0.5, 0.9, 1.3, 2.0, 2.2];
+        let rhs = [1.0, -1.0, 0.5, 2.0, 0.0, 1.0];
+        let vander = Array2::from_shape_fn((6, 6), |(i, j)| nodes[i].powi(j as i32));
+
+        let a = solve_vandermonde(&nodes, &rhs).unwrap();
+        let residual = vander.dot(&Array1::from(a)) - Array1::from(rhs.to_vec());
+        assert!(residual.iter().all(|e| e.abs() < 1e-10));
+
+        let z = solve_vandermonde_transposed(&nodes, &rhs).unwrap();
+        let residual = vander.t().dot(&Array1::from(z)) - Array1::from(rhs.to_vec());
+        assert!(residual.iter().all(|e| e.abs() < 1e-10));
+
+        assert_eq!(
+            solve_vandermonde(&[1.0, 2.0, 1.0], &[0.0; 3]),
+            Err(LinalgError::Singular)
+        );
+        assert_eq!(
+            solve_vandermonde_transposed(&[1.0, 2.0, 1.0], &[0.0; 3]),
+            Err(LinalgError::Singular)
+        );
+    }
+
+    #[test]
+    fn test_circulant() {
+        // power-of-two and Bluestein lengths, including lengths beyond 256 points
+        for n in [1, 2, 7, 8, 12, 300] {
+            let column: Vec<f64> = (0..n)
+                .map(|k| {
+                    if k == 0 {
+                        4.0
+                    } else {
+                        (k as f64 * 0.7).sin() / n as f64
+                    }
+                })
+                .collect();
+            let rhs: Vec<f64> = (0..n).map(|k| (k as f64 * 0.3).cos()).collect();
+
+            let x = solve_circulant(&column, &rhs).unwrap();
+
+            for i in 0..n {
+                let ax: f64 = (0..n).map(|j| column[(i + n - j) % n] * x[j]).sum();
+                assert!((ax - rhs[i]).abs() < 1e-10);
+            }
+        }
+
+        // the all-ones vector is in the kernel of the periodic Laplacian
+        assert_eq!(
+            solve_circulant(&[2.0, -1.0, 0.0, -1.0], &[1.0; 4]),
+            Err(LinalgError::Singular)
+        );
+    }
+}
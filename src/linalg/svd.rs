@@ -0,0 +1,504 @@
+//! Singular value decomposition and derived quantities
+
+use ndarray::prelude::*;
+use num::{Float, NumCast, One, Zero};
+
+use crate::linalg::scalar::Scalar;
+use crate::linalg::{conj_transpose, LinalgError};
+
+/// Singular value decomposition `A = U S V^H` of a real or complex m x n matrix
+///
+/// The singular values are sorted in descending order. The decomposition is computed with
+/// one-sided Jacobi rotations, which determine small singular values to high relative accuracy.
+/// For real matrices `V^H` is simply `V^T`.
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+/// use num::Complex;
+///
+/// use scialg::linalg::svd::SVD;
+///
+/// let arr: Array2<f64> = array![[3.0, 0.0], [4.0, 5.0]];
+/// let svd = SVD::new(&arr).unwrap();
+///
+/// assert!((svd.s()[0] - 45.0_f64.sqrt()).abs() < 1e-12);
+/// assert!((svd.s()[1] - 5.0_f64.sqrt()).abs() < 1e-12);
+/// assert!((svd.condition_number() - 3.0).abs() < 1e-12);
+///
+/// // singular values of complex matrices are real
+/// let arr = array![[Complex::new(0.0, 2.0), Complex::new(0.0, 0.0)], [Complex::new(0.0, 0.0), Complex::new(-1.0, 0.0)]];
+/// assert_eq!(SVD::new(&arr).unwrap().s(), array![2.0, 1.0]);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Singular value decomposition](https://en.wikipedia.org/wiki/Singular_value_decomposition)
+///  - J. Demmel and K. Veselić, *Jacobi's method is more accurate than QR*, SIAM J. Matrix Anal.
+///    Appl. 13 (1992)
+#[derive(Debug, Clone)]
+pub struct SVD<F: Scalar> {
+    u: Array2<F>,
+    s: Array1<F::Real>,
+    vt: Array2<F>,
+}
+
+impl<F: Scalar> SVD<F> {
+    /// Compute the thin decomposition with `k = min(m, n)` singular values,
+    /// where *U* is m x k and *V^H* is k x n
+    ///
+    /// Returns [`LinalgError::NoConvergence`] if *arr* has non-finite entries or the columns
+    /// are not orthogonal after 60 sweeps.
+    pub fn new(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        let (m, n) = arr.dim();
+        if m >= n {
+            let (u, s, v) = one_sided_jacobi(arr)?;
+            Ok(SVD {
+                u,
+                s,
+                vt: conj_transpose(&v),
+            })
+        } else {
+            let (v, s, u) = one_sided_jacobi(&conj_transpose(arr))?;
+            Ok(SVD {
+                u,
+                s,
+                vt: conj_transpose(&v),
+            })
+        }
+    }
+
+    /// Compute the full decomposition, where *U* is m x m and *V^H* is n x n
+    /// and only the first `min(m, n)` singular values are non-zero
+    ///
+    /// Returns [`LinalgError::NoConvergence`] under the same conditions as [`SVD::new`].
+    pub fn full(arr: &Array2<F>) -> Result<Self, LinalgError> {
+        let svd = Self::new(arr)?;
+
+        Ok(SVD {
+            u: complete_basis(&svd.u),
+            s: svd.s,
+            vt: conj_transpose(&complete_basis(&conj_transpose(&svd.vt))),
+        })
+    }
+
+    /// Return the left singular vectors as columns of *U*
+    pub fn u(&self) -> Array2<F> {
+        self.u.clone()
+    }
+
+    /// Return the singular values in descending order
+    pub fn s(&self) -> Array1<F::Real> {
+        self.s.clone()
+    }
+
+    /// Return the conjugated right singular vectors as rows of *V^H*
+    pub fn vt(&self) -> Array2<F> {
+        self.vt.clone()
+    }
+
+    /// Return the default tolerance `eps * max(m, n) * s_max` below which singular values
+    /// are treated as zero
+    pub fn default_tolerance(&self) -> F::Real {
+        let dim = self.u.nrows().max(self.vt.ncols());
+        F::Real::epsilon() * <F::Real as NumCast>::from(dim).unwrap() * self.norm_2()
+    }
+
+    /// Return the numerical rank, the number of singular values larger than *tol*
+    pub fn rank(&self, tol: Option<F::Real>) -> usize {
+        let tol = tol.unwrap_or_else(|| self.default_tolerance());
+        self.s.iter().filter(|s| **s > tol).count()
+    }
+
+    /// Return the matrix 2-norm, the largest singular value
+    pub fn norm_2(&self) -> F::Real {
+        self.s.first().copied().unwrap_or_else(F::Real::zero)
+    }
+
+    /// Return the condition number in the 2-norm, the ratio of largest and smallest singular value
+    pub fn condition_number(&self) -> F::Real {
+        match self.s.last() {
+            Some(s) if *s > F::Real::zero() => self.norm_2() / *s,
+            _ => F::Real::infinity(),
+        }
+    }
+
+    /// Return the Moore-Penrose pseudoinverse, treating singular values below *tol* as zero
+    pub fn pinv(&self, tol: Option<F::Real>) -> Array2<F> {
+        let r = self.rank(tol);
+        let n = self.vt.ncols();
+        let m = self.u.nrows();
+        let mut res = Array2::zeros((n, m));
+
+        for k in 0..r {
+            let inv = F::Real::one() / self.s[k];
+            for i in 0..n {
+                let vik = self.vt[(k, i)].conj().scale(inv);
+                for j in 0..m {
+                    res[(i, j)] = res[(i, j)] + vik * self.u[(j, k)].conj();
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Solve the linear least squares problem `min |A x - b|` with a truncated SVD
+    ///
+    /// Singular values below *tol* are discarded, which returns the minimum norm solution and
+    /// suppresses noise amplified by ill-conditioned directions.
+    ///
+    /// # Panics
+    /// Panics if the length of *b* does not match the number of rows of *A*
+    pub fn solve(&self, b: &Array1<F>, tol: Option<F::Real>) -> Array1<F> {
+        let m = self.u.nrows();
+        assert_eq!(b.len(), m);
+        let r = self.rank(tol);
+        let n = self.vt.ncols();
+        let mut x = Array1::zeros(n);
+
+        for k in 0..r {
+            let coeff = (0..m)
+                .fold(F::zero(), |acc, j| acc + self.u[(j, k)].conj() * b[j])
+                .scale(F::Real::one() / self.s[k]);
+            for i in 0..n {
+                x[i] = x[i] + coeff * self.vt[(k, i)].conj();
+            }
+        }
+
+        x
+    }
+}
+
+/// Return the Moore-Penrose pseudoinverse of *arr* with the default tolerance
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::linalg::svd::pinv;
+///
+/// let arr: Array2<f64> = array![[1.0, 2.0], [2.0, 4.0]];
+/// let pinv = pinv(&arr).unwrap();
+///
+/// assert!((&arr.dot(&pinv).dot(&arr) - &arr).iter().all(|e| e.abs() < 1e-12));
+/// ```
+pub fn pinv<F: Scalar>(arr: &Array2<F>) -> Result<Array2<F>, LinalgError> {
+    SVD::new(arr).map(|svd| svd.pinv(None))
+}
+
+/// Return the numerical rank of *arr* with the default tolerance
+pub fn rank<F: Scalar>(arr: &Array2<F>) -> Result<usize, LinalgError> {
+    SVD::new(arr).map(|svd| svd.rank(None))
+}
+
+/// Return the 2-norm of *arr*
+pub fn norm_2<F: Scalar>(arr: &Array2<F>) -> Result<F::Real, LinalgError> {
+    SVD::new(arr).map(|svd| svd.norm_2())
+}
+
+/// Factors `(U, S, V)` of a thin singular value decomposition
+type Factors<F> = (Array2<F>, Array1<<F as Scalar>::Real>, Array2<F>);
+
+/// Thin SVD `A = U S V^H` of an m x n matrix with m >= n using one-sided Jacobi rotations
+///
+/// Complex columns are first rotated by a phase, so that their inner product is real and the
+/// real Jacobi rotation applies.
+fn one_sided_jacobi<F: Scalar>(arr: &Array2<F>) -> Result<Factors<F>, LinalgError> {
+    if !arr.iter().all(|e| e.modulus().is_finite()) {
+        return Err(LinalgError::NoConvergence);
+    }
+    let (m, n) = arr.dim();
+    let mut a = arr.to_owned();
+    let mut v = Array2::from_diag_elem(n, F::one());
+    let one = F::Real::one();
+    let two = one + one;
+    let tol = F::Real::epsilon();
+
+    let mut converged = false;
+    for _sweep in 0..60 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let mut alpha = F::Real::zero();
+                let mut beta = F::Real::zero();
+                let mut gamma = F::zero();
+                for i in 0..m {
+                    alpha = alpha + a[(i, p)].modulus_squared();
+                    beta = beta + a[(i, q)].modulus_squared();
+                    gamma = gamma + a[(i, p)].conj() * a[(i, q)];
+                }
+                let abs_gamma = gamma.modulus();
+                if abs_gamma == F::Real::zero() || abs_gamma <= tol * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+
+                // column q <- column q * conj(gamma) / |gamma| makes the inner product real
+                let phase = gamma.conj().scale(one / abs_gamma);
+                let zeta = (beta - alpha) / (two * abs_gamma);
+                let t = zeta.signum() / (zeta.abs() + (one + zeta * zeta).sqrt());
+                let c = one / (one + t * t).sqrt();
+                let s = c * t;
+                for i in 0..m {
+                    let (x, y) = (a[(i, p)], a[(i, q)] * phase);
+                    a[(i, p)] = x.scale(c) - y.scale(s);
+                    a[(i, q)] = x.scale(s) + y.scale(c);
+                }
+                for i in 0..n {
+                    let (x, y) = (v[(i, p)], v[(i, q)] * phase);
+                    v[(i, p)] = x.scale(c) - y.scale(s);
+                    v[(i, q)] = x.scale(s) + y.scale(c);
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::NoConvergence);
+    }
+
+    let norms: Vec<F::Real> = a
+        .columns()
+        .into_iter()
+        .map(|col| {
+            col.iter()
+                .fold(F::Real::zero(), |acc, e| acc + e.modulus_squared())
+                .sqrt()
+        })
+        .collect();
+    // the norms of finite columns may still overflow
+    if !norms.iter().all(|s| s.is_finite()) {
+        return Err(LinalgError::NoConvergence);
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| {
+        norms[*j]
+            .partial_cmp(&norms[*i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let s: Array1<F::Real> = order.iter().map(|j| norms[*j]).collect();
+    let v = v.select(Axis(1), &order);
+    let mut u = a.select(Axis(1), &order);
+
+    // columns belonging to zero singular values are completed to an orthonormal set
+    let max = s.first().copied().unwrap_or_else(F::Real::zero);
+    let r = s.iter().filter(|s| **s > max * F::Real::epsilon()).count();
+    for k in 0..r {
+        let inv = one / s[k];
+        u.column_mut(k).mapv_inplace(|e| e.scale(inv));
+    }
+    if r < n {
+        let completed = complete_basis(&u.slice(s![.., ..r]).to_owned());
+        u.slice_mut(s![.., r..])
+            .assign(&completed.slice(s![.., r..n]));
+    }
+
+    Ok((u, s, v))
+}
+
+/// Extend the orthonormal columns of *q* to an orthonormal basis of the whole space
+///
+/// The unit vector with the largest component orthogonal to the current basis is
+/// orthogonalized twice with Gram-Schmidt and appended, until the basis is complete.
+fn complete_basis<F: Scalar>(q: &Array2<F>) -> Array2<F> {
+    let (m, k) = q.dim();
+    let mut basis = Array2::zeros((m, m));
+    basis.slice_mut(s![.., ..k]).assign(q);
+
+    for col in k..m {
+        let filled = basis.slice(s![.., ..col]);
+        let i = (0..m)
+            .map(|i| {
+                let proj = filled
+                    .row(i)
+                    .iter()
+                    .fold(F::Real::zero(), |acc, e| acc + e.modulus_squared());
+                (i, F::Real::one() - proj)
+            })
+            .fold((0, -F::Real::one()), |best, cur| {
+                if cur.1 > best.1 {
+                    cur
+                } else {
+                    best
+                }
+            })
+            .0;
+
+        let mut x = Array1::zeros(m);
+        x[i] = F::one();
+        for _pass in 0..2 {
+            for j in 0..col {
+                let coeff = (0..m).fold(F::zero(), |acc, l| acc + basis[(l, j)].conj() * x[l]);
+                for l in 0..m {
+                    x[l] = x[l] - coeff * basis[(l, j)];
+                }
+            }
+        }
+        let norm = x
+            .iter()
+            .fold(F::Real::zero(), |acc, e| acc + e.modulus_squared())
+            .sqrt();
+        basis
+            .column_mut(col)
+            .assign(&x.mapv(|e| e.scale(F::Real::one() / norm)));
+    }
+
+    basis
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array2<f64>, b: &Array2<f64>, tol: f64) {
+        assert_eq!(a.dim(), b.dim());
+        assert!(a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < tol));
+    }
+
+    fn reconstruct(svd: &SVD<f64>) -> Array2<f64> {
+        let (m, n) = (svd.u().nrows(), svd.vt().ncols());
+        let mut s = Array2::zeros((svd.u().ncols(), svd.vt().nrows()));
+        for (k, sk) in svd.s().iter().enumerate() {
+            s[(k, k)] = *sk;
+        }
+        let res = svd.u().dot(&s).dot(&svd.vt());
+        assert_eq!(res.dim(), (m, n));
+        res
+    }
+
+    fn sample() -> Array2<f64> {
+        array![
+            [2.0, -1.0, 0.5],
+            [1.0, 3.0, -2.0],
+            [0.0, 1.0, 4.0],
+            [-1.0, 2.0, 1.0]
+        ]
+    }
+
+    #[test]
+    fn test_svd_thin_and_full() {
+        for arr in [sample(), sample().t().to_owned()] {
+            let svd = SVD::new(&arr).unwrap();
+            assert_close(&reconstruct(&svd), &arr, 1e-12);
+            assert!(svd.s().windows(2).into_iter().all(|w| w[0] >= w[1]));
+            let k = svd.s().len();
+            assert_close(&svd.u().t().dot(&svd.u()), &Array2::eye(k), 1e-12);
+            assert_close(&svd.vt().dot(&svd.vt().t()), &Array2::eye(k), 1e-12);
+
+            let full = SVD::full(&arr).unwrap();
+            let (m, n) = arr.dim();
+            assert_close(&full.u().t().dot(&full.u()), &Array2::eye(m), 1e-12);
+            assert_close(&full.vt().dot(&full.vt().t()), &Array2::eye(n), 1e-12);
+            assert_close(&reconstruct(&full), &arr, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_svd_rank_deficient() {
+        // third column is the sum of the first two
+        let arr = array![
+            [1.0, 0.0, 1.0],
+            [0.0, 1.0, 1.0],
+            [1.0, 1.0, 2.0],
+            [2.0, -1.0, 1.0]
+        ];
+        let svd = SVD::new(&arr).unwrap();
+
+        assert_eq!(svd.rank(None), 2);
+        assert_eq!(rank(&arr).unwrap(), 2);
+        assert_close(&svd.u().t().dot(&svd.u()), &Array2::eye(3), 1e-12);
+        assert!(svd.condition_number() > 1e15);
+
+        // Moore-Penrose conditions
+        let p = pinv(&arr).unwrap();
+        assert_close(&arr.dot(&p).dot(&arr), &arr, 1e-12);
+        assert_close(&p.dot(&arr).dot(&p), &p, 1e-12);
+        let ap = arr.dot(&p);
+        assert_close(&ap, &ap.t().to_owned(), 1e-12);
+    }
+
+    #[test]
+    fn test_svd_norm_and_condition() {
+        let arr = array![[1.0, 0.0, 0.0], [0.0, -5.0, 0.0], [0.0, 0.0, 0.5]];
+        let svd = SVD::new(&arr).unwrap();
+
+        assert_eq!(svd.s(), array![5.0, 1.0, 0.5]);
+        assert_eq!(norm_2(&arr).unwrap(), 5.0);
+        assert_eq!(svd.condition_number(), 10.0);
+    }
+
+    #[test]
+    fn test_svd_truncated_least_squares() {
+        // nearly collinear columns
+        let arr = array![[1.0, 1.0], [1.0, 1.0 + 1e-10], [1.0, 1.0 - 1e-10]];
+        let b = array![2.0, 2.0, 2.0];
+        let svd = SVD::new(&arr).unwrap();
+
+        let x = svd.solve(&b, Some(1e-6));
+
+        // minimum norm solution spreads the weight evenly
+        assert!((x[0] - 1.0).abs() < 1e-8);
+        assert!((x[1] - 1.0).abs() < 1e-8);
+
+        let x = svd.solve(&array![1.0, 2.0, 3.0], None);
+        let residual = &arr.dot(&x) - &array![1.0, 2.0, 3.0];
+        assert!(arr.t().dot(&residual).iter().all(|e| e.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_svd_complex() {
+        let c = |re, im| num::Complex::new(re, im);
+        let arr = array![
+            [c(1.0, 2.0), c(0.0, -1.0), c(3.0, 0.0)],
+            [c(-2.0, 0.5), c(1.0, 1.0), c(0.0, 0.0)],
+            [c(0.0, 0.0), c(2.0, -3.0), c(1.0, 1.0)],
+            [c(1.0, 0.0), c(1.0, 0.0), c(-1.0, 2.0)]
+        ];
+        let close = |a: &Array2<num::Complex<f64>>, b: &Array2<num::Complex<f64>>| {
+            a.iter().zip(b.iter()).all(|(x, y)| (x - y).norm() < 1e-12)
+        };
+
+        for arr in [arr.clone(), conj_transpose(&arr)] {
+            let (m, n) = arr.dim();
+            for svd in [SVD::new(&arr).unwrap(), SVD::full(&arr).unwrap()] {
+                let (u, vt) = (svd.u(), svd.vt());
+                let mut s = Array2::zeros((u.ncols(), vt.nrows()));
+                for (k, sk) in svd.s().iter().enumerate() {
+                    s[(k, k)] = c(*sk, 0.0);
+                }
+                assert!(close(&u.dot(&s).dot(&vt), &arr));
+                assert!(close(&conj_transpose(&u).dot(&u), &Array2::eye(u.ncols())));
+                assert!(close(
+                    &vt.dot(&conj_transpose(&vt)),
+                    &Array2::eye(vt.nrows())
+                ));
+            }
+
+            let p = pinv(&arr).unwrap();
+            assert_eq!(p.dim(), (n, m));
+            assert!(close(&arr.dot(&p).dot(&arr), &arr));
+        }
+
+        // the singular values are the square roots of the eigenvalues of A^H A
+        let gram = conj_transpose(&arr).dot(&arr);
+        let eig = crate::linalg::eigen::HermitianEigen::new(&gram).unwrap();
+        let s = SVD::new(&arr).unwrap().s();
+        for (sk, lambda) in s.iter().zip(eig.values().iter().rev()) {
+            assert!((sk * sk - lambda).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_svd_non_finite() {
+        let arr = array![[1.0, f64::NAN], [0.0, 1.0]];
+        assert_eq!(SVD::new(&arr).unwrap_err(), LinalgError::NoConvergence);
+
+        let arr = array![[1.0, 0.0], [f64::INFINITY, 1.0], [0.0, 2.0]];
+        assert_eq!(SVD::new(&arr).unwrap_err(), LinalgError::NoConvergence);
+        assert_eq!(norm_2(&arr), Err(LinalgError::NoConvergence));
+    }
+}
@@ -0,0 +1,410 @@
+//! Matrix algebra
+
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+
+use ndarray::Array2;
+use num::Zero;
+
+use crate::vector::Vector;
+
+/// Stack-allocated matrix with *R* rows and *C* columns, stored row by row
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Matrix<const R: usize, const C: usize> {
+    pub coeff: [[f64; C]; R],
+}
+
+impl<const R: usize, const C: usize> Matrix<R, C> {
+    pub fn dim(self) -> (usize, usize) {
+        (R, C)
+    }
+
+    /// Create a matrix from its coefficients in row-major order
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::matrix::Matrix;
+    ///
+    /// let m: Matrix<2, 3> = Matrix::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    ///
+    /// assert_eq!(m[(1, 0)], 4.0);
+    /// ```
+    pub fn new(coeff: &[f64]) -> Self {
+        assert_eq!(
+            coeff.len(),
+            R * C,
+            "length of input does not match dimensions of matrix"
+        );
+
+        let mut arr = [[0.0; C]; R];
+        for (row, chunk) in arr.iter_mut().zip(coeff.chunks(C.max(1))) {
+            row.copy_from_slice(chunk);
+        }
+
+        Matrix { coeff: arr }
+    }
+
+    /// Create a matrix from its rows
+    pub fn from_rows(rows: &[Vector<C>; R]) -> Self {
+        let mut arr = [[0.0; C]; R];
+        for (row, v) in arr.iter_mut().zip(rows) {
+            *row = v.coeff;
+        }
+
+        Matrix { coeff: arr }
+    }
+
+    /// Return row *i*
+    pub fn row(self, i: usize) -> Vector<C> {
+        Vector {
+            coeff: self.coeff[i],
+        }
+    }
+
+    /// Return column *j*
+    pub fn column(self, j: usize) -> Vector<R> {
+        let mut cs = [0.0; R];
+        for (c, row) in cs.iter_mut().zip(self.coeff.iter()) {
+            *c = row[j];
+        }
+
+        Vector { coeff: cs }
+    }
+
+    /// Return the transposed matrix
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::matrix::Matrix;
+    ///
+    /// let m: Matrix<2, 3> = Matrix::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    ///
+    /// assert_eq!(m.transpose(), Matrix::new(&[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]));
+    /// ```
+    pub fn transpose(self) -> Matrix<C, R> {
+        let mut cs = [[0.0; R]; C];
+        for (i, row) in self.coeff.iter().enumerate() {
+            for (j, e) in row.iter().enumerate() {
+                cs[j][i] = *e;
+            }
+        }
+
+        Matrix { coeff: cs }
+    }
+}
+
+impl<const N: usize> Matrix<N, N> {
+    /// Return the identity matrix
+    pub fn identity() -> Self {
+        let mut cs = [[0.0; N]; N];
+        for (i, row) in cs.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+
+        Matrix { coeff: cs }
+    }
+
+    /// Return the sum of the diagonal elements
+    pub fn trace(self) -> f64 {
+        (0..N).map(|i| self.coeff[i][i]).sum()
+    }
+
+    /// Return the determinant
+    ///
+    /// Sizes up to 3 use the explicit formulas, larger ones Gaussian elimination with partial
+    /// pivoting.
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::matrix::Matrix;
+    ///
+    /// let m: Matrix<3, 3> = Matrix::new(&[2.0, 0.0, 1.0, 1.0, 3.0, 2.0, 1.0, 1.0, 2.0]);
+    ///
+    /// assert_eq!(m.determinant(), 6.0);
+    /// ```
+    pub fn determinant(self) -> f64 {
+        let a = &self.coeff;
+        match N {
+            0 => 1.0,
+            1 => a[0][0],
+            2 => a[0][0] * a[1][1] - a[0][1] * a[1][0],
+            3 => {
+                a[0][0] * (a[1][1] * a[2][2] - a[1][2] * a[2][1])
+                    - a[0][1] * (a[1][0] * a[2][2] - a[1][2] * a[2][0])
+                    + a[0][2] * (a[1][0] * a[2][1] - a[1][1] * a[2][0])
+            }
+            _ => {
+                let mut lu = self.coeff;
+                let mut det = 1.0;
+                for k in 0..N {
+                    let p = (k..N)
+                        .max_by(|i, j| lu[*i][k].abs().total_cmp(&lu[*j][k].abs()))
+                        .unwrap();
+                    if lu[p][k] == 0.0 {
+                        return 0.0;
+                    }
+                    if p != k {
+                        lu.swap(p, k);
+                        det = -det;
+                    }
+                    det *= lu[k][k];
+                    let pivot_row = lu[k];
+                    for row in lu.iter_mut().skip(k + 1) {
+                        let factor = row[k] / pivot_row[k];
+                        for (e, p) in row.iter_mut().zip(pivot_row).skip(k) {
+                            *e -= factor * p;
+                        }
+                    }
+                }
+
+                det
+            }
+        }
+    }
+
+    /// Return the inverse, or None if the matrix is singular
+    ///
+    /// Sizes up to 2 use the explicit formulas, larger ones Gauss-Jordan elimination with
+    /// partial pivoting.
+    ///
+    /// # Example
+    /// ```
+    /// use scialg::matrix::Matrix;
+    ///
+    /// let m: Matrix<2, 2> = Matrix::new(&[4.0, 7.0, 2.0, 6.0]);
+    /// let inv = m.inverse().unwrap();
+    ///
+    /// assert_eq!(inv, Matrix::new(&[0.6, -0.7, -0.2, 0.4]));
+    /// assert!(Matrix::<2, 2>::new(&[1.0, 2.0, 2.0, 4.0]).inverse().is_none());
+    ///
+    /// let m: Matrix<4, 4> = Matrix::new(&[
+    ///     0.0, 2.0, 0.0, 1.0, 1.0, 0.0, 3.0, 0.0, 0.0, 1.0, 1.0, 4.0, 2.0, 0.0, 0.0, 1.0,
+    /// ]);
+    /// let diff = m * m.inverse().unwrap() - Matrix::identity();
+    ///
+    /// assert!(diff.coeff.iter().flatten().all(|e| e.abs() < 1e-12));
+    /// assert!((m.determinant() - m.transpose().determinant()).abs() < 1e-12);
+    /// ```
+    pub fn inverse(self) -> Option<Self> {
+        let a = &self.coeff;
+        let mut inv = Self::identity();
+
+        match N {
+            1 | 2 => {
+                let det = self.determinant();
+                if det == 0.0 {
+                    return None;
+                }
+                if N == 1 {
+                    inv.coeff[0][0] = 1.0 / det;
+                } else {
+                    inv.coeff[0][0] = a[1][1] / det;
+                    inv.coeff[0][1] = -a[0][1] / det;
+                    inv.coeff[1][0] = -a[1][0] / det;
+                    inv.coeff[1][1] = a[0][0] / det;
+                }
+            }
+            _ => {
+                let mut lu = self.coeff;
+                for k in 0..N {
+                    let p = (k..N)
+                        .max_by(|i, j| lu[*i][k].abs().total_cmp(&lu[*j][k].abs()))
+                        .unwrap();
+                    if lu[p][k] == 0.0 {
+                        return None;
+                    }
+                    lu.swap(p, k);
+                    inv.coeff.swap(p, k);
+
+                    let pivot = lu[k][k];
+                    lu[k].iter_mut().for_each(|e| *e /= pivot);
+                    inv.coeff[k].iter_mut().for_each(|e| *e /= pivot);
+                    let (pivot_row, inv_row) = (lu[k], inv.coeff[k]);
+                    for i in (0..N).filter(|i| *i != k) {
+                        let factor = lu[i][k];
+                        for (e, p) in lu[i].iter_mut().zip(pivot_row) {
+                            *e -= factor * p;
+                        }
+                        for (e, p) in inv.coeff[i].iter_mut().zip(inv_row) {
+                            *e -= factor * p;
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(inv)
+    }
+}
+
+impl<const R: usize, const C: usize> Zero for Matrix<R, C> {
+    fn zero() -> Self {
+        Matrix {
+            coeff: [[0.0; C]; R],
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeff.iter().flatten().all(|x| *x == 0.0)
+    }
+}
+
+impl<const R: usize, const C: usize> Add for Matrix<R, C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut cs = self.coeff;
+        for (row, rhs_row) in cs.iter_mut().zip(rhs.coeff.iter()) {
+            for (c, r) in row.iter_mut().zip(rhs_row) {
+                *c += r;
+            }
+        }
+
+        Matrix { coeff: cs }
+    }
+}
+
+impl<const R: usize, const C: usize> Sub for Matrix<R, C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut cs = self.coeff;
+        for (row, rhs_row) in cs.iter_mut().zip(rhs.coeff.iter()) {
+            for (c, r) in row.iter_mut().zip(rhs_row) {
+                *c -= r;
+            }
+        }
+
+        Matrix { coeff: cs }
+    }
+}
+
+impl<const R: usize, const C: usize> Mul<f64> for Matrix<R, C> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        let mut cs = self.coeff;
+        cs.iter_mut().flatten().for_each(|c| *c *= rhs);
+
+        Matrix { coeff: cs }
+    }
+}
+
+impl<const R: usize, const C: usize> Div<f64> for Matrix<R, C> {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        let mut cs = self.coeff;
+        cs.iter_mut().flatten().for_each(|c| *c /= rhs);
+
+        Matrix { coeff: cs }
+    }
+}
+
+/// Matrix-vector product
+///
+/// # Example
+/// ```
+/// use scialg::matrix::Matrix;
+/// use scialg::vector::Vector;
+///
+/// // rotation by 90 degrees
+/// let rot: Matrix<2, 2> = Matrix::new(&[0.0, -1.0, 1.0, 0.0]);
+/// let x: Vector<2> = Vector::new(&[1.0, 0.0]);
+///
+/// assert_eq!(rot * x, Vector::new(&[0.0, 1.0]));
+/// ```
+impl<const R: usize, const C: usize> Mul<Vector<C>> for Matrix<R, C> {
+    type Output = Vector<R>;
+
+    fn mul(self, rhs: Vector<C>) -> Self::Output {
+        let mut cs = [0.0; R];
+        for (c, row) in cs.iter_mut().zip(self.coeff.iter()) {
+            *c = row.iter().zip(rhs.coeff.iter()).map(|(a, b)| a * b).sum();
+        }
+
+        Vector { coeff: cs }
+    }
+}
+
+/// Matrix-matrix product
+///
+/// # Example
+/// ```
+/// use scialg::matrix::Matrix;
+///
+/// let a: Matrix<2, 3> = Matrix::new(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+/// let b: Matrix<3, 1> = Matrix::new(&[1.0, 0.0, -1.0]);
+///
+/// assert_eq!(a * b, Matrix::new(&[-2.0, -2.0]));
+/// ```
+impl<const R: usize, const K: usize, const C: usize> Mul<Matrix<K, C>> for Matrix<R, K> {
+    type Output = Matrix<R, C>;
+
+    fn mul(self, rhs: Matrix<K, C>) -> Self::Output {
+        let mut cs = [[0.0; C]; R];
+        for (res_row, row) in cs.iter_mut().zip(self.coeff.iter()) {
+            for (a, rhs_row) in row.iter().zip(rhs.coeff.iter()) {
+                for (c, b) in res_row.iter_mut().zip(rhs_row) {
+                    *c += a * b;
+                }
+            }
+        }
+
+        Matrix { coeff: cs }
+    }
+}
+
+impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+    type Output = f64;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.coeff[index.0][index.1]
+    }
+}
+
+impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.coeff[index.0][index.1]
+    }
+}
+
+/// Conversion to a dynamically sized matrix
+///
+/// # Example
+/// ```
+/// use ndarray::{array, Array2};
+///
+/// use scialg::matrix::Matrix;
+///
+/// let m: Matrix<2, 2> = Matrix::new(&[1.0, 2.0, 3.0, 4.0]);
+/// let arr: Array2<f64> = m.into();
+///
+/// assert_eq!(arr, array![[1.0, 2.0], [3.0, 4.0]]);
+/// assert_eq!(Matrix::from(&arr), m);
+/// ```
+impl<const R: usize, const C: usize> From<Matrix<R, C>> for Array2<f64> {
+    fn from(m: Matrix<R, C>) -> Self {
+        Array2::from_shape_fn((R, C), |(i, j)| m.coeff[i][j])
+    }
+}
+
+/// Conversion from a dynamically sized matrix
+///
+/// # Panics
+/// Panics if the shape of the array does not match the dimensions of the matrix
+impl<const R: usize, const C: usize> From<&Array2<f64>> for Matrix<R, C> {
+    fn from(arr: &Array2<f64>) -> Self {
+        assert_eq!(
+            arr.dim(),
+            (R, C),
+            "shape of input does not match dimensions of matrix"
+        );
+
+        let mut cs = [[0.0; C]; R];
+        for ((i, j), e) in arr.indexed_iter() {
+            cs[i][j] = *e;
+        }
+
+        Matrix { coeff: cs }
+    }
+}
@@ -2,7 +2,9 @@
 
 use ndarray::prelude::*;
 
-use crate::linalg::{invert, transpose};
+use crate::linalg::invert;
+use crate::linalg::qr::QR;
+use crate::linalg::svd::SVD;
 
 /// Calculate a linear regression model `f(x) = b * x + a` for the data set (xs, ys), where xs are exact and ys have
 /// standard deviations of yerrs
@@ -58,23 +60,34 @@ pub fn linear_regression(xs: &[f64], ys: &[f64], yerrs: &[f64]) -> (f64, f64, f6
 
 /// Compute solution to the general linear least squares problem
 ///
+/// The design matrix is decomposed with a [`QR`] decomposition instead of solving the normal
+/// equations, which would square the condition number of the problem.
+/// If the basis functions are linearly dependent on the data points, this falls back to
+/// [`svd_least_squares`] with the default tolerance, which returns the minimum norm solution.
+/// Returns the fitted coefficients and their variances.
+///
+/// # Panics
+/// Panics if the fallback fails, see [`svd_least_squares`]
+///
 /// # Reference
 ///  - [Wikipedia: Generalized least squares](https://en.wikipedia.org/wiki/Generalized_least_squares)
 pub fn general_linear_least_squares(
     xs: &[f64],
     ys: &[f64],
-    fns: &Vec<fn(f64) -> f64>,
+    fns: &[fn(f64) -> f64],
 ) -> (Vec<f64>, Vec<f64>) {
     let n = xs.len();
     let m = fns.len();
     let ys: Array1<f64> = Array1::from(ys.to_vec());
 
     let design: Array2<f64> = Array2::from_shape_fn((n, m), |(j, i)| fns[i](xs[j]));
-    let design_transposed = transpose(&design);
-    let covariance = invert(&design_transposed.dot(&design));
-
-    let res = covariance.dot(&design_transposed);
-    let vals = res.dot(&ys);
+    let qr = QR::new(&design);
+    // (A^T A)^-1 = R^-1 R^-T
+    let (vals, r_inv) = match (qr.solve(&ys), invert(&qr.thin_r())) {
+        (Ok(vals), Ok(r_inv)) => (vals, r_inv),
+        _ => return svd_least_squares(xs, ys.as_slice().unwrap(), fns, None),
+    };
+    let covariance = r_inv.dot(&r_inv.t());
 
     let vals: Vec<_> = vals.iter().cloned().collect();
     let vars: Vec<_> = covariance.diag().iter().cloned().collect();
@@ -82,6 +95,45 @@ pub fn general_linear_least_squares(
     (vals, vars)
 }
 
+/// Compute solution to the general linear least squares problem using a truncated singular
+/// value decomposition of the design matrix
+///
+/// Singular values below *tol* (by default `eps * max(n, m) * s_max`) are discarded.
+/// Unlike [`general_linear_least_squares`] this does not fail for (nearly) linearly dependent
+/// basis functions, and the returned variances only contain the well-determined directions
+/// instead of being dominated by rounding errors.
+/// Returns the fitted coefficients and their variances.
+///
+/// # Panics
+/// Panics if the singular value decomposition of the design matrix does not converge, e.g. for
+/// non-finite data
+///
+/// # References
+///  - [Wikipedia: Singular value decomposition](https://en.wikipedia.org/wiki/Singular_value_decomposition#Applications_of_the_SVD)
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 15.4.2
+pub fn svd_least_squares(
+    xs: &[f64],
+    ys: &[f64],
+    fns: &[fn(f64) -> f64],
+    tol: Option<f64>,
+) -> (Vec<f64>, Vec<f64>) {
+    let n = xs.len();
+    let m = fns.len();
+    let ys: Array1<f64> = Array1::from(ys.to_vec());
+
+    let design: Array2<f64> = Array2::from_shape_fn((n, m), |(j, i)| fns[i](xs[j]));
+    let svd = SVD::new(&design).expect("singular value decomposition did not converge");
+    let vals = svd.solve(&ys, tol);
+
+    let rank = svd.rank(tol);
+    let (s, vt) = (svd.s(), svd.vt());
+    let vars = (0..m)
+        .map(|j| (0..rank).map(|k| (vt[(k, j)] / s[k]).powi(2)).sum())
+        .collect();
+
+    (vals.to_vec(), vars)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +154,75 @@ mod tests {
             assert!((c - 1.0) < 1e-5);
         }
     }
+
+    #[test]
+    fn test_general_linear_least_squares_variances() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = vec![1.1, 2.9, 5.2, 7.1, 8.8];
+        let sigs = vec![1.0; 5];
+
+        let f0: fn(f64) -> f64 = |_| 1.0;
+        let f1: fn(f64) -> f64 = |x| x;
+
+        let (coeffs, vars) = general_linear_least_squares(&xs, &ys, &[f0, f1]);
+        let (a, b, siga, sigb, _chi2) = linear_regression(&xs, &ys, &sigs);
+
+        assert!((coeffs[0] - a).abs() < 1e-12);
+        assert!((coeffs[1] - b).abs() < 1e-12);
+        assert!((vars[0] - siga * siga).abs() < 1e-12);
+        assert!((vars[1] - sigb * sigb).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_svd_least_squares() {
+        let xs = vec![-2.0, -1.0, 0.0, 1.0, 2.0];
+        let ys = vec![3.0, 1.0, 1.0, 3.0, 7.0];
+
+        let f0: fn(f64) -> f64 = |_| 1.0;
+        let f1: fn(f64) -> f64 = |x| x;
+        let f2: fn(f64) -> f64 = |x| x * x;
+        let fs = vec![f0, f1, f2];
+
+        let (coeffs, vars) = svd_least_squares(&xs, &ys, &fs, None);
+        let (coeffs_qr, vars_qr) = general_linear_least_squares(&xs, &ys, &fs);
+
+        for i in 0..3 {
+            assert!((coeffs[i] - coeffs_qr[i]).abs() < 1e-12);
+            assert!((vars[i] - vars_qr[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_general_linear_least_squares_degenerate() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0];
+        let ys = vec![1.0, 3.0, 5.0, 7.0];
+
+        // the first two basis functions are identical
+        let f0: fn(f64) -> f64 = |_| 1.0;
+        let f1: fn(f64) -> f64 = |x| x;
+        let fs = [f0, f0, f1];
+
+        // falls back to the minimum norm solution instead of panicking
+        let res = general_linear_least_squares(&xs, &ys, &fs);
+        assert_eq!(res, svd_least_squares(&xs, &ys, &fs, None));
+        assert!((res.0[2] - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_svd_least_squares_degenerate() {
+        let xs = vec![0.0, 1.0, 2.0, 3.0];
+        let ys = vec![1.0, 3.0, 5.0, 7.0];
+
+        // the first two basis functions are identical
+        let f0: fn(f64) -> f64 = |_| 1.0;
+        let f1: fn(f64) -> f64 = |_| 1.0;
+        let f2: fn(f64) -> f64 = |x| x;
+
+        let (coeffs, vars) = svd_least_squares(&xs, &ys, &[f0, f1, f2], None);
+
+        assert!((coeffs[0] - 0.5).abs() < 1e-12);
+        assert!((coeffs[1] - 0.5).abs() < 1e-12);
+        assert!((coeffs[2] - 2.0).abs() < 1e-12);
+        assert!(vars.iter().all(|v| v.is_finite()));
+    }
 }
@@ -1,43 +1,684 @@
 //! Minimization of functions
 
-use num::Zero;
+pub mod linprog;
+pub mod quadprog;
 
+use std::fmt::Debug;
+
+use num::{Float, Zero};
+
+use crate::matrix::Matrix;
 use crate::sort::co_sort;
 use crate::vector::Vector;
 
+/// Domain of an objective function
+///
+/// Implemented for floats, whose derivatives are floats again, and for [`Vector`], whose
+/// gradient is a vector and whose Hessian is a [`Matrix`].
+pub trait Domain: Copy + Debug {
+    /// Type of the function values
+    type Scalar: Float + Debug;
+    /// Type of the second derivative
+    type Hessian: Copy + Debug;
+}
+
+impl<T: Float + Debug> Domain for T {
+    type Scalar = T;
+    type Hessian = T;
+}
+
+impl<const N: usize> Domain for Vector<N> {
+    type Scalar = f64;
+    type Hessian = Matrix<N, N>;
+}
+
+/// Function to be minimized
+///
+/// Implemented for closures and functions, which provide values only. Derivatives are added
+/// with [`Differentiable`] and [`TwiceDifferentiable`] or by implementing the trait directly.
+///
+/// # Example
+/// ```
+/// use scialg::optimize::{Differentiable, Objective};
+///
+/// let shift = 2.0;
+/// let f = |x: f64| (x - shift) * (x - shift);
+/// assert_eq!(f.value(3.0), 1.0);
+/// assert_eq!(f.gradient(3.0), None);
+///
+/// let g = Differentiable::new(f, |x: f64| 2.0 * (x - shift));
+/// assert_eq!(g.gradient(3.0), Some(2.0));
+/// ```
+pub trait Objective<X: Domain> {
+    /// Return the value at *x*
+    fn value(&self, x: X) -> X::Scalar;
+
+    /// Return the gradient at *x*, if available
+    fn gradient(&self, _x: X) -> Option<X> {
+        None
+    }
+
+    /// Return the Hessian at *x*, if available
+    fn hessian(&self, _x: X) -> Option<X::Hessian> {
+        None
+    }
+}
+
+impl<X: Domain, F: Fn(X) -> X::Scalar> Objective<X> for F {
+    fn value(&self, x: X) -> X::Scalar {
+        self(x)
+    }
+}
+
+/// Objective with a gradient
+#[derive(Debug, Copy, Clone)]
+pub struct Differentiable<F, G> {
+    value: F,
+    gradient: G,
+}
+
+impl<F, G> Differentiable<F, G> {
+    /// Combine the function *value* with its *gradient*
+    pub fn new(value: F, gradient: G) -> Self {
+        Differentiable { value, gradient }
+    }
+}
+
+impl<X: Domain, F: Fn(X) -> X::Scalar, G: Fn(X) -> X> Objective<X> for Differentiable<F, G> {
+    fn value(&self, x: X) -> X::Scalar {
+        (self.value)(x)
+    }
+
+    fn gradient(&self, x: X) -> Option<X> {
+        Some((self.gradient)(x))
+    }
+}
+
+/// Objective with a gradient and a Hessian
+#[derive(Debug, Copy, Clone)]
+pub struct TwiceDifferentiable<F, G, H> {
+    value: F,
+    gradient: G,
+    hessian: H,
+}
+
+impl<F, G, H> TwiceDifferentiable<F, G, H> {
+    /// Combine the function *value* with its *gradient* and *hessian*
+    pub fn new(value: F, gradient: G, hessian: H) -> Self {
+        TwiceDifferentiable {
+            value,
+            gradient,
+            hessian,
+        }
+    }
+}
+
+impl<X, F, G, H> Objective<X> for TwiceDifferentiable<F, G, H>
+where
+    X: Domain,
+    F: Fn(X) -> X::Scalar,
+    G: Fn(X) -> X,
+    H: Fn(X) -> X::Hessian,
+{
+    fn value(&self, x: X) -> X::Scalar {
+        (self.value)(x)
+    }
+
+    fn gradient(&self, x: X) -> Option<X> {
+        Some((self.gradient)(x))
+    }
+
+    fn hessian(&self, x: X) -> Option<X::Hessian> {
+        Some((self.hessian)(x))
+    }
+}
+
+/// Reason a minimization stopped
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The convergence criteria were met
+    Converged,
+    /// The iteration limit was reached before convergence
+    MaxIterations,
+}
+
+/// Outcome of a minimization
+#[derive(Debug, Clone)]
+pub struct OptimizeResult<X: Domain> {
+    /// Approximate minimizer
+    pub x: X,
+    /// Function value at the minimizer
+    pub value: X::Scalar,
+    /// Number of iterations
+    pub iterations: usize,
+    /// Number of function evaluations
+    pub evaluations: usize,
+    /// Number of gradient evaluations
+    pub gradient_evaluations: usize,
+    /// Number of Hessian evaluations
+    pub hessian_evaluations: usize,
+    /// Reason the minimization stopped
+    pub status: Status,
+    /// Human readable description of the status
+    pub message: String,
+}
+
+impl<X: Domain> OptimizeResult<X> {
+    /// Return whether the minimization converged
+    pub fn converged(&self) -> bool {
+        self.status == Status::Converged
+    }
+}
+
+/// Objective that counts its evaluations
+struct Counted<'a, X: Domain, O: Objective<X>> {
+    f: &'a O,
+    evaluations: usize,
+    gradient_evaluations: usize,
+    hessian_evaluations: usize,
+    _domain: std::marker::PhantomData<X>,
+}
+
+impl<'a, X: Domain, O: Objective<X>> Counted<'a, X, O> {
+    fn new(f: &'a O) -> Self {
+        Counted {
+            f,
+            evaluations: 0,
+            gradient_evaluations: 0,
+            hessian_evaluations: 0,
+            _domain: std::marker::PhantomData,
+        }
+    }
+
+    fn value(&mut self, x: X) -> X::Scalar {
+        self.evaluations += 1;
+        self.f.value(x)
+    }
+
+    fn gradient(&mut self, x: X) -> Option<X> {
+        self.gradient_evaluations += 1;
+        self.f.gradient(x)
+    }
+
+    /// Assemble the result at *x* with function value *value*
+    fn finish(
+        self,
+        x: X,
+        value: X::Scalar,
+        iterations: usize,
+        status: Status,
+        message: &str,
+    ) -> OptimizeResult<X> {
+        OptimizeResult {
+            x,
+            value,
+            iterations,
+            evaluations: self.evaluations,
+            gradient_evaluations: self.gradient_evaluations,
+            hessian_evaluations: self.hessian_evaluations,
+            status,
+            message: message.to_string(),
+        }
+    }
+}
+
 /// Find a local minimum of *f* in *[a, c]* using the Golden-section search
 ///
 /// # Example
 /// ```
 /// use scialg::optimize::golden_section;
 ///
-/// let min = golden_section(f32::sin, 3.0, 6.0, 1e-4);
+/// let res = golden_section(f32::sin, 3.0, 6.0, 1e-4);
+///
+/// assert!(res.converged());
+/// assert!((res.x - 1.5 * std::f32::consts::PI).abs() < 5e-4);
+/// assert!((res.value + 1.0).abs() < 1e-6);
 ///
-/// assert!((min - 1.5 * std::f32::consts::PI).abs() < 5e-4);
+/// // closures may capture their environment
+/// let center = 0.5;
+/// let res = golden_section(|x: f64| (x - center).powi(2), 0.0, 2.0, 1e-8);
+///
+/// assert!((res.x - center).abs() < 1e-8);
 /// ```
 ///
 /// # References
 ///  - [Wikipedia: Golden-section search](https://en.wikipedia.org/wiki/Golden-section_search)
-pub fn golden_section(f: fn(f32) -> f32, a: f32, c: f32, tol: f32) -> f32 {
-    let phi = 1.618033988749894848204586834365638118_f32;
+pub fn golden_section<T: Float + Debug, O: Objective<T>>(
+    f: O,
+    a: T,
+    c: T,
+    tol: T,
+) -> OptimizeResult<T> {
+    let two = T::one() + T::one();
+    let phi = (T::one() + T::from(5).unwrap().sqrt()) / two;
+    let mut f = Counted::new(&f);
 
     let mut a = a;
     let mut b = c;
+    let mut iterations = 0;
 
     while (b - a).abs() > tol {
         let c = b - (b - a) / phi;
         let d = a + (b - a) / phi;
-        if f(c) < f(d) {
+        if f.value(c) < f.value(d) {
             b = d;
         } else {
             a = c;
         }
+        iterations += 1;
+    }
+
+    let x = (b + a) / two;
+    let value = f.value(x);
+    f.finish(
+        x,
+        value,
+        iterations,
+        Status::Converged,
+        "interval is smaller than the tolerance",
+    )
+}
+
+/// Triple *a < b < c* with *f(b) < f(a)* and *f(b) < f(c)*, which encloses a local minimum
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bracket<T> {
+    /// Left end
+    pub a: T,
+    /// Interior point
+    pub b: T,
+    /// Right end
+    pub c: T,
+    /// Function value at *a*
+    pub fa: T,
+    /// Function value at *b*
+    pub fb: T,
+    /// Function value at *c*
+    pub fc: T,
+}
+
+/// Find a [`Bracket`] of a local minimum of *f* by walking downhill from the points *a* and *b*
+///
+/// The steps grow by the golden ratio and are accelerated by parabolic extrapolation. Returns
+/// `None` if no bracket is found within *max_iter* steps, for example because *f* decreases
+/// monotonically.
+///
+/// # Example
+/// ```
+/// use scialg::optimize::bracket_minimum;
+///
+/// let f = |x: f64| (x - 10.0).powi(2);
+/// let br = bracket_minimum(f, 0.0, 1.0, 50).unwrap();
+///
+/// assert!(br.a < 10.0 && 10.0 < br.c);
+/// assert!(br.fb < br.fa && br.fb < br.fc);
+///
+/// assert!(bracket_minimum(f64::exp, 0.0, 1.0, 50).is_none());
+/// ```
+///
+/// # References
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 10.1
+pub fn bracket_minimum<T: Float + Debug, O: Objective<T>>(
+    f: O,
+    a: T,
+    b: T,
+    max_iter: usize,
+) -> Option<Bracket<T>> {
+    let gold = (T::one() + T::from(5).unwrap().sqrt()) / T::from(2).unwrap();
+    let limit = T::from(100).unwrap();
+    let tiny = T::min_positive_value().sqrt();
+    let two = T::one() + T::one();
+
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f.value(a), f.value(b));
+    if fb > fa {
+        std::mem::swap(&mut a, &mut b);
+        std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut c = b + gold * (b - a);
+    let mut fc = f.value(c);
+
+    let mut iterations = 0;
+    while fb >= fc {
+        if iterations == max_iter || !fc.is_finite() {
+            return None;
+        }
+        iterations += 1;
+
+        // extremum of the parabola through a, b and c
+        let r = (b - a) * (fb - fc);
+        let q = (b - c) * (fb - fa);
+        let denom = (q - r).abs().max(tiny);
+        let denom = if q - r < T::zero() { -denom } else { denom };
+        let mut u = b - ((b - c) * q - (b - a) * r) / (two * denom);
+        let u_limit = b + limit * (c - b);
+        let mut fu;
+
+        if (b - u) * (u - c) > T::zero() {
+            // between b and c
+            fu = f.value(u);
+            if fu < fc {
+                (a, b, fa, fb) = (b, u, fb, fu);
+                break;
+            } else if fu > fb {
+                (c, fc) = (u, fu);
+                break;
+            }
+            u = c + gold * (c - b);
+            fu = f.value(u);
+        } else if (c - u) * (u - u_limit) > T::zero() {
+            // between c and the limit
+            fu = f.value(u);
+            if fu < fc {
+                (b, c, fb, fc) = (c, u, fc, fu);
+                u = c + gold * (c - b);
+                fu = f.value(u);
+            }
+        } else if (u - u_limit) * (u_limit - c) >= T::zero() {
+            u = u_limit;
+            fu = f.value(u);
+        } else {
+            u = c + gold * (c - b);
+            fu = f.value(u);
+        }
+        (a, b, c, fa, fb, fc) = (b, c, u, fb, fc, fu);
+    }
+
+    if a > c {
+        std::mem::swap(&mut a, &mut c);
+        std::mem::swap(&mut fa, &mut fc);
+    }
+    (fb < fa && fb < fc).then_some(Bracket {
+        a,
+        b,
+        c,
+        fa,
+        fb,
+        fc,
+    })
+}
+
+/// Return *magnitude* with the sign of *sign*
+fn with_sign<T: Float>(magnitude: T, sign: T) -> T {
+    if sign >= T::zero() {
+        magnitude.abs()
+    } else {
+        -magnitude.abs()
+    }
+}
+
+/// Find a local minimum of *f* in *[a, c]* using Brent's method
+///
+/// Combines parabolic interpolation through the three best points with golden-section steps,
+/// which guarantee progress, and needs a single evaluation per iteration. The minimizer is
+/// located to within *tol* plus a relative tolerance of the square root of the machine
+/// epsilon.
+///
+/// # Example
+/// ```
+/// use scialg::optimize::{bracket_minimum, brent};
+///
+/// let f = |x: f64| x.cos() + 0.1 * x;
+/// let br = bracket_minimum(f, 0.0, 1.0, 50).unwrap();
+/// let res = brent(f, br.a, br.c, 1e-10, 100);
+///
+/// // the minimum satisfies sin(x) = 0.1
+/// assert!(res.converged());
+/// assert!((res.x - (std::f64::consts::PI - 0.1_f64.asin())).abs() < 1e-7);
+/// assert!(res.evaluations < 20);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Brent's method](https://en.wikipedia.org/wiki/Brent%27s_method)
+///  - R. P. Brent, *Algorithms for Minimization without Derivatives*, chapter 5, 1973
+pub fn brent<T: Float + Debug, O: Objective<T>>(
+    f: O,
+    a: T,
+    c: T,
+    tol: T,
+    max_iter: usize,
+) -> OptimizeResult<T> {
+    let two = T::one() + T::one();
+    let half = T::one() / two;
+    let golden = (T::from(3).unwrap() - T::from(5).unwrap().sqrt()) / two;
+    let rel = T::epsilon().sqrt();
+    let mut f = Counted::new(&f);
+
+    let (mut a, mut b) = if a < c { (a, c) } else { (c, a) };
+    let mut x = a + golden * (b - a);
+    let (mut w, mut v) = (x, x);
+    let mut fx = f.value(x);
+    let (mut fw, mut fv) = (fx, fx);
+    // current and previous step
+    let (mut d, mut e) = (T::zero(), T::zero());
+
+    for iteration in 0..max_iter {
+        let m = half * (a + b);
+        let tol1 = rel * x.abs() + tol / T::from(3).unwrap();
+        let tol2 = two * tol1;
+        if (x - m).abs() <= tol2 - half * (b - a) {
+            return f.finish(
+                x,
+                fx,
+                iteration,
+                Status::Converged,
+                "interval is smaller than the tolerance",
+            );
+        }
+
+        let mut golden_step = true;
+        if e.abs() > tol1 {
+            // parabola through x, w and v
+            let r = (x - w) * (fx - fv);
+            let q = (x - v) * (fx - fw);
+            let mut p = (x - v) * q - (x - w) * r;
+            let mut q = two * (q - r);
+            if q > T::zero() {
+                p = -p;
+            } else {
+                q = -q;
+            }
+            // accept the parabolic step if it is inside the interval and smaller than half of
+            // the step before last
+            if p.abs() < (half * q * e).abs() && p > q * (a - x) && p < q * (b - x) {
+                e = d;
+                d = p / q;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = with_sign(tol1, m - x);
+                }
+                golden_step = false;
+            }
+        }
+        if golden_step {
+            e = if x >= m { a - x } else { b - x };
+            d = golden * e;
+        }
+
+        let u = if d.abs() >= tol1 {
+            x + d
+        } else {
+            x + with_sign(tol1, d)
+        };
+        let fu = f.value(u);
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            (v, fv, w, fw, x, fx) = (w, fw, x, fx, u, fu);
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                (v, fv, w, fw) = (w, fw, u, fu);
+            } else if fu <= fv || v == x || v == w {
+                (v, fv) = (u, fu);
+            }
+        }
     }
 
-    (b + a) / 2.0
+    f.finish(
+        x,
+        fx,
+        max_iter,
+        Status::MaxIterations,
+        "maximum number of iterations reached",
+    )
 }
 
-/// Find a local minima of *f* using the Nelder-Mead algorithm starting at *p0*
+/// Find a local minimum of *f* in *[a, c]* using Brent's method with derivatives
+///
+/// The sign of the derivative decides which part of the interval to keep, and secant steps on
+/// the derivative replace the parabolic interpolation of [`brent`]. Bisection steps guarantee
+/// progress.
+///
+/// # Panics
+/// Panics if *f* provides no gradient
+///
+/// # Example
+/// ```
+/// use scialg::optimize::{brent_derivative, Differentiable};
+///
+/// let f = Differentiable::new(|x: f64| x.cos() + 0.1 * x, |x: f64| 0.1 - x.sin());
+/// let res = brent_derivative(f, 2.0, 4.0, 1e-10, 100);
+///
+/// assert!(res.converged());
+/// assert!((res.x - (std::f64::consts::PI - 0.1_f64.asin())).abs() < 1e-7);
+/// assert!(res.evaluations < 10 && res.gradient_evaluations <= res.evaluations);
+/// ```
+///
+/// # References
+///  - W. H. Press et al., *Numerical Recipes*, 3rd ed., section 10.4
+pub fn brent_derivative<T: Float + Debug, O: Objective<T>>(
+    f: O,
+    a: T,
+    c: T,
+    tol: T,
+    max_iter: usize,
+) -> OptimizeResult<T> {
+    let two = T::one() + T::one();
+    let half = T::one() / two;
+    let golden = (T::from(3).unwrap() - T::from(5).unwrap().sqrt()) / two;
+    let rel = T::epsilon().sqrt();
+    let mut f = Counted::new(&f);
+    let df = |f: &mut Counted<T, O>, x| f.gradient(x).expect("objective has no gradient");
+
+    let (mut a, mut b) = if a < c { (a, c) } else { (c, a) };
+    let mut x = a + golden * (b - a);
+    let (mut w, mut v) = (x, x);
+    let mut fx = f.value(x);
+    let mut dx = df(&mut f, x);
+    let (mut fw, mut fv, mut dw, mut dv) = (fx, fx, dx, dx);
+    // current and previous step
+    let (mut d, mut e) = (T::zero(), T::zero());
+
+    for iteration in 0..max_iter {
+        let m = half * (a + b);
+        let tol1 = rel * x.abs() + tol / T::from(3).unwrap();
+        let tol2 = two * tol1;
+        if (x - m).abs() <= tol2 - half * (b - a) {
+            return f.finish(
+                x,
+                fx,
+                iteration,
+                Status::Converged,
+                "interval is smaller than the tolerance",
+            );
+        }
+
+        let mut bisect = true;
+        if e.abs() > tol1 {
+            // secant steps through the derivatives at w and v, which have to stay inside the
+            // interval and go downhill
+            let d1 = if dw != dx {
+                (w - x) * dx / (dx - dw)
+            } else {
+                two * (b - a)
+            };
+            let d2 = if dv != dx {
+                (v - x) * dx / (dx - dv)
+            } else {
+                two * (b - a)
+            };
+            let ok1 = (a - (x + d1)) * ((x + d1) - b) > T::zero() && dx * d1 <= T::zero();
+            let ok2 = (a - (x + d2)) * ((x + d2) - b) > T::zero() && dx * d2 <= T::zero();
+            let step = match (ok1, ok2) {
+                (true, true) if d1.abs() < d2.abs() => Some(d1),
+                (true, true) | (false, true) => Some(d2),
+                (true, false) => Some(d1),
+                (false, false) => None,
+            };
+            if let Some(step) = step.filter(|step| step.abs() <= (half * e).abs()) {
+                e = d;
+                d = step;
+                let u = x + d;
+                if u - a < tol2 || b - u < tol2 {
+                    d = with_sign(tol1, m - x);
+                }
+                bisect = false;
+            }
+        }
+        if bisect {
+            e = if dx >= T::zero() { a - x } else { b - x };
+            d = half * e;
+        }
+
+        let (u, fu) = if d.abs() >= tol1 {
+            let u = x + d;
+            (u, f.value(u))
+        } else {
+            let u = x + with_sign(tol1, d);
+            let fu = f.value(u);
+            // the minimum step goes uphill, so x is within the tolerance of the minimizer
+            if fu > fx {
+                return f.finish(
+                    x,
+                    fx,
+                    iteration + 1,
+                    Status::Converged,
+                    "step is smaller than the tolerance",
+                );
+            }
+            (u, fu)
+        };
+        let du = df(&mut f, u);
+        if fu <= fx {
+            if u >= x {
+                a = x;
+            } else {
+                b = x;
+            }
+            (v, fv, dv, w, fw, dw, x, fx, dx) = (w, fw, dw, x, fx, dx, u, fu, du);
+        } else {
+            if u < x {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x {
+                (v, fv, dv, w, fw, dw) = (w, fw, dw, u, fu, du);
+            } else if fu < fv || v == x || v == w {
+                (v, fv, dv) = (u, fu, du);
+            }
+        }
+    }
+
+    f.finish(
+        x,
+        fx,
+        max_iter,
+        Status::MaxIterations,
+        "maximum number of iterations reached",
+    )
+}
+
+/// Find a local minimum of *f* using the Nelder-Mead algorithm starting at *p0*
+///
+/// Uses the default settings of [`NelderMead`] with at most *max_iter* iterations.
 ///
 /// # Example
 /// ```
@@ -47,92 +688,350 @@ pub fn golden_section(f: fn(f32) -> f32, a: f32, c: f32, tol: f32) -> f32 {
 /// use scialg::optimize::nelder_mead;
 ///
 /// let func = |v: Vector<2>| v.coeff[0] * v.coeff[0] + v.coeff[1] * v.coeff[1];
-/// let minima = nelder_mead(func, &Vector::new(&[3.0, 5.0]), 100);
+/// let res = nelder_mead(func, &Vector::new(&[3.0, 5.0]), 100);
 ///
-/// assert!((minima - Vector::zero()).length() < 1e-5);
+/// assert!((res.x - Vector::zero()).length() < 1e-5);
+/// assert!(res.value < 1e-10);
+/// ```
+pub fn nelder_mead<const N: usize, O: Objective<Vector<N>>>(
+    f: O,
+    p0: &Vector<N>,
+    max_iter: usize,
+) -> OptimizeResult<Vector<N>> {
+    NelderMead::new().max_iter(max_iter).minimize(f, p0)
+}
+
+/// Nelder-Mead simplex minimizer
+///
+/// The initial simplex consists of *p0* and the points *p0 + step_i e_i*. The iteration stops
+/// when the function values of all vertices are within the function tolerance of the best one
+/// and all vertices are within the simplex tolerance of the best one in the maximum norm.
+///
+/// Box bounds are enforced by substituting `x = l + (u - l) (sin y + 1) / 2` for coordinates
+/// with two finite bounds and `x = l + y^2` or `x = u - y^2` for coordinates with one, so that
+/// the unconstrained iteration in *y* never leaves the box.
+///
+/// # Example
+/// ```
+/// use scialg::vector::Vector;
+/// use scialg::optimize::NelderMead;
+///
+/// let rosenbrock = |v: Vector<4>| {
+///     (0..3)
+///         .map(|i| {
+///             let (x, y) = (v.coeff[i], v.coeff[i + 1]);
+///             100.0 * (y - x * x).powi(2) + (1.0 - x).powi(2)
+///         })
+///         .sum::<f64>()
+/// };
+/// let res = NelderMead::new()
+///     .adaptive(true)
+///     .tolerances(1e-10, 1e-10)
+///     .max_iter(10_000)
+///     .restarts(2)
+///     .minimize(rosenbrock, &Vector::new(&[-1.0, 1.0, -1.0, 1.0]));
+///
+/// assert!(res.converged());
+/// assert!((res.x - Vector::new(&[1.0; 4])).length() < 1e-6);
+///
+/// // the unconstrained minimum (2, -1) lies outside of the box
+/// let res = NelderMead::new()
+///     .step(Vector::new(&[0.5, 0.5]))
+///     .bounds(Vector::new(&[0.0, 0.0]), Vector::new(&[1.0, f64::INFINITY]))
+///     .minimize(|v: Vector<2>| (v.coeff[0] - 2.0).powi(2) + (v.coeff[1] + 1.0).powi(2), &Vector::new(&[0.5, 0.5]));
+///
+/// assert!(res.converged());
+/// assert!((res.x - Vector::new(&[1.0, 0.0])).length() < 1e-4);
 /// ```
 ///
 /// # References
 ///  - [Wikipedia: Nelder-Mead method](https://en.wikipedia.org/wiki/Nelder%E2%80%93Mead_method)
-///  - [Wikipedia: Centroid](https://en.wikipedia.org/wiki/Centroid)
-pub fn nelder_mead<const N: usize>(
-    f: fn(Vector<N>) -> f64,
-    p0: &Vector<N>,
+///  - F. Gao and L. Han, *Implementing the Nelder-Mead simplex algorithm with adaptive
+///    parameters*, Computational Optimization and Applications 51, 2012
+#[derive(Debug, Copy, Clone)]
+pub struct NelderMead<const N: usize> {
+    step: Vector<N>,
+    x_tol: f64,
+    f_tol: f64,
     max_iter: usize,
-) -> Vector<N> {
-    let alpha = 1.0;
-    let gamma = 2.0;
-    let rho = 0.5;
-    let sigma = 0.5;
-
-    // starting simplex
-    let mut xs: Vec<Vector<N>> = vec![*p0];
-    for i in 0..N {
-        let mut coeffs = p0.coeff;
-        coeffs[i] += 1.0;
-        xs.push(Vector { coeff: coeffs });
-    }
-
-    for _ in 0..max_iter {
-        let mut fs: Vec<f64> = xs.iter().map(|x| f(*x)).collect();
-        co_sort(&mut fs, &mut xs);
-
-        // compute centoid
-        let mut x0 = Vector::zero();
-        for i in 0..N {
-            x0 = x0 + xs[i];
-        }
-        x0 = x0 / N as f64;
+    adaptive: bool,
+    restarts: usize,
+    lower: Vector<N>,
+    upper: Vector<N>,
+}
 
-        // reflect
-        let xr = x0 + (x0 - xs[N]) * alpha;
-        let fr = f(xr);
-        if fs[0] <= fr && fr <= fs[N - 1] {
-            xs[N] = xr;
-            continue;
+impl<const N: usize> Default for NelderMead<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> NelderMead<N> {
+    /// Create a minimizer with unit steps, tolerances of 1e-8, at most *200 N* iterations,
+    /// the standard parameters, no restarts and no bounds
+    pub fn new() -> Self {
+        NelderMead {
+            step: Vector { coeff: [1.0; N] },
+            x_tol: 1e-8,
+            f_tol: 1e-8,
+            max_iter: 200 * N,
+            adaptive: false,
+            restarts: 0,
+            lower: Vector {
+                coeff: [f64::NEG_INFINITY; N],
+            },
+            upper: Vector {
+                coeff: [f64::INFINITY; N],
+            },
         }
+    }
 
-        // expand
-        if fr < fs[0] {
-            let xe = x0 + (xr - x0) * gamma;
-            let fe = f(xe);
-            if fe < fr {
-                xs[N] = xe;
-            } else {
-                xs[N] = xr;
+    /// Set the step sizes along the coordinate axes of the initial simplex
+    ///
+    /// # Panics
+    /// Panics if a step is zero
+    pub fn step(mut self, step: Vector<N>) -> Self {
+        assert!(step.coeff.iter().all(|s| *s != 0.0));
+        self.step = step;
+        self
+    }
+
+    /// Set the absolute tolerances of the simplex size and the function values
+    pub fn tolerances(mut self, x_tol: f64, f_tol: f64) -> Self {
+        self.x_tol = x_tol;
+        self.f_tol = f_tol;
+        self
+    }
+
+    /// Set the maximum number of iterations, including all restarts
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Use the dimension dependent parameters of Gao and Han, which avoid the degeneration of
+    /// the simplex in high dimensions
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Restart with a fresh simplex around the minimizer up to *restarts* times, as long as a
+    /// restart improves the minimum by more than the function tolerance
+    pub fn restarts(mut self, restarts: usize) -> Self {
+        self.restarts = restarts;
+        self
+    }
+
+    /// Restrict the search to the box *[lower, upper]*, whose bounds may be infinite
+    ///
+    /// # Panics
+    /// Panics if a lower bound exceeds its upper bound
+    pub fn bounds(mut self, lower: Vector<N>, upper: Vector<N>) -> Self {
+        assert!((0..N).all(|k| lower.coeff[k] <= upper.coeff[k]));
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    /// Find a local minimum of *f* starting at *p0*, which is moved into the bounds
+    pub fn minimize<O: Objective<Vector<N>>>(
+        &self,
+        f: O,
+        p0: &Vector<N>,
+    ) -> OptimizeResult<Vector<N>> {
+        let n = N as f64;
+        let (alpha, gamma, rho, sigma) = if self.adaptive {
+            (1.0, 1.0 + 2.0 / n, 0.75 - 0.5 / n, 1.0 - 1.0 / n)
+        } else {
+            (1.0, 2.0, 0.5, 0.5)
+        };
+        let mut f = Counted::new(&f);
+        let mut iterations = 0;
+        let mut start = self.clamp(*p0);
+        let mut best: Option<(Vector<N>, f64)> = None;
+        let mut status = Status::MaxIterations;
+
+        for _ in 0..=self.restarts {
+            let mut ys = self.initial_simplex(start);
+            let mut fs: Vec<f64> = ys.iter().map(|y| f.value(self.constrain(*y))).collect();
+            co_sort(&mut fs, &mut ys);
+
+            status = Status::MaxIterations;
+            while iterations < self.max_iter {
+                if self.converged(&ys, &fs) {
+                    status = Status::Converged;
+                    break;
+                }
+                iterations += 1;
+
+                // centroid of all but the worst vertex
+                let x0 = ys[..N].iter().fold(Vector::zero(), |acc, y| acc + *y) / n;
+
+                // reflect
+                let yr = x0 + (x0 - ys[N]) * alpha;
+                let fr = f.value(self.constrain(yr));
+                if fs[0] <= fr && fr < fs[N - 1] {
+                    (ys[N], fs[N]) = (yr, fr);
+                } else if fr < fs[0] {
+                    // expand
+                    let ye = x0 + (yr - x0) * gamma;
+                    let fe = f.value(self.constrain(ye));
+                    (ys[N], fs[N]) = if fe < fr { (ye, fe) } else { (yr, fr) };
+                } else {
+                    // contract outside or inside
+                    let (yc, reference) = if fr < fs[N] {
+                        (x0 + (yr - x0) * rho, fr)
+                    } else {
+                        (x0 + (ys[N] - x0) * rho, fs[N])
+                    };
+                    let fc = f.value(self.constrain(yc));
+                    if fc < reference {
+                        (ys[N], fs[N]) = (yc, fc);
+                    } else {
+                        // shrink
+                        for i in 1..=N {
+                            ys[i] = ys[0] + (ys[i] - ys[0]) * sigma;
+                            fs[i] = f.value(self.constrain(ys[i]));
+                        }
+                    }
+                }
+                co_sort(&mut fs, &mut ys);
             }
-            continue;
-        }
 
-        // contract
-        if fr < fs[N] {
-            // contract outside
-            let xc = x0 + (xr - x0) * rho;
-            let fc = f(xc);
-            if fc < fr {
-                xs[N] = xc;
-                continue;
+            let (x, fx) = (self.constrain(ys[0]), fs[0]);
+            let improved = best.is_none_or(|(_, fb)| fb - fx > self.f_tol);
+            if best.is_none_or(|(_, fb)| fx < fb) {
+                best = Some((x, fx));
             }
-        } else {
-            // contract inside
-            let xc = x0 + (xs[N] - x0) * rho;
-            let fc = f(xc);
-            if fc < fs[N] {
-                xs[N] = xc;
-                continue;
+            if !improved || status != Status::Converged {
+                break;
             }
+            start = x;
         }
 
-        // shrink
-        for i in 0..xs.len() {
-            xs[i] = xs[0] + (xs[i] - xs[0]) * sigma;
+        let (x, value) = best.unwrap();
+        let message = match status {
+            Status::Converged => "simplex is smaller than the tolerances",
+            Status::MaxIterations => "maximum number of iterations reached",
+        };
+        f.finish(x, value, iterations, status, message)
+    }
+
+    fn clamp(&self, x: Vector<N>) -> Vector<N> {
+        let mut coeff = x.coeff;
+        for (k, c) in coeff.iter_mut().enumerate() {
+            *c = c.max(self.lower.coeff[k]).min(self.upper.coeff[k]);
         }
+        Vector { coeff }
     }
 
-    // compute centoid
-    let mut x0 = Vector::zero();
-    for i in 0..N {
-        x0 = x0 + xs[i];
+    /// Map unconstrained coordinates into the box
+    fn constrain(&self, y: Vector<N>) -> Vector<N> {
+        let mut coeff = y.coeff;
+        for (k, c) in coeff.iter_mut().enumerate() {
+            let (l, u) = (self.lower.coeff[k], self.upper.coeff[k]);
+            *c = match (l.is_finite(), u.is_finite()) {
+                (true, true) => l + (u - l) * (c.sin() + 1.0) / 2.0,
+                (true, false) => l + *c * *c,
+                (false, true) => u - *c * *c,
+                (false, false) => *c,
+            };
+        }
+        Vector { coeff }
+    }
+
+    /// Map a point of the box to unconstrained coordinates
+    fn unconstrain(&self, x: Vector<N>) -> Vector<N> {
+        let mut coeff = x.coeff;
+        for (k, c) in coeff.iter_mut().enumerate() {
+            let (l, u) = (self.lower.coeff[k], self.upper.coeff[k]);
+            *c = match (l.is_finite(), u.is_finite()) {
+                (true, true) if u > l => (2.0 * (*c - l) / (u - l) - 1.0).clamp(-1.0, 1.0).asin(),
+                (true, true) => 0.0,
+                (true, false) => (*c - l).max(0.0).sqrt(),
+                (false, true) => (u - *c).max(0.0).sqrt(),
+                (false, false) => *c,
+            };
+        }
+        Vector { coeff }
+    }
+
+    /// Return the vertices of the initial simplex around *p* in unconstrained coordinates
+    fn initial_simplex(&self, p: Vector<N>) -> Vec<Vector<N>> {
+        let y0 = self.unconstrain(p);
+        let mut ys = vec![y0];
+        for i in 0..N {
+            let (l, u) = (self.lower.coeff[i], self.upper.coeff[i]);
+            let (pi, step) = (p.coeff[i], self.step.coeff[i]);
+            let mut coeff = p.coeff;
+            coeff[i] = if (l..=u).contains(&(pi + step)) {
+                pi + step
+            } else if (l..=u).contains(&(pi - step)) {
+                pi - step
+            } else {
+                // the step is wider than the box, so go halfway to the far bound instead of
+                // clamping the vertex back onto p
+                if u - pi > pi - l {
+                    pi + (u - pi) / 2.0
+                } else {
+                    pi - (pi - l) / 2.0
+                }
+            };
+            let mut y = self.unconstrain(Vector { coeff });
+            // a coordinate fixed by l = u still needs an extent in y
+            if y.coeff[i] == y0.coeff[i] {
+                y.coeff[i] += 1.0;
+            }
+            ys.push(y);
+        }
+        ys
+    }
+
+    /// Check the convergence of the simplex *ys* with sorted function values *fs*
+    fn converged(&self, ys: &[Vector<N>], fs: &[f64]) -> bool {
+        let x0 = self.constrain(ys[0]);
+        fs.iter().all(|fi| (fi - fs[0]).abs() <= self.f_tol)
+            && ys.iter().all(|y| {
+                let x = self.constrain(*y);
+                (0..N).all(|k| (x.coeff[k] - x0.coeff[k]).abs() <= self.x_tol)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nelder_mead_start_on_bound() {
+        // the default unit steps are wider than the box and the start is on a corner
+        let f = |v: Vector<2>| (v.coeff[0] - 0.3).powi(2) + (v.coeff[1] - 0.2).powi(2);
+        let res = NelderMead::new()
+            .bounds(Vector::new(&[0.0, 0.0]), Vector::new(&[0.5, 0.5]))
+            .minimize(f, &Vector::new(&[0.0, 0.0]));
+
+        assert!(res.converged());
+        assert!((res.x - Vector::new(&[0.3, 0.2])).length() < 1e-4);
+
+        // start on the upper bound of a narrow box and on a one-sided bound
+        let f = |v: Vector<2>| (v.coeff[0] - 1.005).powi(2) + (v.coeff[1] - 2.0).powi(2);
+        let res = NelderMead::new()
+            .bounds(
+                Vector::new(&[1.0, 0.0]),
+                Vector::new(&[1.01, f64::INFINITY]),
+            )
+            .minimize(f, &Vector::new(&[1.01, 0.0]));
+
+        assert!(res.converged());
+        assert!((res.x - Vector::new(&[1.005, 2.0])).length() < 1e-4);
+
+        // a coordinate fixed by equal bounds
+        let res = NelderMead::new()
+            .bounds(Vector::new(&[0.3, 0.0]), Vector::new(&[0.3, 0.5]))
+            .minimize(f, &Vector::new(&[0.3, 0.5]));
+
+        assert!(res.converged());
+        assert!((res.x - Vector::new(&[0.3, 0.5])).length() < 1e-4);
     }
-    x0 / N as f64
 }
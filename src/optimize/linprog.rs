@@ -0,0 +1,531 @@
+//! Linear programming
+//!
+//! Solves `min c^T x` subject to `A_ub x <= b_ub`, `A_eq x = b_eq` and `l <= x <= u` with the
+//! two-phase revised simplex method. Bland's rule selects the entering and leaving variables,
+//! which rules out cycling on degenerate problems.
+
+use ndarray::prelude::*;
+
+use crate::linalg::invert;
+
+/// Tolerance for reduced costs, pivots and feasibility
+const TOL: f64 = 1e-9;
+/// Number of basis updates after which the basis inverse is recomputed from scratch
+const REFACTOR: usize = 50;
+
+/// Termination status of [`LinearProgram::solve`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LinprogStatus {
+    /// An optimal solution was found
+    Optimal,
+    /// No point satisfies the constraints
+    Infeasible,
+    /// The objective is unbounded below on the feasible set
+    Unbounded,
+    /// The iteration limit was reached
+    IterationLimit,
+}
+
+/// Solution of a [`LinearProgram`]
+///
+/// The point, objective and dual values are only meaningful if the status is
+/// [`LinprogStatus::Optimal`].
+#[derive(Debug, Clone)]
+pub struct LinprogResult {
+    /// Termination status
+    pub status: LinprogStatus,
+    /// Minimizer
+    pub x: Array1<f64>,
+    /// Objective value `c^T x`
+    pub objective: f64,
+    /// Dual values of the inequality constraints, the sensitivities `d objective / d b_ub`,
+    /// which are non-positive
+    pub ineq_duals: Array1<f64>,
+    /// Dual values of the equality constraints, the sensitivities `d objective / d b_eq`
+    pub eq_duals: Array1<f64>,
+    /// Reduced costs `c - A_ub^T y_ub - A_eq^T y_eq`, the dual values of the bounds, which are
+    /// non-negative at active lower bounds and non-positive at active upper bounds
+    pub reduced_costs: Array1<f64>,
+    /// Number of simplex iterations of both phases
+    pub iterations: usize,
+}
+
+/// Linear program `min c^T x` subject to `A_ub x <= b_ub`, `A_eq x = b_eq` and `l <= x <= u`
+///
+/// The variables are non-negative unless other bounds are given, and bounds may be infinite.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::optimize::linprog::{LinearProgram, LinprogStatus};
+///
+/// // maximize 3x + 5y subject to x <= 4, 2y <= 12, 3x + 2y <= 18
+/// let res = LinearProgram::new(array![-3.0, -5.0])
+///     .inequalities(array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]], array![4.0, 12.0, 18.0])
+///     .solve();
+///
+/// assert_eq!(res.status, LinprogStatus::Optimal);
+/// assert!((&res.x - &array![2.0, 6.0]).iter().all(|e| e.abs() < 1e-9));
+/// assert!((res.objective + 36.0).abs() < 1e-9);
+/// // relaxing the last constraint by one improves the objective by one
+/// assert!((res.ineq_duals[2] + 1.0).abs() < 1e-9);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Simplex algorithm](https://en.wikipedia.org/wiki/Simplex_algorithm)
+///  - [Wikipedia: Revised simplex method](https://en.wikipedia.org/wiki/Revised_simplex_method)
+///  - [Wikipedia: Bland's rule](https://en.wikipedia.org/wiki/Bland%27s_rule)
+#[derive(Debug, Clone)]
+pub struct LinearProgram {
+    c: Array1<f64>,
+    a_ub: Array2<f64>,
+    b_ub: Array1<f64>,
+    a_eq: Array2<f64>,
+    b_eq: Array1<f64>,
+    bounds: Vec<(f64, f64)>,
+    max_iter: usize,
+}
+
+/// Representation of an original variable by non-negative standard form variables
+#[derive(Debug, Copy, Clone)]
+enum Substitution {
+    /// `x = lower + x'`
+    Shifted(f64, usize),
+    /// `x = upper - x'`
+    Mirrored(f64, usize),
+    /// `x = x+ - x-`
+    Free(usize, usize),
+}
+
+impl LinearProgram {
+    /// Create the problem of minimizing `c^T x` over `x >= 0`
+    pub fn new(c: Array1<f64>) -> Self {
+        let n = c.len();
+        LinearProgram {
+            c,
+            a_ub: Array2::zeros((0, n)),
+            b_ub: Array1::zeros(0),
+            a_eq: Array2::zeros((0, n)),
+            b_eq: Array1::zeros(0),
+            bounds: vec![(0.0, f64::INFINITY); n],
+            max_iter: 10_000,
+        }
+    }
+
+    /// Set the inequality constraints `a x <= b`
+    ///
+    /// # Panics
+    /// Panics if the dimensions of *a* and *b* do not match the number of variables
+    pub fn inequalities(mut self, a: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(a.ncols(), self.c.len());
+        assert_eq!(a.nrows(), b.len());
+        self.a_ub = a;
+        self.b_ub = b;
+        self
+    }
+
+    /// Set the equality constraints `a x = b`
+    ///
+    /// # Panics
+    /// Panics if the dimensions of *a* and *b* do not match the number of variables
+    pub fn equalities(mut self, a: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(a.ncols(), self.c.len());
+        assert_eq!(a.nrows(), b.len());
+        self.a_eq = a;
+        self.b_eq = b;
+        self
+    }
+
+    /// Set the bounds `lower <= x_i <= upper` of every variable, which may be infinite
+    ///
+    /// # Panics
+    /// Panics if the number of bounds does not match the number of variables or a lower bound
+    /// exceeds its upper bound
+    pub fn bounds(mut self, bounds: Vec<(f64, f64)>) -> Self {
+        assert_eq!(bounds.len(), self.c.len());
+        assert!(bounds.iter().all(|(l, u)| l <= u));
+        self.bounds = bounds;
+        self
+    }
+
+    /// Set the maximum number of simplex iterations, 10000 by default
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Solve the problem with the two-phase revised simplex method
+    pub fn solve(&self) -> LinprogResult {
+        let n = self.c.len();
+        let m_ub = self.b_ub.len();
+        let m_eq = self.b_eq.len();
+
+        // substitute non-negative variables for the bounded ones
+        let mut subs = Vec::with_capacity(n);
+        let mut cols = 0;
+        let mut upper_rows = Vec::new();
+        for (k, (l, u)) in self.bounds.iter().enumerate() {
+            if l.is_finite() {
+                subs.push(Substitution::Shifted(*l, cols));
+                if u.is_finite() {
+                    upper_rows.push((k, u - l));
+                }
+                cols += 1;
+            } else if u.is_finite() {
+                subs.push(Substitution::Mirrored(*u, cols));
+                cols += 1;
+            } else {
+                subs.push(Substitution::Free(cols, cols + 1));
+                cols += 2;
+            }
+        }
+
+        // standard form rows: inequalities with slacks, equalities, finite upper bounds with
+        // slacks
+        let m = m_ub + m_eq + upper_rows.len();
+        let n_std = cols + m_ub + upper_rows.len();
+        let mut a = Array2::zeros((m, n_std));
+        let mut b = Array1::zeros(m);
+        let mut cost = Array1::zeros(n_std);
+        let rows = self
+            .a_ub
+            .rows()
+            .into_iter()
+            .zip(self.b_ub.iter())
+            .chain(self.a_eq.rows().into_iter().zip(self.b_eq.iter()));
+        for (i, (row, bi)) in rows.enumerate() {
+            b[i] = *bi;
+            for (k, sub) in subs.iter().enumerate() {
+                match *sub {
+                    Substitution::Shifted(l, j) => {
+                        a[(i, j)] = row[k];
+                        b[i] -= row[k] * l;
+                    }
+                    Substitution::Mirrored(u, j) => {
+                        a[(i, j)] = -row[k];
+                        b[i] -= row[k] * u;
+                    }
+                    Substitution::Free(p, q) => {
+                        a[(i, p)] = row[k];
+                        a[(i, q)] = -row[k];
+                    }
+                }
+            }
+            if i < m_ub {
+                a[(i, cols + i)] = 1.0;
+            }
+        }
+        for (r, (k, width)) in upper_rows.iter().enumerate() {
+            let i = m_ub + m_eq + r;
+            if let Substitution::Shifted(_, j) = subs[*k] {
+                a[(i, j)] = 1.0;
+            }
+            a[(i, cols + m_ub + r)] = 1.0;
+            b[i] = *width;
+        }
+        for (k, sub) in subs.iter().enumerate() {
+            match *sub {
+                Substitution::Shifted(_, j) => cost[j] = self.c[k],
+                Substitution::Mirrored(_, j) => cost[j] = -self.c[k],
+                Substitution::Free(p, q) => {
+                    cost[p] = self.c[k];
+                    cost[q] = -self.c[k];
+                }
+            }
+        }
+
+        // rows with negative right hand side are negated, so that the artificial basis is
+        // feasible
+        let signs: Array1<f64> = b.mapv(|e: f64| if e < 0.0 { -1.0 } else { 1.0 });
+        for (mut row, s) in a.rows_mut().into_iter().zip(signs.iter()) {
+            row.mapv_inplace(|e| e * s);
+        }
+        b *= &signs;
+
+        let mut simplex = Simplex::new(a, b);
+        let scale = simplex.b.iter().fold(1.0, |acc: f64, e| acc.max(e.abs()));
+
+        // phase 1 minimizes the sum of the artificial variables
+        let phase1_cost = Array1::from_shape_fn(n_std + m, |j| if j >= n_std { 1.0 } else { 0.0 });
+        let mut status = simplex.run(&phase1_cost, n_std + m, self.max_iter);
+        if status == LinprogStatus::Optimal {
+            let infeasibility = simplex.objective(&phase1_cost);
+            if infeasibility > TOL * scale {
+                status = LinprogStatus::Infeasible;
+            } else {
+                simplex.drive_out_artificials(n_std);
+                let mut phase2_cost = Array1::zeros(n_std + m);
+                phase2_cost.slice_mut(s![..n_std]).assign(&cost);
+                let remaining = self.max_iter.saturating_sub(simplex.iterations);
+                status = simplex.run(&phase2_cost, n_std, remaining);
+            }
+        } else if status == LinprogStatus::Unbounded {
+            // the phase 1 objective is bounded below by zero
+            status = LinprogStatus::IterationLimit;
+        }
+
+        // recover the original variables and duals
+        let x_std = simplex.solution();
+        let x = Array1::from_shape_fn(n, |k| match subs[k] {
+            Substitution::Shifted(l, j) => l + x_std[j],
+            Substitution::Mirrored(u, j) => u - x_std[j],
+            Substitution::Free(p, q) => x_std[p] - x_std[q],
+        });
+        let mut cost_full = Array1::zeros(n_std + m);
+        cost_full.slice_mut(s![..n_std]).assign(&cost);
+        let y = simplex.duals(&cost_full) * &signs;
+        let ineq_duals = y.slice(s![..m_ub]).to_owned();
+        let eq_duals = y.slice(s![m_ub..m_ub + m_eq]).to_owned();
+        let reduced_costs =
+            &self.c - &self.a_ub.t().dot(&ineq_duals) - self.a_eq.t().dot(&eq_duals);
+
+        LinprogResult {
+            status,
+            objective: self.c.dot(&x),
+            x,
+            ineq_duals,
+            eq_duals,
+            reduced_costs,
+            iterations: simplex.iterations,
+        }
+    }
+}
+
+/// Revised simplex iteration on `A x = b`, `x >= 0` with `b >= 0`, starting from the basis of
+/// artificial variables appended as identity columns
+struct Simplex {
+    a: Array2<f64>,
+    b: Array1<f64>,
+    basis: Vec<usize>,
+    binv: Array2<f64>,
+    iterations: usize,
+    updates: usize,
+}
+
+impl Simplex {
+    fn new(a: Array2<f64>, b: Array1<f64>) -> Self {
+        let (m, n) = a.dim();
+        let mut full = Array2::zeros((m, n + m));
+        full.slice_mut(s![.., ..n]).assign(&a);
+        full.slice_mut(s![.., n..]).assign(&Array2::eye(m));
+
+        Simplex {
+            a: full,
+            b,
+            basis: (n..n + m).collect(),
+            binv: Array2::eye(m),
+            iterations: 0,
+            updates: 0,
+        }
+    }
+
+    /// Return the values of the basic variables
+    fn basic_values(&self) -> Array1<f64> {
+        self.binv.dot(&self.b)
+    }
+
+    /// Return the values of all variables
+    fn solution(&self) -> Array1<f64> {
+        let mut x = Array1::zeros(self.a.ncols());
+        for (i, v) in self.basis.iter().zip(self.basic_values()) {
+            x[*i] = v.max(0.0);
+        }
+        x
+    }
+
+    fn objective(&self, cost: &Array1<f64>) -> f64 {
+        cost.dot(&self.solution())
+    }
+
+    /// Return the simplex multipliers `y = B^-T c_B`
+    fn duals(&self, cost: &Array1<f64>) -> Array1<f64> {
+        let c_b: Array1<f64> = self.basis.iter().map(|j| cost[*j]).collect();
+        self.binv.t().dot(&c_b)
+    }
+
+    /// Iterate with Bland's rule, letting only the first *eligible* columns enter the basis
+    fn run(&mut self, cost: &Array1<f64>, eligible: usize, max_iter: usize) -> LinprogStatus {
+        for _ in 0..max_iter {
+            let y = self.duals(cost);
+            let entering = (0..eligible)
+                .find(|j| !self.basis.contains(j) && cost[*j] - self.a.column(*j).dot(&y) < -TOL);
+            let Some(j) = entering else {
+                return LinprogStatus::Optimal;
+            };
+
+            let w = self.binv.dot(&self.a.column(j));
+            let x_b = self.basic_values();
+            let mut leaving: Option<(usize, f64)> = None;
+            for (r, (wr, xr)) in w.iter().zip(x_b.iter()).enumerate() {
+                if *wr <= TOL {
+                    continue;
+                }
+                let ratio = xr.max(0.0) / wr;
+                leaving = match leaving {
+                    Some((l, best))
+                        if ratio > best + TOL
+                            || (ratio >= best - TOL && self.basis[l] < self.basis[r]) =>
+                    {
+                        Some((l, best))
+                    }
+                    _ => Some((r, ratio)),
+                };
+            }
+            let Some((r, _)) = leaving else {
+                return LinprogStatus::Unbounded;
+            };
+
+            self.pivot(r, j, &w);
+            self.iterations += 1;
+        }
+
+        LinprogStatus::IterationLimit
+    }
+
+    /// Replace the basic variable of row *r* by *j*, where *w* is `B^-1 a_j`
+    fn pivot(&mut self, r: usize, j: usize, w: &Array1<f64>) {
+        self.basis[r] = j;
+        self.updates += 1;
+        if self.updates >= REFACTOR {
+            let b = self.a.select(Axis(1), &self.basis);
+            if let Ok(binv) = invert(&b) {
+                self.binv = binv;
+                self.updates = 0;
+                return;
+            }
+        }
+
+        let pivot_row = self.binv.row(r).mapv(|e| e / w[r]);
+        for (i, mut row) in self.binv.rows_mut().into_iter().enumerate() {
+            if i == r {
+                row.assign(&pivot_row);
+            } else if w[i] != 0.0 {
+                row.scaled_add(-w[i], &pivot_row);
+            }
+        }
+    }
+
+    /// Replace artificial variables that remain basic at level zero by structural variables,
+    /// leaving them only in rows that are linearly dependent on the others
+    fn drive_out_artificials(&mut self, n: usize) {
+        for r in 0..self.basis.len() {
+            if self.basis[r] < n {
+                continue;
+            }
+            let row = self.binv.row(r).dot(&self.a.slice(s![.., ..n]));
+            let candidate = (0..n).find(|j| !self.basis.contains(j) && row[*j].abs() > TOL);
+            if let Some(j) = candidate {
+                let w = self.binv.dot(&self.a.column(j));
+                self.pivot(r, j, &w);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array1<f64>, b: &Array1<f64>) {
+        assert!((a - b).iter().all(|e| e.abs() < 1e-9), "{a} != {b}");
+    }
+
+    #[test]
+    fn test_linprog_equalities_and_bounds() {
+        // min x + 2y - z with x + y + z = 4, x - y >= -1, -1 <= y <= 1, z <= 2 and x free
+        let res = LinearProgram::new(array![1.0, 2.0, -1.0])
+            .inequalities(array![[-1.0, 1.0, 0.0]], array![1.0])
+            .equalities(array![[1.0, 1.0, 1.0]], array![4.0])
+            .bounds(vec![
+                (f64::NEG_INFINITY, f64::INFINITY),
+                (-1.0, 1.0),
+                (f64::NEG_INFINITY, 2.0),
+            ])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Optimal);
+        assert_close(&res.x, &array![3.0, -1.0, 2.0]);
+        assert!((res.objective + 1.0).abs() < 1e-9);
+        assert_close(&res.ineq_duals, &array![0.0]);
+        assert_close(&res.eq_duals, &array![1.0]);
+        assert_close(&res.reduced_costs, &array![0.0, 1.0, -2.0]);
+    }
+
+    #[test]
+    fn test_linprog_degenerate() {
+        // Beale's example, on which the simplex method with Dantzig's rule cycles
+        let res = LinearProgram::new(array![-0.75, 150.0, -0.02, 6.0])
+            .inequalities(
+                array![
+                    [0.25, -60.0, -0.04, 9.0],
+                    [0.5, -90.0, -0.02, 3.0],
+                    [0.0, 0.0, 1.0, 0.0]
+                ],
+                array![0.0, 0.0, 1.0],
+            )
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Optimal);
+        assert_close(&res.x, &array![0.04, 0.0, 1.0, 0.0]);
+        assert!((res.objective + 0.05).abs() < 1e-9);
+        // strong duality
+        assert!((res.ineq_duals.dot(&array![0.0, 0.0, 1.0]) - res.objective).abs() < 1e-9);
+        assert!(res.ineq_duals.iter().all(|y| *y <= 1e-12));
+        assert!(res.reduced_costs.iter().all(|r| *r >= -1e-9));
+    }
+
+    #[test]
+    fn test_linprog_redundant_equalities() {
+        let res = LinearProgram::new(array![1.0, -1.0])
+            .equalities(array![[1.0, 1.0], [2.0, 2.0]], array![2.0, 4.0])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Optimal);
+        assert_close(&res.x, &array![0.0, 2.0]);
+        assert!((res.objective + 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_linprog_infeasible() {
+        let res = LinearProgram::new(array![1.0, 1.0])
+            .inequalities(array![[1.0, 1.0], [-1.0, -1.0]], array![1.0, -3.0])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Infeasible);
+
+        let res = LinearProgram::new(array![1.0])
+            .equalities(array![[1.0]], array![-1.0])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_linprog_unbounded() {
+        let res = LinearProgram::new(array![-1.0, 0.0])
+            .inequalities(array![[1.0, -1.0]], array![1.0])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Unbounded);
+
+        let res = LinearProgram::new(array![1.0])
+            .bounds(vec![(f64::NEG_INFINITY, 0.0)])
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::Unbounded);
+    }
+
+    #[test]
+    fn test_linprog_iteration_limit() {
+        let res = LinearProgram::new(array![-3.0, -5.0])
+            .inequalities(
+                array![[1.0, 0.0], [0.0, 2.0], [3.0, 2.0]],
+                array![4.0, 12.0, 18.0],
+            )
+            .max_iter(1)
+            .solve();
+
+        assert_eq!(res.status, LinprogStatus::IterationLimit);
+    }
+}
@@ -0,0 +1,594 @@
+//! Quadratic programming
+//!
+//! Solves the convex problem `min 1/2 x^T Q x + c^T x` subject to `A_ub x <= b_ub`,
+//! `A_eq x = b_eq` and `l <= x <= u`. All constraints are stacked into `l <= A x <= u` and the
+//! problem is solved with the alternating direction method of multipliers (ADMM) in the form of
+//! OSQP. The ADMM iterate is then polished by solving the KKT system on the guessed active set,
+//! which usually recovers the solution to machine precision.
+
+use ndarray::prelude::*;
+
+use crate::linalg::cholesky::Cholesky;
+use crate::linalg::lu::LU;
+
+/// Proximal regularization of the ADMM linear system
+const SIGMA: f64 = 1e-6;
+/// Over-relaxation parameter
+const ALPHA: f64 = 1.6;
+/// Initial penalty parameter
+const RHO: f64 = 0.1;
+/// Factor by which the penalty of equality constraints exceeds the others
+const RHO_EQ: f64 = 1e3;
+/// Number of iterations between updates of the penalty parameter
+const ADAPT_INTERVAL: usize = 25;
+/// Relative tolerance of the infeasibility certificates
+const CERTIFICATE_TOL: f64 = 1e-7;
+/// Regularization of the KKT system in the polishing step
+const POLISH_DELTA: f64 = 1e-9;
+/// Number of iterative refinement steps in the polishing step
+const POLISH_REFINE: usize = 5;
+
+/// Termination status of [`QuadraticProgram::solve`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QpStatus {
+    /// An optimal solution was found
+    Optimal,
+    /// No point satisfies the constraints
+    Infeasible,
+    /// The objective is unbounded below on the feasible set
+    Unbounded,
+    /// `Q` is not positive semidefinite
+    NonConvex,
+    /// The iteration limit was reached
+    IterationLimit,
+}
+
+/// Solution of a [`QuadraticProgram`]
+///
+/// The point, objective and dual values are only meaningful if the status is
+/// [`QpStatus::Optimal`].
+#[derive(Debug, Clone)]
+pub struct QpResult {
+    /// Termination status
+    pub status: QpStatus,
+    /// Minimizer
+    pub x: Array1<f64>,
+    /// Objective value `1/2 x^T Q x + c^T x`
+    pub objective: f64,
+    /// Dual values of the inequality constraints, the sensitivities `d objective / d b_ub`,
+    /// which are non-positive
+    pub ineq_duals: Array1<f64>,
+    /// Dual values of the equality constraints, the sensitivities `d objective / d b_eq`
+    pub eq_duals: Array1<f64>,
+    /// Dual values of the bounds, which are non-negative at active lower bounds and
+    /// non-positive at active upper bounds
+    pub bound_duals: Array1<f64>,
+    /// Number of ADMM iterations
+    pub iterations: usize,
+}
+
+/// Convex quadratic program `min 1/2 x^T Q x + c^T x` subject to `A_ub x <= b_ub`,
+/// `A_eq x = b_eq` and `l <= x <= u`
+///
+/// `Q` has to be symmetric and positive semidefinite. The variables are free unless bounds are
+/// given, and bounds may be infinite.
+///
+/// # Example
+/// ```
+/// use ndarray::array;
+///
+/// use scialg::optimize::quadprog::{QpStatus, QuadraticProgram};
+///
+/// // project (1, 2) onto the half-plane x + y <= 1
+/// let res = QuadraticProgram::new(array![[2.0, 0.0], [0.0, 2.0]], array![-2.0, -4.0])
+///     .inequalities(array![[1.0, 1.0]], array![1.0])
+///     .solve();
+///
+/// assert_eq!(res.status, QpStatus::Optimal);
+/// assert!((&res.x - &array![0.0, 1.0]).iter().all(|e| e.abs() < 1e-9));
+/// assert!((res.ineq_duals[0] + 2.0).abs() < 1e-9);
+/// ```
+///
+/// # References
+///  - [Wikipedia: Quadratic programming](https://en.wikipedia.org/wiki/Quadratic_programming)
+///  - B. Stellato et al., *OSQP: an operator splitting solver for quadratic programs*,
+///    Mathematical Programming Computation 12, 2020
+#[derive(Debug, Clone)]
+pub struct QuadraticProgram {
+    q: Array2<f64>,
+    c: Array1<f64>,
+    a_ub: Array2<f64>,
+    b_ub: Array1<f64>,
+    a_eq: Array2<f64>,
+    b_eq: Array1<f64>,
+    bounds: Vec<(f64, f64)>,
+    max_iter: usize,
+    tol: f64,
+}
+
+impl QuadraticProgram {
+    /// Create the unconstrained problem of minimizing `1/2 x^T q x + c^T x`
+    ///
+    /// # Panics
+    /// Panics if *q* is not square or does not match the length of *c*
+    pub fn new(q: Array2<f64>, c: Array1<f64>) -> Self {
+        let n = c.len();
+        assert_eq!(q.dim(), (n, n));
+        QuadraticProgram {
+            q,
+            c,
+            a_ub: Array2::zeros((0, n)),
+            b_ub: Array1::zeros(0),
+            a_eq: Array2::zeros((0, n)),
+            b_eq: Array1::zeros(0),
+            bounds: vec![(f64::NEG_INFINITY, f64::INFINITY); n],
+            max_iter: 10_000,
+            tol: 1e-6,
+        }
+    }
+
+    /// Set the inequality constraints `a x <= b`
+    ///
+    /// # Panics
+    /// Panics if the dimensions of *a* and *b* do not match the number of variables
+    pub fn inequalities(mut self, a: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(a.ncols(), self.c.len());
+        assert_eq!(a.nrows(), b.len());
+        self.a_ub = a;
+        self.b_ub = b;
+        self
+    }
+
+    /// Set the equality constraints `a x = b`
+    ///
+    /// # Panics
+    /// Panics if the dimensions of *a* and *b* do not match the number of variables
+    pub fn equalities(mut self, a: Array2<f64>, b: Array1<f64>) -> Self {
+        assert_eq!(a.ncols(), self.c.len());
+        assert_eq!(a.nrows(), b.len());
+        self.a_eq = a;
+        self.b_eq = b;
+        self
+    }
+
+    /// Set the bounds `lower <= x_i <= upper` of every variable, which may be infinite
+    ///
+    /// # Panics
+    /// Panics if the number of bounds does not match the number of variables or a lower bound
+    /// exceeds its upper bound
+    pub fn bounds(mut self, bounds: Vec<(f64, f64)>) -> Self {
+        assert_eq!(bounds.len(), self.c.len());
+        assert!(bounds.iter().all(|(l, u)| l <= u));
+        self.bounds = bounds;
+        self
+    }
+
+    /// Set the maximum number of ADMM iterations, 10000 by default
+    pub fn max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Set the absolute and relative tolerance of the primal and dual residuals of the ADMM
+    /// iteration, 1e-6 by default
+    pub fn tolerance(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    /// Solve the problem with ADMM and polish the solution
+    pub fn solve(&self) -> QpResult {
+        let n = self.c.len();
+        let m_ub = self.b_ub.len();
+        let m_eq = self.b_eq.len();
+        let boxed: Vec<usize> = (0..n)
+            .filter(|k| self.bounds[*k].0.is_finite() || self.bounds[*k].1.is_finite())
+            .collect();
+
+        // stack all constraints into lower <= a x <= upper
+        let m = m_ub + m_eq + boxed.len();
+        let mut a = Array2::zeros((m, n));
+        a.slice_mut(s![..m_ub, ..]).assign(&self.a_ub);
+        a.slice_mut(s![m_ub..m_ub + m_eq, ..]).assign(&self.a_eq);
+        for (r, k) in boxed.iter().enumerate() {
+            a[(m_ub + m_eq + r, *k)] = 1.0;
+        }
+        let lower: Array1<f64> = std::iter::repeat_n(f64::NEG_INFINITY, m_ub)
+            .chain(self.b_eq.iter().copied())
+            .chain(boxed.iter().map(|k| self.bounds[*k].0))
+            .collect();
+        let upper: Array1<f64> = self
+            .b_ub
+            .iter()
+            .chain(self.b_eq.iter())
+            .copied()
+            .chain(boxed.iter().map(|k| self.bounds[*k].1))
+            .collect();
+
+        // scale the objective so that the largest entry of Q is at most one, which makes the
+        // regularization and the convexity test relative to the size of Q
+        let scale = self.q.iter().fold(1.0, |acc: f64, e| acc.max(e.abs()));
+        let (q, c) = (&self.q / scale, &self.c / scale);
+        let admm = Admm {
+            q: &q,
+            c: &c,
+            a,
+            lower,
+            upper,
+        };
+        let (status, x, y, iterations) = admm.run(self.max_iter, self.tol);
+        let y = y * scale;
+
+        // the ADMM multipliers are the negated sensitivities
+        let bound_rows = y.slice(s![m_ub + m_eq..]);
+        let mut bound_duals = Array1::zeros(n);
+        for (k, yk) in boxed.iter().zip(bound_rows.iter()) {
+            bound_duals[*k] = -yk;
+        }
+
+        QpResult {
+            status,
+            objective: 0.5 * x.dot(&self.q.dot(&x)) + self.c.dot(&x),
+            ineq_duals: y.slice(s![..m_ub]).mapv(|e| -e),
+            eq_duals: y.slice(s![m_ub..m_ub + m_eq]).mapv(|e| -e),
+            bound_duals,
+            x,
+            iterations,
+        }
+    }
+}
+
+/// ADMM iteration on `min 1/2 x^T q x + c^T x` subject to `lower <= a x <= upper`
+struct Admm<'a> {
+    q: &'a Array2<f64>,
+    c: &'a Array1<f64>,
+    a: Array2<f64>,
+    lower: Array1<f64>,
+    upper: Array1<f64>,
+}
+
+/// Residuals of an iterate together with the scales of their tolerances
+struct Residuals {
+    primal: f64,
+    dual: f64,
+    primal_scale: f64,
+    dual_scale: f64,
+}
+
+fn norm_inf(v: &Array1<f64>) -> f64 {
+    v.iter().fold(0.0, |acc: f64, e| acc.max(e.abs()))
+}
+
+impl Admm<'_> {
+    /// Return the penalty parameter of every constraint
+    fn penalties(&self, rho: f64) -> Array1<f64> {
+        Array::from_shape_fn(self.lower.len(), |i| {
+            if self.lower[i] == self.upper[i] {
+                RHO_EQ * rho
+            } else {
+                rho
+            }
+        })
+    }
+
+    /// Return whether `q` is positive semidefinite up to the proximal regularization
+    ///
+    /// The constraint term of the ADMM system can hide negative curvature of `q`, so convexity
+    /// is checked on `q + sigma I` alone. Since the objective is scaled to `|q| <= 1`, sigma
+    /// acts as a relative tolerance.
+    fn is_convex(&self) -> bool {
+        let n = self.c.len();
+        Cholesky::new(&(self.q + &(SIGMA * Array2::eye(n)))).is_ok()
+    }
+
+    /// Factorize `q + sigma I + a^T diag(rho) a`, which fails if `q` is not positive
+    /// semidefinite
+    fn factorize(&self, rho: &Array1<f64>) -> Option<Cholesky<f64>> {
+        let n = self.c.len();
+        let scaled = &self.a * &rho.view().insert_axis(Axis(1));
+        let k = self.q + &(SIGMA * Array2::eye(n)) + self.a.t().dot(&scaled);
+        Cholesky::new(&k).ok()
+    }
+
+    fn project(&self, z: &Array1<f64>) -> Array1<f64> {
+        Array::from_shape_fn(z.len(), |i| z[i].max(self.lower[i]).min(self.upper[i]))
+    }
+
+    fn residuals(&self, x: &Array1<f64>, z: &Array1<f64>, y: &Array1<f64>) -> Residuals {
+        let ax = self.a.dot(x);
+        let qx = self.q.dot(x);
+        let aty = self.a.t().dot(y);
+        Residuals {
+            primal: norm_inf(&(&ax - z)),
+            dual: norm_inf(&(&qx + self.c + &aty)),
+            primal_scale: norm_inf(&ax).max(norm_inf(z)),
+            dual_scale: norm_inf(&qx).max(norm_inf(&aty)).max(norm_inf(self.c)),
+        }
+    }
+
+    /// Check whether the change *dy* of the multipliers certifies primal infeasibility
+    fn primal_infeasible(&self, dy: &Array1<f64>) -> bool {
+        let norm = norm_inf(dy);
+        if norm == 0.0 {
+            return false;
+        }
+        let eps = CERTIFICATE_TOL * norm;
+        let mut support = 0.0;
+        for (i, d) in dy.iter().enumerate() {
+            let bound = if *d > 0.0 {
+                self.upper[i]
+            } else {
+                self.lower[i]
+            };
+            if bound.is_finite() {
+                support += bound * d;
+            } else if d.abs() > eps {
+                return false;
+            }
+        }
+        support < -eps && norm_inf(&self.a.t().dot(dy)) <= eps
+    }
+
+    /// Check whether the change *dx* of the iterate certifies dual infeasibility
+    fn dual_infeasible(&self, dx: &Array1<f64>) -> bool {
+        let norm = norm_inf(dx);
+        if norm == 0.0 {
+            return false;
+        }
+        let eps = CERTIFICATE_TOL * norm;
+        let adx = self.a.dot(dx);
+        let recession = adx.iter().enumerate().all(|(i, e)| {
+            (self.upper[i].is_infinite() || *e <= eps)
+                && (self.lower[i].is_infinite() || *e >= -eps)
+        });
+        recession && self.c.dot(dx) < -eps && norm_inf(&self.q.dot(dx)) <= eps
+    }
+
+    /// Solve the equality constrained problem on the constraints that are active at *z* and
+    /// *y*, returning the solution and its multipliers
+    fn polish(&self, z: &Array1<f64>, y: &Array1<f64>) -> Option<(Array1<f64>, Array1<f64>)> {
+        let n = self.c.len();
+        let at_lower = |i: usize| z[i] - self.lower[i] < -y[i];
+        let at_upper = |i: usize| self.upper[i] - z[i] < y[i];
+        let active: Vec<usize> = (0..z.len())
+            .filter(|i| self.lower[*i] == self.upper[*i] || at_lower(*i) || at_upper(*i))
+            .collect();
+        let p = active.len();
+
+        let a_active = self.a.select(Axis(0), &active);
+        let mut kkt = Array2::zeros((n + p, n + p));
+        kkt.slice_mut(s![..n, ..n]).assign(self.q);
+        kkt.slice_mut(s![..n, n..]).assign(&a_active.t());
+        kkt.slice_mut(s![n.., ..n]).assign(&a_active);
+        let mut regularized = kkt.clone();
+        for i in 0..n + p {
+            regularized[(i, i)] += if i < n { POLISH_DELTA } else { -POLISH_DELTA };
+        }
+        let lu = LU::new(&regularized).ok()?;
+
+        let mut rhs = Array1::zeros(n + p);
+        rhs.slice_mut(s![..n]).assign(&-self.c);
+        for (r, i) in active.iter().enumerate() {
+            rhs[n + r] = if at_upper(*i) {
+                self.upper[*i]
+            } else {
+                self.lower[*i]
+            };
+        }
+        let mut sol = lu.solve(&rhs);
+        for _ in 0..POLISH_REFINE {
+            let correction = lu.solve(&(&rhs - &kkt.dot(&sol)));
+            sol += &correction;
+        }
+
+        let mut y_polished = Array1::zeros(z.len());
+        for (r, i) in active.iter().enumerate() {
+            let yi = sol[n + r];
+            y_polished[*i] = if self.lower[*i] == self.upper[*i] {
+                yi
+            } else if at_upper(*i) {
+                yi.max(0.0)
+            } else {
+                yi.min(0.0)
+            };
+        }
+        Some((sol.slice(s![..n]).to_owned(), y_polished))
+    }
+
+    /// Run the iteration, returning the status, solution, multipliers and iteration count
+    fn run(&self, max_iter: usize, tol: f64) -> (QpStatus, Array1<f64>, Array1<f64>, usize) {
+        let (n, m) = (self.c.len(), self.lower.len());
+        let mut x = Array1::zeros(n);
+        let mut z = self.project(&Array1::zeros(m));
+        let mut y = Array1::zeros(m);
+
+        if !self.is_convex() {
+            return (QpStatus::NonConvex, x, y, 0);
+        }
+        let mut rho = RHO;
+        let mut penalties = self.penalties(rho);
+        let Some(mut chol) = self.factorize(&penalties) else {
+            return (QpStatus::NonConvex, x, y, 0);
+        };
+
+        for k in 1..=max_iter {
+            let rhs = SIGMA * &x - self.c + self.a.t().dot(&(&penalties * &z - &y));
+            let x_tilde = chol.solve(&rhs);
+            let z_tilde = self.a.dot(&x_tilde);
+            let x_next = ALPHA * &x_tilde + (1.0 - ALPHA) * &x;
+            let z_relaxed = ALPHA * &z_tilde + (1.0 - ALPHA) * &z;
+            let z_next = self.project(&(&z_relaxed + &(&y / &penalties)));
+            let y_next = &y + &(&penalties * &(&z_relaxed - &z_next));
+            let dx = &x_next - &x;
+            let dy = &y_next - &y;
+            x = x_next;
+            z = z_next;
+            y = y_next;
+
+            let res = self.residuals(&x, &z, &y);
+            let primal_tol = tol + tol * res.primal_scale;
+            let dual_tol = tol + tol * res.dual_scale;
+            if res.primal <= primal_tol && res.dual <= dual_tol {
+                if let Some((xp, yp)) = self.polish(&z, &y) {
+                    let zp = self.project(&self.a.dot(&xp));
+                    let polished = self.residuals(&xp, &zp, &yp);
+                    if polished.primal <= res.primal.max(tol) && polished.dual <= res.dual.max(tol)
+                    {
+                        return (QpStatus::Optimal, xp, yp, k);
+                    }
+                }
+                return (QpStatus::Optimal, x, y, k);
+            }
+            if self.primal_infeasible(&dy) {
+                return (QpStatus::Infeasible, x, y, k);
+            }
+            if self.dual_infeasible(&dx) {
+                return (QpStatus::Unbounded, x, y, k);
+            }
+
+            // balance the relative primal and dual residuals
+            if k % ADAPT_INTERVAL == 0 {
+                let ratio = (res.primal
+                    / (res.primal_scale + 1e-30)
+                    / (res.dual / (res.dual_scale + 1e-30) + 1e-30))
+                    .sqrt();
+                let next = (rho * ratio).clamp(1e-6, 1e6);
+                if next > 5.0 * rho || next < 0.2 * rho {
+                    rho = next;
+                    penalties = self.penalties(rho);
+                    match self.factorize(&penalties) {
+                        Some(c) => chol = c,
+                        None => return (QpStatus::NonConvex, x, y, k),
+                    }
+                }
+            }
+        }
+
+        (QpStatus::IterationLimit, x, y, max_iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &Array1<f64>, b: &Array1<f64>) {
+        assert!((a - b).iter().all(|e| e.abs() < 1e-8), "{a} != {b}");
+    }
+
+    #[test]
+    fn test_quadprog_unconstrained() {
+        let q = array![[4.0, 1.0], [1.0, 3.0]];
+        let c = array![1.0, 2.0];
+        let res = QuadraticProgram::new(q.clone(), c.clone()).solve();
+
+        assert_eq!(res.status, QpStatus::Optimal);
+        assert_close(&q.dot(&res.x), &-c);
+    }
+
+    #[test]
+    fn test_quadprog_simplex_projection() {
+        // project d onto the probability simplex, a constrained least squares problem
+        let d = array![0.5, 0.8, -0.3];
+        let res = QuadraticProgram::new(2.0 * Array2::eye(3), -2.0 * &d)
+            .equalities(array![[1.0, 1.0, 1.0]], array![1.0])
+            .bounds(vec![(0.0, f64::INFINITY); 3])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::Optimal);
+        assert_close(&res.x, &array![0.35, 0.65, 0.0]);
+        assert_close(&res.eq_duals, &array![-0.3]);
+        assert_close(&res.bound_duals, &array![0.0, 0.0, 0.9]);
+    }
+
+    #[test]
+    fn test_quadprog_linear_objective() {
+        // Q = 0 turns the problem into a linear program
+        let res = QuadraticProgram::new(Array2::zeros((2, 2)), array![-1.0, -1.0])
+            .inequalities(array![[1.0, 2.0]], array![4.0])
+            .bounds(vec![(0.0, 1.0), (0.0, 2.0)])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::Optimal);
+        assert_close(&res.x, &array![1.0, 1.5]);
+        assert!((res.objective + 2.5).abs() < 1e-8);
+        assert_close(&res.ineq_duals, &array![-0.5]);
+        assert_close(&res.bound_duals, &array![-0.5, 0.0]);
+    }
+
+    #[test]
+    fn test_quadprog_portfolio() {
+        // minimize the variance of a long-only portfolio with a required return
+        let cov = array![[0.1, 0.02, 0.0], [0.02, 0.08, 0.01], [0.0, 0.01, 0.2]];
+        let mu = array![0.05, 0.08, 0.12];
+        let res = QuadraticProgram::new(cov.clone(), Array1::zeros(3))
+            .inequalities(array![[-0.05, -0.08, -0.12]], array![-0.08])
+            .equalities(array![[1.0, 1.0, 1.0]], array![1.0])
+            .bounds(vec![(0.0, 1.0); 3])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::Optimal);
+        assert!((res.x.sum() - 1.0).abs() < 1e-8);
+        assert!(mu.dot(&res.x) >= 0.08 - 1e-8);
+        assert!(res.x.iter().all(|w| *w >= -1e-8));
+        // stationarity of the Lagrangian
+        let grad = cov.dot(&res.x)
+            - res.ineq_duals[0] * array![-0.05, -0.08, -0.12]
+            - res.eq_duals[0] * Array1::ones(3)
+            - &res.bound_duals;
+        assert!(grad.iter().all(|e| e.abs() < 1e-8));
+    }
+
+    #[test]
+    fn test_quadprog_infeasible() {
+        let res = QuadraticProgram::new(array![[1.0]], array![0.0])
+            .inequalities(array![[1.0]], array![-1.0])
+            .bounds(vec![(0.0, f64::INFINITY)])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::Infeasible);
+    }
+
+    #[test]
+    fn test_quadprog_unbounded() {
+        let res = QuadraticProgram::new(array![[1.0, 0.0], [0.0, 0.0]], array![0.0, -1.0])
+            .bounds(vec![
+                (f64::NEG_INFINITY, f64::INFINITY),
+                (0.0, f64::INFINITY),
+            ])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::Unbounded);
+    }
+
+    #[test]
+    fn test_quadprog_nonconvex() {
+        let res = QuadraticProgram::new(array![[-1.0, 0.0], [0.0, 1.0]], array![0.0, 0.0]).solve();
+
+        assert_eq!(res.status, QpStatus::NonConvex);
+
+        // the penalty of the bounds outweighs the negative curvature in the ADMM system
+        let res = QuadraticProgram::new(array![[-0.05]], array![0.0])
+            .bounds(vec![(0.0, 1.0)])
+            .solve();
+
+        assert_eq!(res.status, QpStatus::NonConvex);
+    }
+
+    #[test]
+    fn test_quadprog_badly_scaled_least_squares() {
+        // non-negative least squares with a rank deficient design, so Q = A^T A is singular
+        for s in [1.0, 1e5, 1e6] {
+            let a = array![[1.0, 2.0, 3.0], [0.1, 0.7, 1.3]] * s;
+            let b = a.dot(&array![1.0, 0.0, 2.0]);
+            let res = QuadraticProgram::new(a.t().dot(&a), -a.t().dot(&b))
+                .bounds(vec![(0.0, f64::INFINITY); 3])
+                .solve();
+
+            assert_eq!(res.status, QpStatus::Optimal);
+            assert!(res.x.iter().all(|e| *e >= -1e-9));
+            let residual = &a.dot(&res.x) - &b;
+            assert!(residual.dot(&residual).sqrt() <= 1e-6 * b.dot(&b).sqrt());
+        }
+    }
+}